@@ -2,18 +2,18 @@ use crate::{
     clear_color::{ClearColor, ClearColorConfig},
     core_3d::{Camera3d, Opaque3d},
     prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
-    skybox::{SkyboxBindGroup, SkyboxPipelineId},
+    skybox::{
+        skybox_replaces_clear, Skybox, SkyboxBindGroup, SkyboxPipelineId, SkyboxReplacesClear,
+    },
 };
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
     camera::ExtractedCamera,
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_phase::RenderPhase,
-    render_resource::{
-        LoadOp, Operations, PipelineCache, RenderPassDepthStencilAttachment, RenderPassDescriptor,
-    },
+    render_resource::{LoadOp, Operations, RenderPassDepthStencilAttachment, RenderPassDescriptor},
     renderer::RenderContext,
-    view::{ViewDepthTexture, ViewTarget, ViewUniformOffset},
+    view::{ViewDepthTexture, ViewTarget},
 };
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -35,9 +35,12 @@ impl ViewNode for MainOpaquePass3dNode {
         Option<&'static NormalPrepass>,
         Option<&'static MotionVectorPrepass>,
         Option<&'static DeferredPrepass>,
-        Option<&'static SkyboxPipelineId>,
-        Option<&'static SkyboxBindGroup>,
-        &'static ViewUniformOffset,
+        Option<(
+            &'static Skybox,
+            &'static SkyboxPipelineId,
+            Has<SkyboxBindGroup>,
+            &'static SkyboxReplacesClear,
+        )>,
     );
 
     fn run(
@@ -55,21 +58,24 @@ impl ViewNode for MainOpaquePass3dNode {
             normal_prepass,
             motion_vector_prepass,
             deferred_prepass,
-            skybox_pipeline,
-            skybox_bind_group,
-            view_uniform_offset,
+            skybox,
         ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
-        let load = if deferred_prepass.is_none() {
+        let load = if deferred_prepass.is_some() {
+            // If the deferred lighting pass has run, don't clear again in this pass.
+            LoadOp::Load
+        } else if skybox.is_some_and(|(skybox, pipeline_id, has_bind_group, _)| {
+            skybox_replaces_clear(camera, skybox, pipeline_id, has_bind_group, world)
+        }) {
+            // The skybox is drawn over the whole background after this pass anyway.
+            LoadOp::Load
+        } else {
             match camera_3d.clear_color {
                 ClearColorConfig::Default => LoadOp::Clear(world.resource::<ClearColor>().0.into()),
                 ClearColorConfig::Custom(color) => LoadOp::Clear(color.into()),
                 ClearColorConfig::None => LoadOp::Load,
             }
-        } else {
-            // If the deferred lighting pass has run, don't clear again in this pass.
-            LoadOp::Load
         };
 
         // Run the opaque pass, sorted front-to-back
@@ -122,18 +128,6 @@ impl ViewNode for MainOpaquePass3dNode {
             alpha_mask_phase.render(&mut render_pass, world, view_entity);
         }
 
-        // Draw the skybox using a fullscreen triangle
-        if let (Some(skybox_pipeline), Some(skybox_bind_group)) =
-            (skybox_pipeline, skybox_bind_group)
-        {
-            let pipeline_cache = world.resource::<PipelineCache>();
-            if let Some(pipeline) = pipeline_cache.get_render_pipeline(skybox_pipeline.0) {
-                render_pass.set_render_pipeline(pipeline);
-                render_pass.set_bind_group(0, &skybox_bind_group.0, &[view_uniform_offset.offset]);
-                render_pass.draw(0..3, 0..1);
-            }
-        }
-
         Ok(())
     }
 }
@@ -1,4 +1,7 @@
-use crate::core_3d::Transparent3d;
+use crate::{
+    core_3d::Transparent3d,
+    skybox::{Skybox, SkyboxResolve},
+};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
     camera::ExtractedCamera,
@@ -21,17 +24,25 @@ impl ViewNode for MainTransparentPass3dNode {
         &'static RenderPhase<Transparent3d>,
         &'static ViewTarget,
         &'static ViewDepthTexture,
+        Option<&'static Skybox>,
     );
     fn run(
         &self,
         graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (camera, transparent_phase, target, depth): QueryItem<Self::ViewQuery>,
+        (camera, transparent_phase, target, depth, skybox): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.view_entity();
 
-        if !transparent_phase.items.is_empty() {
+        // A skybox can leave the resolve of the multisampled target to this pass, which then
+        // has to run even without transparent items.
+        let resolves_skybox = target.sampled_main_texture().is_some()
+            && skybox.is_some_and(|skybox| {
+                skybox.enabled && skybox.resolve == SkyboxResolve::TransparentPass
+            });
+
+        if !transparent_phase.items.is_empty() || resolves_skybox {
             // Run the transparent pass, sorted back-to-front
             // NOTE: Scoped to drop the mutable borrow of render_context
             #[cfg(feature = "trace")]
@@ -15,6 +15,7 @@ pub mod graph {
         pub const END_PREPASSES: &str = "end_prepasses";
         pub const START_MAIN_PASS: &str = "start_main_pass";
         pub const MAIN_OPAQUE_PASS: &str = "main_opaque_pass";
+        pub const SKYBOX: &str = "skybox";
         pub const MAIN_TRANSPARENT_PASS: &str = "main_transparent_pass";
         pub const END_MAIN_PASS: &str = "end_main_pass";
         pub const BLOOM: &str = "bloom";
@@ -69,7 +70,7 @@ use crate::{
         NormalPrepass, Opaque3dPrepass, ViewPrepassTextures, MOTION_VECTOR_PREPASS_FORMAT,
         NORMAL_PREPASS_FORMAT,
     },
-    skybox::SkyboxPlugin,
+    skybox::{SkyboxNode, SkyboxPlugin},
     tonemapping::TonemappingNode,
     upscaling::UpscalingNode,
 };
@@ -131,6 +132,7 @@ impl Plugin for Core3dPlugin {
                 CORE_3D,
                 MAIN_OPAQUE_PASS,
             )
+            .add_render_graph_node::<ViewNodeRunner<SkyboxNode>>(CORE_3D, SKYBOX)
             .add_render_graph_node::<ViewNodeRunner<MainTransparentPass3dNode>>(
                 CORE_3D,
                 MAIN_TRANSPARENT_PASS,
@@ -148,6 +150,7 @@ impl Plugin for Core3dPlugin {
                     END_PREPASSES,
                     START_MAIN_PASS,
                     MAIN_OPAQUE_PASS,
+                    SKYBOX,
                     MAIN_TRANSPARENT_PASS,
                     END_MAIN_PASS,
                     TONEMAPPING,
@@ -16,7 +16,15 @@ mod taa;
 pub mod tonemapping;
 pub mod upscaling;
 
-pub use skybox::Skybox;
+pub use skybox::{
+    Skybox, SkyboxAlpha, SkyboxAmbient, SkyboxBindGroupLayoutKey, SkyboxBlendMode,
+    SkyboxColorConversion, SkyboxColorSpace, SkyboxDepthConvention, SkyboxDepthRange,
+    SkyboxDetailLayoutKey, SkyboxExclude, SkyboxFaces, SkyboxFog, SkyboxHorizonFade, SkyboxLayers,
+    SkyboxLut, SkyboxNode, SkyboxOnly, SkyboxPipeline, SkyboxPipelineId, SkyboxPipelineKey,
+    SkyboxPrepassPipeline, SkyboxPrepassPipelineId, SkyboxPrepassPipelineKey, SkyboxProjection,
+    SkyboxReplacesClear, SkyboxResolve, SkyboxSampler, SkyboxStencil, SkyboxTransition,
+    SKYBOX_PREPASS_SHADER_HANDLE, SKYBOX_SHADER_HANDLE,
+};
 
 /// Experimental features that are not yet finished. Please report any issues you encounter!
 pub mod experimental {
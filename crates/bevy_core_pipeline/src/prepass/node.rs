@@ -16,6 +16,8 @@ use bevy_render::{
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
 
+use crate::skybox::{draw_skybox_prepass, SkyboxPrepassBindGroup, SkyboxPrepassPipelineId};
+
 use super::{AlphaMask3dPrepass, DeferredPrepass, Opaque3dPrepass, ViewPrepassTextures};
 
 /// Render node used by the prepass.
@@ -32,6 +34,10 @@ impl ViewNode for PrepassNode {
         &'static ViewDepthTexture,
         &'static ViewPrepassTextures,
         Option<&'static DeferredPrepass>,
+        Option<(
+            &'static SkyboxPrepassPipelineId,
+            &'static SkyboxPrepassBindGroup,
+        )>,
     );
 
     fn run(
@@ -45,6 +51,7 @@ impl ViewNode for PrepassNode {
             view_depth_texture,
             view_prepass_textures,
             deferred_prepass,
+            skybox_prepass,
         ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
@@ -118,6 +125,14 @@ impl ViewNode for PrepassNode {
                 let _alpha_mask_prepass_span = info_span!("alpha_mask_prepass").entered();
                 alpha_mask_prepass_phase.render(&mut render_pass, world, view_entity);
             }
+
+            // Write the motion vectors and the depth of the sky, behind all the geometry drawn
+            // so far.
+            if let Some(skybox_prepass) = skybox_prepass {
+                #[cfg(feature = "trace")]
+                let _skybox_prepass_span = info_span!("skybox_prepass").entered();
+                draw_skybox_prepass(&mut render_pass, world, skybox_prepass);
+            }
         }
         if deferred_prepass.is_none() {
             // Copy if deferred isn't going to
@@ -0,0 +1,261 @@
+use bevy_asset::{AssetEvent, AssetId, Assets};
+use bevy_ecs::{
+    event::EventReader,
+    prelude::{Component, Entity},
+    system::{Query, Res},
+};
+use bevy_render::{
+    color::{Color, SrgbColorSpace},
+    render_resource::TextureFormat,
+    texture::Image,
+};
+use bevy_utils::{tracing::warn, HashSet};
+
+use super::{Skybox, SkyboxColorSpace};
+
+/// The approximate ambient color of the sky of a [`Skybox`], for cheap ambient lighting.
+///
+/// Add this to a camera with a [`Skybox`] to compute the average color of the smallest mip
+/// level of [`Skybox::image`], which stands in for the irradiance of the whole sky. This is much
+/// lighter than prefiltering an `EnvironmentMapLight`, e.g. for low-end targets, but it is a
+/// single color, without any direction.
+///
+/// The color is computed on the CPU from the data of the image, once [`Skybox::image`] is loaded
+/// and again each time it changes or is modified, not every frame. The component is only
+/// changed then, so lighting systems can react to it with
+/// [`Changed<SkyboxAmbient>`](bevy_ecs::query::Changed).
+///
+/// The color is linear, and decoded according to [`Skybox::color_space`]. It is the average of
+/// the first six layers of cubemaps, or of the first layer of other images, and doesn't include
+/// the adjustments of the skybox like [`Skybox::brightness`] or [`Skybox::tint`].
+///
+/// Only uncompressed images in the `Rgba8Unorm`, `Bgra8Unorm`, `Rgba16Float`, `Rgba32Float` and
+/// `Rgb9e5Ufloat` formats and their sRGB variants are supported. Other images log a warning and
+/// leave the color unchanged.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SkyboxAmbient {
+    color: Color,
+    source: Option<AssetId<Image>>,
+}
+
+impl Default for SkyboxAmbient {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            source: None,
+        }
+    }
+}
+
+impl SkyboxAmbient {
+    /// Returns the average color of the smallest mip level of the skybox image, or black until
+    /// it is [ready](SkyboxAmbient::is_ready).
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Whether the color was computed from the current [`Skybox::image`].
+    pub fn is_ready(&self) -> bool {
+        self.source.is_some()
+    }
+}
+
+/// Computes the [`SkyboxAmbient`] color of skyboxes whose image changed.
+pub(super) fn update_skybox_ambient(
+    mut image_events: EventReader<AssetEvent<Image>>,
+    images: Res<Assets<Image>>,
+    mut skyboxes: Query<(Entity, &Skybox, &mut SkyboxAmbient)>,
+) {
+    let modified: HashSet<AssetId<Image>> = image_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, skybox, mut ambient) in &mut skyboxes {
+        let id = skybox.image.id();
+        if ambient.source == Some(id) && !modified.contains(&id) {
+            continue;
+        }
+        let Some(image) = images.get(id) else {
+            // Wait for the image to be loaded, keeping the previous color meanwhile.
+            if ambient.source.is_some() {
+                ambient.source = None;
+            }
+            continue;
+        };
+
+        match average_smallest_mip(image, skybox.color_space) {
+            Ok(color) => ambient.color = color,
+            Err(err) => warn!("The ambient color of the skybox of {entity:?} is unknown: {err}"),
+        }
+        // Images that can't be read are only reported once.
+        ambient.source = Some(id);
+    }
+}
+
+/// Returns the average linear color of the smallest mip level of `image`.
+fn average_smallest_mip(
+    image: &Image,
+    color_space: Option<SkyboxColorSpace>,
+) -> Result<Color, String> {
+    let descriptor = &image.texture_descriptor;
+    let format = descriptor.format;
+    let Some(texel_size) = texel_size(format) else {
+        return Err(format!("its format {format:?} is not supported"));
+    };
+    let srgb = color_space.map_or(format.is_srgb(), |color_space| {
+        color_space == SkyboxColorSpace::Srgb
+    });
+
+    let mip_size = |mip: u32| {
+        let width = (descriptor.size.width >> mip).max(1) as usize;
+        let height = (descriptor.size.height >> mip).max(1) as usize;
+        width * height
+    };
+    let last_mip = descriptor.mip_level_count.max(1) - 1;
+    let layer_len = (0..=last_mip).map(mip_size).sum::<usize>() * texel_size;
+    let layers = if descriptor.size.depth_or_array_layers >= 6 {
+        6
+    } else {
+        1
+    };
+    if image.data.len() < layer_len * layers {
+        return Err(format!(
+            "its data is {} bytes, but {layers} layer(s) of {layer_len} bytes were expected",
+            image.data.len()
+        ));
+    }
+
+    // The data of each layer holds all of its mips, from the largest to the smallest.
+    let last_mip_len = mip_size(last_mip) * texel_size;
+    let mut sum = [0.0f64; 4];
+    let mut count = 0;
+    for layer in 0..layers {
+        let end = (layer + 1) * layer_len;
+        for texel in image.data[end - last_mip_len..end].chunks_exact(texel_size) {
+            let mut color = decode_texel(format, texel);
+            if srgb {
+                for channel in &mut color[..3] {
+                    *channel = channel.nonlinear_to_linear_srgb();
+                }
+            }
+            for (sum, channel) in sum.iter_mut().zip(color) {
+                *sum += channel as f64;
+            }
+            count += 1;
+        }
+    }
+
+    let [r, g, b, a] = sum.map(|sum| (sum / count as f64) as f32);
+    Ok(Color::rgba_linear(r, g, b, a))
+}
+
+/// The size in bytes of a texel of the supported formats.
+fn texel_size(format: TextureFormat) -> Option<usize> {
+    match format {
+        TextureFormat::Rgba8Unorm
+        | TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Bgra8Unorm
+        | TextureFormat::Bgra8UnormSrgb
+        | TextureFormat::Rgb9e5Ufloat => Some(4),
+        TextureFormat::Rgba16Float => Some(8),
+        TextureFormat::Rgba32Float => Some(16),
+        _ => None,
+    }
+}
+
+/// Decodes a texel of one of the formats supported by [`texel_size`] to its RGBA channels,
+/// without decoding sRGB.
+fn decode_texel(format: TextureFormat, texel: &[u8]) -> [f32; 4] {
+    let unorm = |byte: u8| byte as f32 / 255.0;
+    match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+            [texel[0], texel[1], texel[2], texel[3]].map(unorm)
+        }
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+            [texel[2], texel[1], texel[0], texel[3]].map(unorm)
+        }
+        TextureFormat::Rgb9e5Ufloat => {
+            let bits = u32::from_le_bytes([texel[0], texel[1], texel[2], texel[3]]);
+            // Three 9 bit mantissas sharing a 5 bit exponent, biased by 15 and the mantissa bits.
+            let scale = 2f32.powi((bits >> 27) as i32 - 15 - 9);
+            let mantissa = |shift: u32| ((bits >> shift) & 0x1ff) as f32 * scale;
+            [mantissa(0), mantissa(9), mantissa(18), 1.0]
+        }
+        TextureFormat::Rgba16Float => {
+            let channel = |index: usize| {
+                f16_to_f32(u16::from_le_bytes([texel[2 * index], texel[2 * index + 1]]))
+            };
+            [channel(0), channel(1), channel(2), channel(3)]
+        }
+        TextureFormat::Rgba32Float => {
+            let channel = |index: usize| {
+                f32::from_le_bytes([
+                    texel[4 * index],
+                    texel[4 * index + 1],
+                    texel[4 * index + 2],
+                    texel[4 * index + 3],
+                ])
+            };
+            [channel(0), channel(1), channel(2), channel(3)]
+        }
+        _ => unreachable!("unsupported formats have no texel size"),
+    }
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = (bits >> 10) & 0x1f;
+    let mantissa = (bits & 0x3ff) as f32;
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exponent as i32 - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_render::{
+        color::Color,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::Image,
+    };
+
+    use super::average_smallest_mip;
+
+    #[test]
+    fn averages_the_smallest_mip_of_each_face() {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 6,
+            },
+            TextureDimension::D2,
+            &[255, 255, 255, 255],
+            TextureFormat::Rgba8Unorm,
+        );
+        // Append a black 1x1 mip to each face, except the last one which is white.
+        image.texture_descriptor.mip_level_count = 2;
+        image.data = (0..6)
+            .flat_map(|face| {
+                let smallest = if face == 5 { 255 } else { 0 };
+                [[255; 4]; 4]
+                    .into_iter()
+                    .chain([[smallest, smallest, smallest, 255]])
+                    .flatten()
+            })
+            .collect();
+
+        let color = average_smallest_mip(&image, None).unwrap();
+        assert_eq!(
+            color,
+            Color::rgba_linear(1.0 / 6.0, 1.0 / 6.0, 1.0 / 6.0, 1.0)
+        );
+    }
+}
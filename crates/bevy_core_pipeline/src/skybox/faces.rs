@@ -0,0 +1,107 @@
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    prelude::{Component, Entity},
+    system::{Commands, Query, ResMut},
+};
+use bevy_render::{
+    render_resource::{TextureDimension, TextureViewDescriptor, TextureViewDimension},
+    texture::Image,
+};
+use bevy_utils::tracing::error;
+
+use super::Skybox;
+
+/// Six separate face images a [`Skybox`] cubemap is assembled from, see [`Skybox::from_faces`].
+///
+/// The faces are in the layer order of cube textures: `+X`, `-X`, `+Y`, `-Y`, `+Z`, `-Z`.
+/// Once all of them are loaded, they are copied into a single cube [`Image`] which replaces
+/// [`Skybox::image`], and this component is removed. The skybox isn't drawn until then.
+///
+/// The faces must be single layer 2D images of the same square size, mip count and format.
+/// The cubemap keeps that format, so faces loaded as sRGB result in an sRGB cubemap and
+/// linear faces in a linear one. It uses the sampler of the first face.
+#[derive(Component, Clone, Debug)]
+pub struct SkyboxFaces(pub [Handle<Image>; 6]);
+
+/// Replaces the image of skyboxes with the cubemap assembled from their [`SkyboxFaces`], once
+/// all faces are loaded.
+pub(super) fn assemble_skybox_faces(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut skyboxes: Query<(Entity, &mut Skybox, &SkyboxFaces)>,
+) {
+    for (entity, mut skybox, skybox_faces) in &mut skyboxes {
+        let Some(faces) = skybox_faces
+            .0
+            .iter()
+            .map(|face| images.get(face))
+            .collect::<Option<Vec<_>>>()
+        else {
+            // Wait for all faces to be loaded.
+            continue;
+        };
+
+        match assemble_cube(&faces) {
+            Ok(cube) => skybox.image = images.add(cube),
+            Err(err) => {
+                error!("The skybox faces of {entity:?} can't be assembled into a cubemap: {err}");
+            }
+        }
+        commands.entity(entity).remove::<SkyboxFaces>();
+    }
+}
+
+/// Copies six single layer face images into the layers of a cube [`Image`].
+fn assemble_cube(faces: &[&Image]) -> Result<Image, String> {
+    let first = &faces[0].texture_descriptor;
+    for (index, face) in faces.iter().enumerate() {
+        let descriptor = &face.texture_descriptor;
+        if descriptor.dimension != TextureDimension::D2
+            || descriptor.size.depth_or_array_layers != 1
+        {
+            return Err(format!("face {index} is not a single layer 2D image"));
+        }
+        if descriptor.size != first.size {
+            return Err(format!(
+                "face {index} is {}x{}, but face 0 is {}x{}",
+                descriptor.size.width, descriptor.size.height, first.size.width, first.size.height
+            ));
+        }
+        if descriptor.format != first.format {
+            return Err(format!(
+                "face {index} has the format {:?}, but face 0 has {:?}",
+                descriptor.format, first.format
+            ));
+        }
+        if descriptor.mip_level_count != first.mip_level_count {
+            return Err(format!(
+                "face {index} has {} mip level(s), but face 0 has {}",
+                descriptor.mip_level_count, first.mip_level_count
+            ));
+        }
+    }
+    if first.size.width != first.size.height {
+        return Err(format!(
+            "the faces must be square, but they are {}x{}",
+            first.size.width, first.size.height
+        ));
+    }
+
+    let mut texture_descriptor = first.clone();
+    texture_descriptor.size.depth_or_array_layers = 6;
+
+    Ok(Image {
+        // The data of array textures is laid out layer by layer, each with all of its mips,
+        // which is exactly the data of the faces one after the other.
+        data: faces
+            .iter()
+            .flat_map(|face| face.data.iter().copied())
+            .collect(),
+        texture_descriptor,
+        sampler_descriptor: faces[0].sampler_descriptor.clone(),
+        texture_view_descriptor: Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        }),
+    })
+}
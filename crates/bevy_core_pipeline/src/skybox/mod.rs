@@ -1,40 +1,92 @@
-use bevy_app::{App, Plugin};
+mod ambient;
+mod faces;
+mod node;
+mod prepass;
+
+pub use ambient::SkyboxAmbient;
+pub use faces::SkyboxFaces;
+pub use node::SkyboxNode;
+pub(crate) use prepass::draw_skybox_prepass;
+pub use prepass::{
+    SkyboxPrepassBindGroup, SkyboxPrepassPipeline, SkyboxPrepassPipelineId,
+    SkyboxPrepassPipelineKey, SkyboxPrepassUniforms, SKYBOX_PREPASS_SHADER_HANDLE,
+};
+
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_asset::{load_internal_asset, Handle};
 use bevy_ecs::{
     prelude::{Component, Entity},
-    query::With,
+    query::{Has, QueryItem, With, Without},
     schedule::IntoSystemConfigs,
-    system::{Commands, Query, Res, ResMut, Resource},
+    system::{Commands, Local, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
 };
+use bevy_math::{Mat3, Mat4, Quat, Vec2, Vec4};
 use bevy_render::{
-    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    camera::ExtractedCamera,
+    color::Color,
+    extract_component::{
+        ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+        UniformComponentPlugin,
+    },
     render_asset::RenderAssets,
     render_resource::{
-        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutDescriptor,
-        BindGroupLayoutEntry, BindingType, BufferBindingType, CachedRenderPipelineId,
+        AddressMode, BindGroup, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry,
+        BindingType, BlendState, Buffer, BufferBindingType, BufferId, CachedRenderPipelineId,
         ColorTargetState, ColorWrites, CompareFunction, DepthBiasState, DepthStencilState,
-        FragmentState, MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor,
-        SamplerBindingType, Shader, ShaderStages, ShaderType, SpecializedRenderPipeline,
-        SpecializedRenderPipelines, StencilFaceState, StencilState, TextureFormat,
-        TextureSampleType, TextureViewDimension, VertexState,
+        DownlevelFlags, DynamicBindGroupEntries, Extent3d, FilterMode, FragmentState,
+        MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor, Sampler,
+        SamplerBindingType, SamplerDescriptor, SamplerId, Shader, ShaderDefVal, ShaderStages,
+        ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines, StencilFaceState,
+        StencilOperation, StencilState, TextureDimension, TextureFormat, TextureSampleType,
+        TextureUsages, TextureViewDimension, TextureViewId, VertexState,
     },
-    renderer::RenderDevice,
+    renderer::{RenderAdapter, RenderDevice},
     texture::{BevyDefault, Image},
-    view::{ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniforms},
-    Render, RenderApp, RenderSet,
+    view::{
+        ExtractedView, Msaa, RenderLayers, ViewTarget, ViewUniform, ViewUniformOffset,
+        ViewUniforms, VisibilitySystems, VisibleEntities,
+    },
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_utils::{
+    tracing::{error, warn},
+    HashMap, HashSet,
 };
+use std::sync::{Mutex, PoisonError};
 
 use crate::core_3d::CORE_3D_DEPTH_FORMAT;
 
-const SKYBOX_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(55594763423201);
+pub const SKYBOX_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(55594763423201);
 
 pub struct SkyboxPlugin;
 
 impl Plugin for SkyboxPlugin {
     fn build(&self, app: &mut App) {
         load_internal_asset!(app, SKYBOX_SHADER_HANDLE, "skybox.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            SKYBOX_PREPASS_SHADER_HANDLE,
+            "skybox_prepass.wgsl",
+            Shader::from_wgsl
+        );
 
-        app.add_plugins(ExtractComponentPlugin::<Skybox>::default());
+        app.add_plugins((
+            ExtractComponentPlugin::<Skybox>::default(),
+            ExtractComponentPlugin::<SkyboxExclude>::default(),
+            ExtractComponentPlugin::<SkyboxReplacesClear>::default(),
+            UniformComponentPlugin::<SkyboxUniforms>::default(),
+            UniformComponentPlugin::<SkyboxFog>::default(),
+            UniformComponentPlugin::<SkyboxPrepassUniforms>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            (
+                faces::assemble_skybox_faces,
+                ambient::update_skybox_ambient.after(faces::assemble_skybox_faces),
+                hide_entities_from_skybox_only_cameras.after(VisibilitySystems::CheckVisibility),
+            ),
+        );
 
         let render_app = match app.get_sub_app_mut(RenderApp) {
             Ok(render_app) => render_app,
@@ -42,113 +94,1713 @@ impl Plugin for SkyboxPlugin {
         };
 
         render_app
+            .init_resource::<SkyboxBindGroups>()
             .init_resource::<SpecializedRenderPipelines<SkyboxPipeline>>()
+            .init_resource::<SpecializedRenderPipelines<SkyboxPrepassPipeline>>()
+            .add_systems(ExtractSchedule, prepass::extract_skybox_prepass_uniforms)
             .add_systems(
                 Render,
                 (
+                    apply_skybox_fov_mip_bias
+                        .in_set(RenderSet::Prepare)
+                        .before(RenderSet::PrepareResources),
                     prepare_skybox_pipelines.in_set(RenderSet::Prepare),
+                    prepass::prepare_skybox_prepass_pipelines.in_set(RenderSet::Prepare),
                     prepare_skybox_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                    prepass::prepare_skybox_prepass_bind_groups
+                        .in_set(RenderSet::PrepareBindGroups),
                 ),
             );
     }
 
     fn finish(&self, app: &mut App) {
-        let render_app = match app.get_sub_app_mut(RenderApp) {
-            Ok(render_app) => render_app,
-            Err(_) => return,
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
         };
 
-        let render_device = render_app.world.resource::<RenderDevice>().clone();
-
-        render_app.insert_resource(SkyboxPipeline::new(&render_device));
+        render_app
+            .init_resource::<SkyboxPipeline>()
+            .init_resource::<SkyboxPrepassPipeline>();
     }
 }
 
-/// Adds a skybox to a 3D camera, based on a cubemap texture.
+/// Adds a skybox to a 3D camera, based on a cubemap or equirectangular texture.
+///
+/// Note that this component does not affect the scene's lighting by itself.
+/// To do so, use `EnvironmentMapLight` alongside this component, or `SkyboxAsEnvironment` to
+/// prefilter one from the skybox image. For a single approximate ambient color instead, see
+/// [`SkyboxAmbient`].
+///
+/// # Deferred rendering
+///
+/// Cameras with a [`DeferredPrepass`](crate::prepass::DeferredPrepass) draw the skybox the
+/// same way as forward ones. The deferred lighting pass writes the lit G-buffer into the
+/// [`ViewTarget`] before the main opaque pass, and the skybox is drawn after it. Its
+/// [`CompareFunction::GreaterEqual`] depth test against the depth written by the deferred
+/// prepass only passes on pixels no geometry covered, so the sky fills exactly the background
+/// left untouched by the lighting pass.
 ///
-/// Note that this component does not (currently) affect the scene's lighting.
-/// To do so, use `EnvironmentMapLight` alongside this component.
+/// # Transparency
+///
+/// The skybox is drawn by the [`SkyboxNode`], after the opaque and alpha mask phases of the
+/// main opaque pass, and before the main transparent pass. Transparent surfaces are blended over
+/// the contents of the [`ViewTarget`] at that point, so glass in front of the sky blends with
+/// the sky color rather than the clear color. The skybox doesn't write depth, which doesn't matter
+/// for the transparent pass: its depth test only compares against opaque geometry, and the sky
+/// is behind everything anyway.
+///
+/// There is no order-independent transparency in this renderer yet. Such a technique would
+/// have to resolve its accumulated transparent layers over the view target after the main
+/// opaque pass, like the main transparent pass does, to keep blending against the sky.
+///
+/// # Motion vectors
+///
+/// Cameras with a [`MotionVectorPrepass`](crate::prepass::MotionVectorPrepass) also draw the
+/// sky into the prepass, as do the ones writing the depth of the sky with
+/// [`Skybox::write_depth`]. There it writes the motion vectors of the background pixels, e.g. so
+/// that TAA doesn't ghost over the sky while the camera turns. The sky is infinitely far away,
+/// so its motion only follows the rotation and projection of the camera between the previous
+/// frame and this one, and is exactly zero for static cameras. Changes of the skybox itself,
+/// like [`Skybox::rotation`] or [`Skybox::pan`], don't move it. The prepass doesn't write any
+/// color, and leaves the normals of the sky at the value they are cleared with.
 ///
 /// See also <https://en.wikipedia.org/wiki/Skybox_(video_games)>.
-#[derive(Component, ExtractComponent, Clone)]
-pub struct Skybox(pub Handle<Image>);
+#[derive(Component, Clone)]
+pub struct Skybox {
+    /// The image to display, ignored while [`Skybox::transition`] is set.
+    ///
+    /// Any floating point format can be used, including block compressed ones like BC6H
+    /// that the device supports. Images that can't be bound as a skybox texture, e.g. with an
+    /// integer format or without [`TextureUsages::TEXTURE_BINDING`], are not drawn and
+    /// reported with an error instead.
+    pub image: Handle<Image>,
+    /// Scale factor applied to the skybox image samples.
+    ///
+    /// A value of `1.0` leaves the image unchanged. This is useful to adjust
+    /// the radiance of an HDR capture without re-authoring the texture.
+    pub brightness: f32,
+    /// Exposure adjustment of the skybox, in stops (EV).
+    ///
+    /// Each stop doubles or halves the skybox radiance, on top of [`Skybox::brightness`].
+    /// The default of `0.0` EV is neutral. The adjustment is applied in the skybox shader,
+    /// so it happens before tonemapping on HDR cameras, and before the conversion to sRGB
+    /// on non-HDR cameras.
+    pub exposure: f32,
+    /// A color multiplied into the skybox, per camera.
+    ///
+    /// This allows showing the same image differently in several cameras, e.g. green
+    /// for a night vision camera. Use [`Color::WHITE`] to leave the image unchanged.
+    pub tint: Color,
+    /// The orientation of the skybox in world space.
+    ///
+    /// This allows spinning the sky independently of the camera, e.g. for a
+    /// day/night cycle or to align a captured panorama with the scene.
+    /// Use [`Quat::IDENTITY`] to leave the cubemap unrotated.
+    pub rotation: Quat,
+    /// An offset, in radians, panning the images across the sky, e.g. for drifting clouds.
+    ///
+    /// `x` turns the images around their `+Y` axis (azimuth) and `y` tilts them around their
+    /// `+X` axis (elevation). This is uploaded as a uniform every frame, so it can be animated
+    /// from a system, e.g. by increasing `x` with the elapsed time. It is applied in the space
+    /// of the images, before [`Skybox::rotation`], so both can be used together. Use
+    /// [`Vec2::ZERO`] to leave the images in place.
+    pub pan: Vec2,
+    /// How [`Skybox::image`] is projected onto the sky.
+    pub projection: SkyboxProjection,
+    /// Whether the images are mirrored vertically, swapping the `+Y` and `-Y` directions.
+    ///
+    /// Tools disagree on which way is up in a cubemap, so this fixes an upside-down sky
+    /// without re-baking the image. It is applied after [`Skybox::rotation`], i.e. in the
+    /// space of the image. It is a shader def rather than a uniform, so toggling it
+    /// specializes another pipeline.
+    pub flip_y: bool,
+    /// The color space the texels of the images are encoded in, overriding the one implied
+    /// by their texture format.
+    ///
+    /// `None` trusts the format, see [`SkyboxColorSpace`] for when that goes wrong.
+    pub color_space: Option<SkyboxColorSpace>,
+    /// An optional crossfade between two images, displayed instead of [`Skybox::image`].
+    ///
+    /// Both images must use the same [`SkyboxProjection`].
+    pub transition: Option<SkyboxTransition>,
+    /// The layer of a cubemap array to display, e.g. to animate the sky by flipping
+    /// through frames.
+    ///
+    /// Cubemap images with more than six layers are bound as cube arrays, and need a
+    /// [`TextureViewDimension::CubeArray`] view. Layers past the end of the array show
+    /// the last one. This is ignored for single cubemaps.
+    pub layer: u32,
+    /// Offset applied to the mip level the skybox image is sampled from.
+    ///
+    /// Positive values sample smaller mips for a blurred sky, e.g. when the image has
+    /// prefiltered mips. The resulting level is clamped to the mips the image has, so
+    /// this has no effect on images without mips. Use `0.0` to sample the image as is.
+    pub mip_bias: f32,
+    /// How strongly the field of view of the camera offsets the mip level the skybox images
+    /// are sampled from, on top of [`Skybox::mip_bias`] and [`SkyboxLayers::mip_bias`].
+    ///
+    /// Zooming in magnifies the sky, so a fixed mip bias tuned for one field of view over-blurs
+    /// it when zoomed in, or aliases when zoomed out. With a strength of `1.0`, the bias is
+    /// lowered by one mip level each time the tangent of half the vertical field of view halves,
+    /// relative to the default 45 degree field of view. Orthographic cameras use their
+    /// [`Skybox::orthographic_fov`], so they get a fixed offset. Use `0.0` to disable it.
+    pub fov_mip_bias: f32,
+    /// Whether the skybox is drawn.
+    ///
+    /// A disabled skybox still has its pipeline and bind group prepared, so toggling
+    /// this every frame (e.g. to hide the background in a photo mode) is cheap, unlike
+    /// removing and re-inserting the component.
+    pub enabled: bool,
+    /// The sampler used to sample the skybox images.
+    pub sampler: SkyboxSampler,
+    /// The layers the skybox belongs to.
+    ///
+    /// The skybox is only drawn if these intersect the camera's [`RenderLayers`]. Like any
+    /// other entity, a camera without [`RenderLayers`] only sees layer `0`. Use
+    /// [`RenderLayers::all()`] to draw the skybox regardless of the camera's layers.
+    pub render_layers: RenderLayers,
+    /// Restricts the skybox to pixels passing a stencil test, e.g. for portals or windows.
+    ///
+    /// `None` ignores the stencil buffer.
+    pub stencil: Option<SkyboxStencil>,
+    /// Whether the sky fades into the fog color of the camera near the horizon.
+    ///
+    /// This keeps the horizon consistent with distance-fogged scenery. It uses the
+    /// [`SkyboxFog`] of the view, which `bevy_pbr` provides for cameras with `FogSettings`,
+    /// and has no effect on cameras without fog.
+    pub fog: bool,
+    /// Fades the sky towards a color below an elevation, e.g. for a hazy horizon in
+    /// cinematic shots.
+    ///
+    /// Unlike [`Skybox::fog`], this doesn't depend on the fog of the camera. The fade is
+    /// applied to the sky color first, and the fog on top of it. `None`, the default,
+    /// doesn't fade the sky, and doesn't compile the fade into the shader.
+    pub horizon_fade: Option<SkyboxHorizonFade>,
+    /// Makes the sky translucent, e.g. to composite the view over a video feed, see
+    /// [`SkyboxAlpha`].
+    ///
+    /// `None`, the default, draws an opaque sky that replaces the background.
+    pub alpha: Option<SkyboxAlpha>,
+    /// Whether the sky writes its depth into the depth prepass of cameras with a
+    /// [`DepthPrepass`](crate::prepass::DepthPrepass), for effects reading the prepass depth.
+    ///
+    /// The sky is drawn into the prepass after all geometry, at the far plane or at the far end
+    /// of its [`Skybox::depth_range`], and the depth test of the [`Skybox::depth_convention`]
+    /// keeps it from overwriting the depth of actual geometry. The prepass is cleared to the
+    /// reverse-z far plane, so this only changes the depth of sky pixels with a depth range, or
+    /// with the standard-z convention.
+    pub write_depth: bool,
+    /// The vertical field of view, in radians, used to spread the sky over the screen
+    /// on orthographic cameras.
+    ///
+    /// Orthographic projections have parallel rays, which would otherwise all sample the
+    /// same direction. This has no effect on perspective cameras.
+    pub orthographic_fov: f32,
+    /// A custom fragment shader replacing the one of the skybox, e.g. to add procedural
+    /// stars on top of the sampled sky.
+    ///
+    /// The shader must provide a `skybox_fragment` entry point taking the
+    /// `@builtin(position)` of the fragment and returning the `@location(0)` color. It is
+    /// specialized with the same shader defs and bind group layout as the built-in
+    /// `skybox.wgsl`, whose group `0` bindings it should copy:
+    ///
+    /// - `0`: the skybox texture, a `texture_cube<f32>` (`texture_cube_array<f32>` with
+    ///   `CUBE_ARRAY`, `texture_2d<f32>` with `EQUIRECTANGULAR`),
+    /// - `1`: its `sampler`,
+    /// - `2`: the `View` uniform,
+    /// - `3`: the skybox uniforms,
+    /// - `4`: the texture blended towards, with `BLEND`,
+    /// - `5`: the skybox fog uniform, with `FOG`,
+    /// - `6`: the [`Skybox::lut`], a `texture_1d<f32>` with `LUT_CURVE`, or a
+    ///   `texture_3d<f32>` with `LUT_CUBE`,
+    /// - `7`: the linear clamping `sampler` of the LUT, with either of them,
+    /// - `8`: the detail cubemap of the [`Skybox::layers`], a `texture_cube<f32>` with `DETAIL`,
+    ///   along with one of `DETAIL_ADD`, `DETAIL_MULTIPLY` or `DETAIL_SCREEN`,
+    /// - `9`: the dedicated `sampler` of the detail, see [`SkyboxLayers::sampler`], with
+    ///   `DETAIL_SAMPLER`,
+    /// - `10`: the [`SkyboxAlpha::mask`], a `texture_2d<f32>` with `ALPHA_MASK`.
+    ///
+    /// Shaders specialized with `ALPHA_OUTPUT` should return premultiplied colors with the
+    /// alpha of the [`Skybox::alpha`], which are blended over the background.
+    /// While the images are loading, the shader is specialized with `SOLID` instead, which
+    /// leaves out bindings `0`, `1`, `4`, `6`, `7`, `8` and `9`, see [`Skybox::loading_color`]. Shaders should
+    /// also honor `FLIP_Y`, see [`Skybox::flip_y`], and `STANDARD_Z` when computing ray
+    /// directions from the inverse projection, see [`Skybox::depth_convention`].
+    ///
+    /// Unused bindings may be left out.
+    pub fragment_shader: Option<Handle<Shader>>,
+    /// A solid color drawn instead of the sky while its images are loading.
+    ///
+    /// This avoids flashing the clear color of the camera while an image is streamed in,
+    /// and switches to the images as soon as they are ready. `None` draws nothing until
+    /// then. The color is drawn as is, without [`Skybox::brightness`] or [`Skybox::tint`].
+    pub loading_color: Option<Color>,
+    /// The maximum luminance of the sky, in the units of the HDR view target.
+    ///
+    /// Captured HDR skies may contain a few extremely bright texels, e.g. the sun, which blow
+    /// out bloom. The color of brighter pixels is scaled down to this luminance, after
+    /// [`Skybox::brightness`] and [`Skybox::tint`] are applied, keeping its hue. `None` leaves
+    /// the sky unclamped, and doesn't compile the clamp into the shader.
+    pub max_luminance: Option<f32>,
+    /// The strength of a screen space dither removing the banding of smooth skies, in steps
+    /// of the 8 bit target, e.g. `1.0`.
+    ///
+    /// This only applies to cameras without [`hdr`](bevy_render::camera::Camera::hdr), whose
+    /// 8 bit target quantizes gradients into visible bands. HDR targets don't band, and are
+    /// dithered by the [`DebandDither`](crate::tonemapping::DebandDither) of tonemapping
+    /// instead, so the dither isn't compiled into their pipeline. `None` disables it.
+    pub dither: Option<f32>,
+    /// The saturation of the sky, e.g. `0.0` for grayscale, or above `1.0` for more vivid
+    /// colors.
+    ///
+    /// This keeps the luminance of the colors. It is applied to the sampled colors, before
+    /// [`Skybox::brightness`] and [`Skybox::tint`]. The default of `1.0` leaves them unchanged.
+    pub saturation: f32,
+    /// The contrast of the sky, above `1.0` to spread colors away from middle gray, or below to
+    /// pull them towards it.
+    ///
+    /// The contrast is applied around middle gray (`0.18`) in logarithmic space, so it works
+    /// the same for HDR images, and is applied after [`Skybox::saturation`]. The default of
+    /// `1.0` leaves the colors unchanged.
+    ///
+    /// The adjustments are only compiled into the shader when either of them is not `1.0`.
+    pub contrast: f32,
+    /// A lookup table shaping the tones of the sky, independently of the tonemapping of the
+    /// view.
+    ///
+    /// A 1D image maps each color channel through the same curve, e.g. to crush or lift the
+    /// midtones, and a 3D image maps the whole color, like a color grading cube. The table is
+    /// indexed with the sampled colors clamped to `0.0..=1.0`, after [`Skybox::saturation`]
+    /// and [`Skybox::contrast`], and before [`Skybox::brightness`] and [`Skybox::tint`], so
+    /// HDR images should be scaled into that range with those adjustments first. The texel
+    /// centers at both ends of the table map the colors `0.0` and `1.0`.
+    ///
+    /// The image must have a filterable floating point format, and is sampled linearly. The
+    /// sky is drawn without the table until it is loaded, and the lookup is only compiled
+    /// into the shader while a table is set.
+    pub lut: Option<Handle<Image>>,
+    /// A detail cubemap layered permanently over the sky, e.g. high-frequency clouds over a
+    /// captured gradient.
+    ///
+    /// Unlike a [`Skybox::transition`], which replaces one image with another, both images
+    /// are always drawn. See [`SkyboxLayers`].
+    pub layers: Option<SkyboxLayers>,
+    /// The depth convention of the camera, which decides where the far plane of the skybox is
+    /// and how its depth test compares against the scene.
+    ///
+    /// Core 3D cameras use [`SkyboxDepthConvention::ReverseZ`], the default. Custom cameras
+    /// with a standard projection, e.g. for some offscreen passes, should use
+    /// [`SkyboxDepthConvention::StandardZ`], or the sky either z-fights with the cleared depth
+    /// or disappears.
+    pub depth_convention: SkyboxDepthConvention,
+    /// The part of the depth buffer the view renders into, for renderers splitting the depth
+    /// range of huge worlds into segments drawn by separate views.
+    ///
+    /// The sky is drawn at the [far](SkyboxDepthRange::far) end of the range instead of the far
+    /// plane of the [`Skybox::depth_convention`], so it lands behind the segment of this view,
+    /// e.g. in the farthest segment. `None`, the default, uses the far plane. Ranges that don't
+    /// fit the convention are reported and ignored, see [`SkyboxDepthRange::is_valid_for`].
+    pub depth_range: Option<SkyboxDepthRange>,
+    /// Whether cubemaps are sampled half a texel inside the edges of their faces, so that
+    /// filtering doesn't bleed across them.
+    ///
+    /// Cube textures are filtered seamlessly across their faces by the Vulkan, Metal, DX12 and
+    /// WebGL2 backends, so this is off by default. Some native OpenGL drivers filter each face
+    /// on its own instead, which blends the edge texels with the clamped border and shows
+    /// visible seams on low resolution cubemaps, e.g. with [`SkyboxSampler::Linear`].
+    ///
+    /// The correction uses the size of the base mip level, so it covers cubemaps without mips,
+    /// or sampled at their base level. Smaller mips still need seamless filtering to avoid
+    /// seams, as does nearest filtering of any level, which never bleeds. The faces are
+    /// stretched by a texel, and equirectangular images are not affected.
+    pub cube_edge_fixup: bool,
+    /// Which pass resolves the sky from the multisampled [`ViewTarget`] into its main texture
+    /// when [`Msaa`] is on.
+    ///
+    /// By default, the [`SkyboxNode`] resolves at the end of its pass, so passes ordered after
+    /// it see the same sky coverage in both textures. Without MSAA, the sky is always drawn
+    /// into the main texture directly.
+    pub resolve: SkyboxResolve,
+}
+
+impl Default for Skybox {
+    fn default() -> Self {
+        Self {
+            image: Handle::default(),
+            brightness: 1.0,
+            exposure: 0.0,
+            tint: Color::WHITE,
+            rotation: Quat::IDENTITY,
+            pan: Vec2::ZERO,
+            projection: SkyboxProjection::default(),
+            flip_y: false,
+            color_space: None,
+            transition: None,
+            layer: 0,
+            mip_bias: 0.0,
+            fov_mip_bias: 0.0,
+            enabled: true,
+            sampler: SkyboxSampler::default(),
+            render_layers: RenderLayers::all(),
+            stencil: None,
+            fog: false,
+            horizon_fade: None,
+            alpha: None,
+            write_depth: false,
+            orthographic_fov: std::f32::consts::FRAC_PI_4,
+            fragment_shader: None,
+            loading_color: None,
+            max_luminance: None,
+            dither: None,
+            saturation: 1.0,
+            contrast: 1.0,
+            lut: None,
+            layers: None,
+            depth_convention: SkyboxDepthConvention::default(),
+            depth_range: None,
+            cube_edge_fixup: false,
+            resolve: SkyboxResolve::default(),
+        }
+    }
+}
+
+impl From<Handle<Image>> for Skybox {
+    fn from(image: Handle<Image>) -> Self {
+        Self::new(image)
+    }
+}
+
+impl Skybox {
+    /// Creates a skybox displaying `image`, with all other settings left at their defaults.
+    pub fn new(image: Handle<Image>) -> Self {
+        Self {
+            image,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a cubemap skybox from six separate face images, in the order `+X`, `-X`, `+Y`,
+    /// `-Y`, `+Z`, `-Z`.
+    ///
+    /// The faces are assembled into [`Skybox::image`] once they are loaded, see
+    /// [`SkyboxFaces`]. Both returned components should be inserted on the camera.
+    pub fn from_faces(faces: [Handle<Image>; 6]) -> (Self, SkyboxFaces) {
+        (Self::default(), SkyboxFaces(faces))
+    }
+
+    /// Returns this [`Skybox`] with a new brightness.
+    #[must_use]
+    pub fn with_brightness(mut self, brightness: f32) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Returns this [`Skybox`] with a new tint.
+    #[must_use]
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Returns this [`Skybox`] with a new rotation.
+    #[must_use]
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Whether the skybox should be drawn for a view with the given layers.
+    fn is_visible_in(&self, view_layers: Option<&RenderLayers>) -> bool {
+        self.render_layers
+            .intersects(view_layers.unwrap_or(&RenderLayers::default()))
+    }
+
+    /// Returns the mip bias added by [`Skybox::fov_mip_bias`] for a view with the given
+    /// projection.
+    fn fov_mip_bias(&self, projection: &Mat4) -> f32 {
+        if self.fov_mip_bias == 0.0 {
+            return 0.0;
+        }
+        let tan_half_fov = if projection.w_axis.w == 1.0 {
+            (self.orthographic_fov * 0.5).tan()
+        } else {
+            projection.y_axis.y.recip()
+        };
+        let reference = (std::f32::consts::FRAC_PI_4 * 0.5).tan();
+        self.fov_mip_bias * (tan_half_fov / reference).log2()
+    }
+
+    /// Returns the [`Skybox::depth_range`], unless it is reversed for the
+    /// [`Skybox::depth_convention`].
+    fn depth_range(&self) -> Option<SkyboxDepthRange> {
+        self.depth_range
+            .filter(|range| range.is_valid_for(self.depth_convention))
+    }
+
+    /// Whether the sky is drawn over every background pixel of the view once it is prepared, see
+    /// [`SkyboxReplacesClear`].
+    fn covers_background(&self) -> bool {
+        self.enabled && self.stencil.is_none() && self.alpha.is_none()
+    }
+
+    /// Whether the shader pulls cube samples away from the face edges, see
+    /// [`Skybox::cube_edge_fixup`].
+    fn uses_cube_edge_fixup(&self, layout: &SkyboxBindGroupLayoutKey) -> bool {
+        self.cube_edge_fixup && layout.projection == SkyboxProjection::Cubemap && !layout.solid
+    }
+
+    /// Returns the image to sample, and the image to blend it towards if a
+    /// transition is in progress.
+    ///
+    /// Transitions sitting at either end only sample a single image, so they
+    /// use the same pipeline as a plain skybox.
+    fn sampled_images(&self) -> (&Handle<Image>, Option<&Handle<Image>>) {
+        match &self.transition {
+            None => (&self.image, None),
+            Some(transition) if transition.t <= 0.0 => (&transition.from, None),
+            Some(transition) if transition.t >= 1.0 => (&transition.to, None),
+            Some(transition) => (&transition.from, Some(&transition.to)),
+        }
+    }
+}
+
+/// Restricts a camera with a [`Skybox`] to only draw the sky, without any geometry.
+///
+/// Together with a [`RenderTarget::Image`](bevy_render::camera::RenderTarget::Image), this
+/// captures the skybox into an offscreen image, e.g. for the background of a minimap or as a
+/// reflection source. The image handle of the target can then be used like any other image,
+/// e.g. in a material. The camera still runs its render graph, with the same skybox pipeline as
+/// other cameras, but no entity is visible to it, so its geometry passes draw nothing.
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct SkyboxOnly;
+
+/// The depth convention of a camera drawing a [`Skybox`], see [`Skybox::depth_convention`].
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+pub enum SkyboxDepthConvention {
+    /// The far plane is at a depth of `0.0`, as with the reverse-z projections of core 3D.
+    #[default]
+    ReverseZ,
+    /// The far plane is at a depth of `1.0`, as with standard projections.
+    StandardZ,
+}
+
+impl SkyboxDepthConvention {
+    /// The depth comparison that only passes on pixels where nothing is closer than the far
+    /// plane.
+    pub fn depth_compare(self) -> CompareFunction {
+        match self {
+            SkyboxDepthConvention::ReverseZ => CompareFunction::GreaterEqual,
+            SkyboxDepthConvention::StandardZ => CompareFunction::LessEqual,
+        }
+    }
+
+    /// The depth of the far plane.
+    pub fn far_plane(self) -> f32 {
+        match self {
+            SkyboxDepthConvention::ReverseZ => 0.0,
+            SkyboxDepthConvention::StandardZ => 1.0,
+        }
+    }
+}
+
+/// A segment of the depth buffer, see [`Skybox::depth_range`].
+///
+/// Both ends are depth values as written to the depth buffer, so with reverse-z the `near` end is
+/// greater than the `far` end, e.g. `1.0` and `0.5` for the nearest of two segments splitting
+/// the depth buffer in half. The depth test of the skybox still uses the comparison of the
+/// [`SkyboxDepthConvention`], so the sky is only drawn where nothing of the segment, or of the
+/// segments in front of it, is closer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkyboxDepthRange {
+    /// The depth of the near end of the segment.
+    pub near: f32,
+    /// The depth of the far end of the segment, where the sky is drawn.
+    pub far: f32,
+}
+
+impl SkyboxDepthRange {
+    /// Whether the range goes from near to far in the direction of `convention`, within
+    /// `0.0..=1.0`.
+    pub fn is_valid_for(self, convention: SkyboxDepthConvention) -> bool {
+        let in_range = (0.0..=1.0).contains(&self.near) && (0.0..=1.0).contains(&self.far);
+        in_range
+            && match convention {
+                SkyboxDepthConvention::ReverseZ => self.near > self.far,
+                SkyboxDepthConvention::StandardZ => self.near < self.far,
+            }
+    }
+}
+
+/// The pass that resolves a multisampled [`Skybox`] into the main texture of the
+/// [`ViewTarget`], see [`Skybox::resolve`].
+///
+/// With MSAA, the skybox is drawn into the multisampled texture of the view, like the other
+/// main passes. Passes reading the main texture only see the sky once a pass has resolved the
+/// multisampled texture into it.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+pub enum SkyboxResolve {
+    /// The skybox pass resolves at its end, so the sky is in both textures right after the
+    /// [`SkyboxNode`].
+    #[default]
+    SkyboxPass,
+    /// The skybox pass only writes the multisampled texture, which saves a resolve. The sky
+    /// lands in the main texture with the resolve of the main transparent pass, so custom passes
+    /// between the [`SkyboxNode`] and the main transparent pass only see it in the multisampled
+    /// texture. The main transparent pass then runs even without transparent items.
+    TransparentPass,
+}
+
+impl SkyboxResolve {
+    /// Whether the sky is in the main texture of the view right after the [`SkyboxNode`].
+    pub fn writes_main_texture(self, msaa: Msaa) -> bool {
+        msaa == Msaa::Off || self == SkyboxResolve::SkyboxPass
+    }
+}
+
+/// Prevents a camera from drawing its [`Skybox`], even though it has one.
+///
+/// This is an opt-out for setups where several cameras share the same components, e.g. an
+/// overlay camera spawned with the same bundle as the main camera. Unlike removing the
+/// [`Skybox`], the component can be toggled without losing the skybox settings of the camera.
+#[derive(Component, ExtractComponent, Clone, Copy, Default, Debug)]
+pub struct SkyboxExclude;
+
+/// Skips clearing the view target of a camera whose [`Skybox`] is drawn over all of its
+/// background anyway, which saves filling the screen twice.
+///
+/// The main opaque pass then keeps the previous contents of the target instead of clearing it
+/// with the [`ClearColorConfig`](crate::clear_color::ClearColorConfig) of the camera, and the
+/// skybox overwrites every pixel no geometry covered. The target is still cleared on frames
+/// where the sky doesn't cover the whole background:
+///
+/// - while the skybox is disabled, hidden from the camera by its render layers, excluded with
+///   [`SkyboxExclude`], or not ready to be drawn, e.g. while its images are loading without a
+///   [`Skybox::loading_color`],
+/// - if it is masked by a [`Skybox::stencil`],
+/// - if the camera renders to a viewport, which only covers part of the target.
+///
+/// A custom [`Skybox::fragment_shader`] that discards fragments must not be combined with this.
+#[derive(Component, ExtractComponent, Clone, Copy, Default, Debug)]
+pub struct SkyboxReplacesClear;
+
+/// Whether the sky of a view with [`SkyboxReplacesClear`] is drawn over all of its background
+/// this frame, so that the main opaque pass doesn't need to clear the view target.
+///
+/// `has_bind_group` is whether the view has a [`SkyboxBindGroup`], which the [`SkyboxNode`]
+/// needs as much as the pipeline to draw anything.
+pub(crate) fn skybox_replaces_clear(
+    camera: &ExtractedCamera,
+    skybox: &Skybox,
+    pipeline_id: &SkyboxPipelineId,
+    has_bind_group: bool,
+    world: &World,
+) -> bool {
+    let pipeline_ready = world
+        .resource::<PipelineCache>()
+        .get_render_pipeline(pipeline_id.0)
+        .is_some();
+    sky_covers_view(
+        skybox,
+        camera.viewport.is_some(),
+        pipeline_ready,
+        has_bind_group,
+    )
+}
+
+/// Whether `skybox` is drawn over the whole background of its view, given whether the view is
+/// rendered to a viewport and whether its pipeline and bind group are ready.
+fn sky_covers_view(
+    skybox: &Skybox,
+    has_viewport: bool,
+    pipeline_ready: bool,
+    has_bind_group: bool,
+) -> bool {
+    !has_viewport && pipeline_ready && has_bind_group && skybox.covers_background()
+}
+
+/// The filter of the views whose [`Skybox`] is drawn.
+type SkyboxViewFilter = Without<SkyboxExclude>;
+
+/// Hides all entities from [`SkyboxOnly`] cameras, so that no geometry is queued for them.
+fn hide_entities_from_skybox_only_cameras(
+    mut cameras: Query<&mut VisibleEntities, With<SkyboxOnly>>,
+) {
+    for mut visible_entities in &mut cameras {
+        visible_entities.entities.clear();
+    }
+}
+
+/// The alpha channel written by a translucent [`Skybox`], see [`Skybox::alpha`].
+///
+/// The sky is output with premultiplied alpha, and blended over the background of the view with
+/// [`BlendState::PREMULTIPLIED_ALPHA_BLENDING`]. Clear the camera to a transparent color so that
+/// the background only holds the sky, and composite the view target with premultiplied alpha
+/// over the external content. The alpha of the skybox images is ignored.
+///
+/// The sky is opaque where the alpha is `1.0`, which is the default, so a skybox with only a
+/// [`SkyboxAlpha::mask`] shows the external content where the mask is black.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkyboxAlpha {
+    /// The alpha of the whole sky, between `0.0` and `1.0`.
+    pub alpha: f32,
+    /// A 2D image stretched over the viewport of the view, whose red channel scales the alpha,
+    /// e.g. a single channel `R8Unorm` mask of where the sky is visible.
+    ///
+    /// The mask is read without filtering, at the texel under each pixel. The sky is drawn
+    /// without it until it is loaded.
+    pub mask: Option<Handle<Image>>,
+}
+
+impl Default for SkyboxAlpha {
+    fn default() -> Self {
+        Self {
+            alpha: 1.0,
+            mask: None,
+        }
+    }
+}
+
+/// A fade of the [`Skybox`] towards a color near the horizon, see [`Skybox::horizon_fade`].
+///
+/// The elevations are angles in radians of the view rays above the horizontal plane of the
+/// world, regardless of the [`Skybox::rotation`]. The sky is unchanged at and above
+/// [`SkyboxHorizonFade::start`], and fades linearly into the color until
+/// [`SkyboxHorizonFade::end`], below which it is fully faded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkyboxHorizonFade {
+    /// The color the sky fades into. Its alpha is the strength of the fade when fully faded.
+    pub color: Color,
+    /// The elevation at which the fade starts, e.g. `0.2` for a little above the horizon.
+    pub start: f32,
+    /// The elevation at which the sky is fully faded, below [`SkyboxHorizonFade::start`].
+    pub end: f32,
+}
+
+impl SkyboxHorizonFade {
+    /// The end elevation passed to the shader, which divides by the length of the range and
+    /// so needs the end below the start.
+    fn shader_end(self) -> f32 {
+        self.end.min(self.start - f32::EPSILON)
+    }
+}
+
+impl Default for SkyboxHorizonFade {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            start: 0.2,
+            end: 0.0,
+        }
+    }
+}
+
+/// A crossfade between two skybox images, e.g. to move from a sunset to a night sky.
+#[derive(Clone)]
+pub struct SkyboxTransition {
+    /// The image shown when `t` is `0.0`.
+    pub from: Handle<Image>,
+    /// The image shown when `t` is `1.0`.
+    pub to: Handle<Image>,
+    /// The blend factor between [`SkyboxTransition::from`] and [`SkyboxTransition::to`].
+    ///
+    /// This is uploaded as a uniform every frame, so it can be animated from a system.
+    pub t: f32,
+}
+
+/// A detail cubemap layered over the image of a [`Skybox`], see [`Skybox::layers`].
+///
+/// The detail is sampled with the same view ray as the sky, after [`Skybox::rotation`],
+/// [`Skybox::pan`] and [`Skybox::flip_y`], and combined with it with the [`SkyboxBlendMode`].
+/// Its alpha channel masks the layer, so transparent texels leave the sky unchanged. The
+/// combined color is then adjusted like the sky alone would be.
+///
+/// The detail must be a single cubemap, whatever the [`Skybox::projection`] of the sky, in
+/// the same color space as the sky. The sky is drawn without it until it is loaded.
+#[derive(Clone)]
+pub struct SkyboxLayers {
+    /// The detail cubemap.
+    pub detail: Handle<Image>,
+    /// How the detail is combined with the sky.
+    pub blend_mode: SkyboxBlendMode,
+    /// The sampler of the detail, or `None` to sample it with the [`Skybox::sampler`] of the
+    /// sky.
+    ///
+    /// A dedicated sampler is bound alongside the one of the sky, e.g. to blur a detail layer
+    /// with trilinear filtering and [`SkyboxLayers::mip_bias`] over a sharp, nearest-filtered
+    /// sky.
+    pub sampler: Option<SkyboxSampler>,
+    /// The bias added to the mip level the detail is sampled at, independently of the
+    /// [`Skybox::mip_bias`] of the sky.
+    pub mip_bias: f32,
+}
+
+impl SkyboxLayers {
+    /// Layers `detail` over the sky with `blend_mode`, sampled like the sky.
+    pub fn new(detail: Handle<Image>, blend_mode: SkyboxBlendMode) -> Self {
+        Self {
+            detail,
+            blend_mode,
+            sampler: None,
+            mip_bias: 0.0,
+        }
+    }
+}
+
+/// How the detail of [`SkyboxLayers`] is combined with the sky.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SkyboxBlendMode {
+    /// Adds the detail to the sky, e.g. for glowing aurorae or stars.
+    #[default]
+    Add,
+    /// Multiplies the sky by the detail, e.g. to darken it with a cloud shadow layer.
+    Multiply,
+    /// Brightens the sky by the detail without exceeding white, e.g. for clouds.
+    ///
+    /// Screening assumes colors in `0.0..=1.0`, so HDR images brighter than that may darken
+    /// instead.
+    Screen,
+}
+
+impl ExtractComponent for Skybox {
+    type Query = &'static Self;
+    // The image of the skybox is only set once its faces are assembled.
+    type Filter = Without<SkyboxFaces>;
+    type Out = (Self, SkyboxUniforms);
+
+    fn extract_component(skybox: QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        Some((
+            skybox.clone(),
+            SkyboxUniforms {
+                // Rotating the skybox is equivalent to rotating the view rays
+                // the other way before sampling.
+                transform: Mat3::from_quat(skybox.rotation.inverse()),
+                brightness: skybox.brightness * skybox.exposure.exp2(),
+                blend: skybox
+                    .transition
+                    .as_ref()
+                    .map_or(0.0, |transition| transition.t.clamp(0.0, 1.0)),
+                tint: skybox.tint.as_linear_rgba_f32().into(),
+                layer: skybox.layer,
+                // WGSL requires sampling biases to be within [-16.0, 15.99].
+                mip_bias: skybox.mip_bias.clamp(-16.0, 15.99),
+                orthographic_scale: (skybox.orthographic_fov * 0.5).tan(),
+                loading_color: skybox
+                    .loading_color
+                    .unwrap_or(Color::NONE)
+                    .as_linear_rgba_f32()
+                    .into(),
+                pan: skybox.pan,
+                max_luminance: skybox.max_luminance.unwrap_or(f32::INFINITY),
+                dither_strength: skybox.dither.unwrap_or(0.0),
+                saturation: skybox.saturation,
+                contrast: skybox.contrast,
+                horizon_fade_color: skybox
+                    .horizon_fade
+                    .map_or(Color::NONE, |fade| fade.color)
+                    .as_linear_rgba_f32()
+                    .into(),
+                horizon_fade_start: skybox.horizon_fade.map_or(0.0, |fade| fade.start),
+                horizon_fade_end: skybox.horizon_fade.map_or(-1.0, |fade| fade.shader_end()),
+                detail_mip_bias: skybox
+                    .layers
+                    .as_ref()
+                    .map_or(0.0, |layers| layers.mip_bias.clamp(-16.0, 15.99)),
+                alpha: skybox
+                    .alpha
+                    .as_ref()
+                    .map_or(1.0, |alpha| alpha.alpha.clamp(0.0, 1.0)),
+                far_depth: skybox.depth_range().map_or(0.0, |range| range.far),
+            },
+        ))
+    }
+}
+
+/// The per-view uniform data extracted from [`Skybox`], used by the skybox shader.
+#[derive(Component, ShaderType, Clone)]
+pub struct SkyboxUniforms {
+    transform: Mat3,
+    brightness: f32,
+    blend: f32,
+    tint: Vec4,
+    layer: u32,
+    mip_bias: f32,
+    orthographic_scale: f32,
+    loading_color: Vec4,
+    pan: Vec2,
+    max_luminance: f32,
+    dither_strength: f32,
+    saturation: f32,
+    contrast: f32,
+    horizon_fade_color: Vec4,
+    horizon_fade_start: f32,
+    horizon_fade_end: f32,
+    detail_mip_bias: f32,
+    alpha: f32,
+    far_depth: f32,
+}
+
+/// The fog a [`Skybox`] fades into near the horizon, when [`Skybox::fog`] is enabled.
+///
+/// This is a render world component of views, inserted during extraction, e.g. by
+/// `bevy_pbr` from the `FogSettings` of the camera.
+#[derive(Component, ShaderType, Clone, Copy, Debug)]
+pub struct SkyboxFog {
+    /// The fog color in linear RGBA. The alpha is the strength of the fog at the horizon.
+    pub color: Vec4,
+}
+
+/// How the [`Skybox`] image is projected onto the sky.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SkyboxProjection {
+    /// The image is a cubemap, i.e. a texture with six layers
+    /// viewed as [`TextureViewDimension::Cube`].
+    #[default]
+    Cubemap,
+    /// The image is a single 2D texture in equirectangular (latitude-longitude) layout,
+    /// typically with a 2:1 aspect ratio, as commonly used for HDRIs.
+    ///
+    /// The horizontal center of the image faces `-Z`, and the left and right edges meet
+    /// behind it at `+Z`. The image's sampler should use
+    /// [`AddressMode::Repeat`](bevy_render::render_resource::AddressMode::Repeat)
+    /// horizontally so that filtering across that seam wraps around correctly.
+    Equirectangular,
+}
+
+/// The color space the texels of [`Skybox`] images are encoded in, see [`Skybox::color_space`].
+///
+/// The skybox shader works with linear colors. Textures with an sRGB format, like
+/// [`TextureFormat::Rgba8UnormSrgb`], are decoded to linear by the GPU when sampled, and other
+/// formats are sampled as is. Images whose format doesn't match their data, e.g. sRGB photos
+/// loaded into a linear format or linear data in an sRGB format, look washed out or too dark.
+/// Overriding the color space converts their samples in the shader instead of re-creating the
+/// image. Only the image being sampled is checked, the blended image of a
+/// [`SkyboxTransition`] is assumed to have the same format.
+///
+/// The output is unaffected: the skybox writes linear colors, which are tonemapped on
+/// [`hdr`](bevy_render::camera::Camera::hdr) cameras, and encoded to sRGB by the
+/// [`TextureFormat::bevy_default()`] target of other cameras.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SkyboxColorSpace {
+    /// The texels are sRGB encoded, as is common for 8 bit LDR images.
+    Srgb,
+    /// The texels are linear, as is common for HDR images.
+    Linear,
+}
+
+impl SkyboxColorSpace {
+    /// Returns the conversion the shader needs to apply to samples of a texture of `format`
+    /// that holds colors in this color space.
+    pub fn conversion_from(self, format: TextureFormat) -> SkyboxColorConversion {
+        match (self, format.is_srgb()) {
+            (SkyboxColorSpace::Srgb, false) => SkyboxColorConversion::DecodeSrgb,
+            (SkyboxColorSpace::Linear, true) => SkyboxColorConversion::EncodeSrgb,
+            _ => SkyboxColorConversion::None,
+        }
+    }
+}
+
+/// The conversion applied to the samples of the skybox images to get linear colors, see
+/// [`SkyboxColorSpace::conversion_from`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SkyboxColorConversion {
+    /// The samples are linear already.
+    #[default]
+    None,
+    /// The samples are sRGB encoded, because the texture format is linear.
+    DecodeSrgb,
+    /// The samples were decoded by the GPU from an sRGB format, but the data was linear.
+    EncodeSrgb,
+}
+
+/// A stencil test restricting where a [`Skybox`] is drawn.
+///
+/// The skybox is drawn where `compare` passes between [`SkyboxStencil::reference`] and the
+/// stencil value of the pixel. The stencil buffer itself is left unchanged.
+///
+/// This requires the depth texture of the view to have a stencil aspect, which the default
+/// [`CORE_3D_DEPTH_FORMAT`] does not. The stencil test is ignored otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SkyboxStencil {
+    /// The reference value the stencil buffer is compared against.
+    pub reference: u32,
+    /// The comparison between the reference value and the stencil buffer.
+    pub compare: CompareFunction,
+}
+
+/// The sampler used for a [`Skybox`].
+///
+/// Apart from [`SkyboxSampler::Image`], these use a dedicated sampler that repeats
+/// horizontally and clamps vertically, which suits both cubemaps and equirectangular images.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SkyboxSampler {
+    /// Use the sampler of the skybox image itself.
+    #[default]
+    Image,
+    /// Nearest-neighbor filtering, e.g. for pixel-art skies.
+    ///
+    /// This is required for images whose format is not filterable, like
+    /// [`TextureFormat::Rgba32Float`] on most devices.
+    Nearest,
+    /// Trilinear filtering.
+    Linear,
+    /// Trilinear filtering with the given maximum anisotropy, between `1` and `16`.
+    ///
+    /// This keeps captured skies sharp near the edges of wide fields of view, and at grazing
+    /// angles of detailed cubemaps. Values outside of that range are clamped to it, and
+    /// devices without anisotropic filtering fall back to trilinear filtering, both with a
+    /// warning the first time the sampler is used.
+    Anisotropic(u16),
+}
+
+impl SkyboxSampler {
+    /// Returns the descriptor of the dedicated sampler, or `None` to use the image's sampler.
+    fn descriptor(self) -> Option<SamplerDescriptor<'static>> {
+        let (filter, anisotropy_clamp) = match self {
+            SkyboxSampler::Image => return None,
+            SkyboxSampler::Nearest => (FilterMode::Nearest, 1),
+            SkyboxSampler::Linear => (FilterMode::Linear, 1),
+            SkyboxSampler::Anisotropic(anisotropy) => (FilterMode::Linear, anisotropy.clamp(1, 16)),
+        };
+
+        Some(SamplerDescriptor {
+            label: Some("skybox_sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: filter,
+            min_filter: filter,
+            mipmap_filter: filter,
+            anisotropy_clamp,
+            ..Default::default()
+        })
+    }
+
+    /// Whether the sampler must be bound as [`SamplerBindingType::Filtering`].
+    ///
+    /// The image's own sampler is unknown when the bind group layout is created, but a
+    /// filtering binding accepts non-filtering samplers too. Anisotropic samplers only use
+    /// linear filters, as wgpu requires, so they are filtering as well.
+    fn is_filtering(self) -> bool {
+        self != SkyboxSampler::Nearest
+    }
+
+    /// Returns a warning if the sampler doesn't filter with the requested anisotropy, because
+    /// it is out of range or the device doesn't support anisotropic filtering.
+    fn anisotropy_warning(self, supports_anisotropy: bool) -> Option<String> {
+        let SkyboxSampler::Anisotropic(anisotropy) = self else {
+            return None;
+        };
+        if !(1..=16).contains(&anisotropy) {
+            Some(format!(
+                "Skybox sampler anisotropy {anisotropy} is outside of the supported range of 1 to \
+                16, it is clamped to {}.",
+                anisotropy.clamp(1, 16)
+            ))
+        } else if anisotropy > 1 && !supports_anisotropy {
+            Some(format!(
+                "Skybox sampler anisotropy {anisotropy} is ignored, the device doesn't support \
+                anisotropic filtering."
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// The parts of a [`SkyboxPipelineKey`] that affect the layout of the skybox bind group.
+///
+/// See [`Skybox::fragment_shader`] for the bindings of each layout.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct SkyboxBindGroupLayoutKey {
+    /// How the skybox image is projected, which decides the dimension of its view.
+    pub projection: SkyboxProjection,
+    /// Whether a second image is bound to blend towards.
+    pub blend: bool,
+    /// Whether the cubemaps are bound as cube arrays.
+    pub array: bool,
+    /// Whether the sampler is bound as [`SamplerBindingType::Filtering`].
+    pub filtering: bool,
+    /// Whether the textures are bound as filterable.
+    pub filterable: bool,
+    /// Whether a [`SkyboxFog`] uniform is bound.
+    pub fog: bool,
+    /// Whether the [`Skybox::loading_color`] is drawn instead of the images, which are
+    /// not bound then.
+    pub solid: bool,
+    /// The kind of [`Skybox::lut`] bound, if any.
+    pub lut: Option<SkyboxLut>,
+    /// The layout of the detail cubemap of the [`Skybox::layers`], if it is bound.
+    pub detail: Option<SkyboxDetailLayoutKey>,
+    /// Whether the [`SkyboxAlpha::mask`] is bound.
+    pub alpha_mask: bool,
+}
+
+/// The parts of a [`SkyboxBindGroupLayoutKey`] binding the detail of the [`Skybox::layers`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct SkyboxDetailLayoutKey {
+    /// Whether the detail texture is bound as filterable.
+    pub filterable: bool,
+    /// Whether the detail has a dedicated sampler, see [`SkyboxLayers::sampler`], and if so,
+    /// whether it is bound as [`SamplerBindingType::Filtering`].
+    pub sampler: Option<bool>,
+}
+
+/// The kind of lookup table of a [`Skybox::lut`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SkyboxLut {
+    /// A 1D table mapping each color channel.
+    Curve,
+    /// A 3D table mapping whole colors.
+    Cube,
+}
+
+impl SkyboxLut {
+    /// Returns the kind of a lookup table texture of the given dimension and format, or an
+    /// error message if it can't be used as one.
+    pub fn new(dimension: TextureDimension, format: TextureFormat) -> Result<Self, String> {
+        let lut = match dimension {
+            TextureDimension::D1 => SkyboxLut::Curve,
+            TextureDimension::D3 => SkyboxLut::Cube,
+            TextureDimension::D2 => {
+                return Err("its LUT is a 2D texture, expected a 1D or 3D texture".to_string())
+            }
+        };
+        match format.sample_type(None) {
+            Some(TextureSampleType::Float { filterable: true }) => Ok(lut),
+            _ => Err(format!(
+                "its LUT format {format:?} is not a filterable floating point format"
+            )),
+        }
+    }
+
+    /// The dimension of the texture view the table is bound with.
+    pub fn view_dimension(self) -> TextureViewDimension {
+        match self {
+            SkyboxLut::Curve => TextureViewDimension::D1,
+            SkyboxLut::Cube => TextureViewDimension::D3,
+        }
+    }
+}
+
+impl SkyboxBindGroupLayoutKey {
+    /// Returns the layout key of `skybox`, or an error message if its image can't be
+    /// bound as a skybox texture.
+    pub fn new(
+        skybox: &Skybox,
+        images: &RenderAssets<Image>,
+        view_has_fog: bool,
+    ) -> Result<Self, String> {
+        // The sky is drawn without its alpha mask while it is loading. The mask doesn't depend
+        // on the sky, so it is also bound while the sky is loading.
+        let alpha_mask = match skybox
+            .alpha
+            .as_ref()
+            .and_then(|alpha| alpha.mask.as_ref())
+            .and_then(|mask| images.get(mask))
+        {
+            Some(mask) => {
+                if mask.texture.dimension() != TextureDimension::D2
+                    || mask.texture.depth_or_array_layers() != 1
+                {
+                    return Err("its alpha mask is not a single 2D texture".into());
+                }
+                check_texture_binding(mask.texture.usage(), mask.texture.sample_count())
+                    .and_then(|()| is_filterable(mask.texture_format, SkyboxSampler::Nearest))
+                    .map_err(|err| format!("its alpha mask can't be bound, {err}"))?;
+                true
+            }
+            None => false,
+        };
+
+        let (image, blend_target) = skybox.sampled_images();
+        let image = images.get(image);
+        let loading =
+            image.is_none() || blend_target.is_some_and(|target| images.get(target).is_none());
+        if loading && skybox.loading_color.is_some() {
+            return Ok(Self {
+                projection: SkyboxProjection::default(),
+                blend: false,
+                array: false,
+                filtering: true,
+                filterable: true,
+                fog: skybox.fog && view_has_fog,
+                solid: true,
+                lut: None,
+                detail: None,
+                alpha_mask,
+            });
+        }
+
+        if let Some(image) = image {
+            check_texture_shape(
+                skybox.projection,
+                image.texture.dimension(),
+                image.texture.size(),
+            )?;
+            check_texture_binding(image.texture.usage(), image.texture.sample_count())?;
+        }
 
+        // The sky is drawn without the table while it is loading.
+        let lut = match skybox.lut.as_ref().and_then(|lut| images.get(lut)) {
+            Some(lut) => {
+                check_texture_binding(lut.texture.usage(), lut.texture.sample_count())
+                    .map_err(|err| format!("its LUT can't be bound, {err}"))?;
+                Some(SkyboxLut::new(lut.texture.dimension(), lut.texture_format)?)
+            }
+            None => None,
+        };
+
+        // The sky is drawn without its detail while it is loading, like without the table.
+        let detail = match skybox.layers.as_ref().and_then(|layers| {
+            images
+                .get(&layers.detail)
+                .map(|detail| (detail, layers.sampler))
+        }) {
+            Some((detail, detail_sampler)) => {
+                let size = detail.texture.size();
+                check_texture_shape(SkyboxProjection::Cubemap, detail.texture.dimension(), size)
+                    .and_then(|()| {
+                        check_texture_binding(detail.texture.usage(), detail.texture.sample_count())
+                    })
+                    .map_err(|err| format!("its detail layer can't be bound, {err}"))?;
+                if size.depth_or_array_layers != 6 {
+                    return Err(format!(
+                        "its detail layer has {} layers, expected a single cubemap",
+                        size.depth_or_array_layers
+                    ));
+                }
+                let sampler = detail_sampler.unwrap_or(skybox.sampler);
+                let filterable = is_filterable(detail.texture_format, sampler)
+                    .map_err(|err| format!("its detail layer can't be sampled, {err}"))?;
+                Some(SkyboxDetailLayoutKey {
+                    filterable,
+                    sampler: detail_sampler.map(SkyboxSampler::is_filtering),
+                })
+            }
+            None => None,
+        };
+        // A detail sampled with the sampler of the sky is bound like the sky.
+        let shared_filterable = match detail {
+            Some(SkyboxDetailLayoutKey {
+                filterable,
+                sampler: None,
+            }) => filterable,
+            _ => true,
+        };
+        let filterable = match image {
+            Some(image) => is_filterable(image.texture_format, skybox.sampler)?,
+            None => true,
+        } && shared_filterable;
+
+        Ok(Self {
+            projection: skybox.projection,
+            blend: blend_target.is_some(),
+            array: skybox.projection == SkyboxProjection::Cubemap
+                && image.is_some_and(|image| image.texture.depth_or_array_layers() > 6),
+            filtering: skybox.sampler.is_filtering(),
+            // Images are checked again once they are loaded.
+            filterable,
+            fog: skybox.fog && view_has_fog,
+            solid: false,
+            lut,
+            detail: detail.map(|detail| SkyboxDetailLayoutKey {
+                // Both textures share the binding type of the sampler of the sky.
+                filterable: if detail.sampler.is_none() {
+                    filterable
+                } else {
+                    detail.filterable
+                },
+                ..detail
+            }),
+            alpha_mask,
+        })
+    }
+}
+
+/// Checks that a texture of the given dimension and size can be bound with `projection`.
+///
+/// Binding e.g. a plain 2D image as a cube texture would otherwise fail wgpu validation.
+fn check_texture_shape(
+    projection: SkyboxProjection,
+    dimension: TextureDimension,
+    size: Extent3d,
+) -> Result<(), String> {
+    let layers = size.depth_or_array_layers;
+    match projection {
+        SkyboxProjection::Cubemap
+            if dimension != TextureDimension::D2 || layers == 0 || layers % 6 != 0 =>
+        {
+            Err(format!(
+                "it is not a cube texture, its dimension is {dimension:?} with {layers} layer(s), \
+                expected a 2D texture with a multiple of 6 layers viewed as a cube"
+            ))
+        }
+        SkyboxProjection::Cubemap if size.width != size.height => Err(format!(
+            "its cube faces must be square, but they are {}x{}",
+            size.width, size.height
+        )),
+        SkyboxProjection::Equirectangular if dimension != TextureDimension::D2 || layers != 1 => {
+            Err(format!(
+                "it is not an equirectangular image, its dimension is {dimension:?} with {layers} \
+                layer(s), expected a single 2D texture"
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a texture with the given usage and sample count can be bound as a sampled
+/// texture, which is otherwise a wgpu validation error when creating the bind group.
+fn check_texture_binding(usage: TextureUsages, sample_count: u32) -> Result<(), String> {
+    if !usage.contains(TextureUsages::TEXTURE_BINDING) {
+        return Err(format!(
+            "its usage {usage:?} doesn't include `TextureUsages::TEXTURE_BINDING`"
+        ));
+    }
+    if sample_count != 1 {
+        return Err(format!(
+            "it is multisampled with {sample_count} samples, which can't be sampled as a skybox"
+        ));
+    }
+    Ok(())
+}
+
+/// Returns whether a skybox texture of `format` is filterable, or an error message if it
+/// can't be sampled with `sampler`.
+///
+/// Block compressed formats are sampled like any other format, BC6H for instance is
+/// filterable floating point.
+fn is_filterable(format: TextureFormat, sampler: SkyboxSampler) -> Result<bool, String> {
+    match format.sample_type(None) {
+        Some(TextureSampleType::Float { filterable: true }) => Ok(true),
+        Some(TextureSampleType::Float { filterable: false }) if !sampler.is_filtering() => {
+            Ok(false)
+        }
+        Some(TextureSampleType::Float { filterable: false }) => Err(format!(
+            "its format {format:?} is not filterable, which requires `SkyboxSampler::Nearest`"
+        )),
+        _ => Err(format!(
+            "its format {format:?} can't be sampled as floating point values"
+        )),
+    }
+}
+
+/// The bind group layouts and samplers shared by the skybox pipelines.
+///
+/// It implements [`SpecializedRenderPipeline`] for a [`SkyboxPipelineKey`], which crates
+/// drawing a variant of the skybox can reuse, e.g. by specializing it and adjusting the
+/// returned descriptor in their own [`SpecializedRenderPipeline`] implementation.
+///
+/// The bind group layout of a [`SkyboxPipelineKey::layout`] is created the first time it is
+/// needed, by [`SkyboxPipeline::bind_group_layout`] or when specializing the key.
 #[derive(Resource)]
-struct SkyboxPipeline {
-    bind_group_layout: BindGroupLayout,
+pub struct SkyboxPipeline {
+    render_device: RenderDevice,
+    bind_group_layouts: Mutex<HashMap<SkyboxBindGroupLayoutKey, BindGroupLayout>>,
+    samplers: HashMap<SkyboxSampler, Sampler>,
+    lut_sampler: Option<Sampler>,
+}
+
+impl FromWorld for SkyboxPipeline {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            render_device: world.resource::<RenderDevice>().clone(),
+            bind_group_layouts: Mutex::default(),
+            samplers: HashMap::default(),
+            lut_sampler: None,
+        }
+    }
 }
 
 impl SkyboxPipeline {
-    fn new(render_device: &RenderDevice) -> Self {
-        let bind_group_layout_descriptor = BindGroupLayoutDescriptor {
-            label: Some("skybox_bind_group_layout"),
-            entries: &[
+    /// Returns the dedicated sampler for `sampler`, creating it the first time it is requested,
+    /// or `None` if the image's own sampler should be used.
+    pub fn sampler(
+        &mut self,
+        render_device: &RenderDevice,
+        sampler: SkyboxSampler,
+    ) -> Option<&Sampler> {
+        let descriptor = sampler.descriptor()?;
+        Some(
+            self.samplers
+                .entry(sampler)
+                .or_insert_with(|| render_device.create_sampler(&descriptor)),
+        )
+    }
+
+    /// Returns the linear clamping sampler of lookup tables, creating it the first time it is
+    /// requested.
+    pub fn lut_sampler(&mut self, render_device: &RenderDevice) -> &Sampler {
+        self.lut_sampler.get_or_insert_with(|| {
+            render_device.create_sampler(&SamplerDescriptor {
+                label: Some("skybox_lut_sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                mipmap_filter: FilterMode::Linear,
+                ..Default::default()
+            })
+        })
+    }
+
+    /// Returns the bind group layout for `key`, creating it the first time it is requested.
+    pub fn bind_group_layout(&self, key: SkyboxBindGroupLayoutKey) -> BindGroupLayout {
+        let mut bind_group_layouts = self
+            .bind_group_layouts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let layout = bind_group_layouts.entry(key).or_insert_with(|| {
+            let view_dimension = match key.projection {
+                SkyboxProjection::Cubemap if key.array => TextureViewDimension::CubeArray,
+                SkyboxProjection::Cubemap => TextureViewDimension::Cube,
+                SkyboxProjection::Equirectangular => TextureViewDimension::D2,
+            };
+
+            let texture_entry = |binding| BindGroupLayoutEntry {
+                binding,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    sample_type: TextureSampleType::Float {
+                        filterable: key.filterable,
+                    },
+                    view_dimension,
+                    multisampled: false,
+                },
+                count: None,
+            };
+
+            let mut entries = vec![
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::VERTEX_FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(ViewUniform::min_size()),
+                    },
+                    count: None,
+                },
                 BindGroupLayoutEntry {
-                    binding: 0,
+                    binding: 3,
+                    // The vertex shader reads the far depth of a `Skybox::depth_range`.
+                    visibility: ShaderStages::VERTEX_FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(SkyboxUniforms::min_size()),
+                    },
+                    count: None,
+                },
+            ];
+            if !key.solid {
+                entries.push(texture_entry(0));
+                entries.push(BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(if key.filtering {
+                        SamplerBindingType::Filtering
+                    } else {
+                        SamplerBindingType::NonFiltering
+                    }),
+                    count: None,
+                });
+            }
+            if key.blend {
+                entries.push(texture_entry(4));
+            }
+            if let Some(detail) = key.detail {
+                entries.push(BindGroupLayoutEntry {
+                    binding: 8,
                     visibility: ShaderStages::FRAGMENT,
                     ty: BindingType::Texture {
-                        sample_type: TextureSampleType::Float { filterable: true },
+                        sample_type: TextureSampleType::Float {
+                            filterable: detail.filterable,
+                        },
                         view_dimension: TextureViewDimension::Cube,
                         multisampled: false,
                     },
                     count: None,
-                },
-                BindGroupLayoutEntry {
-                    binding: 1,
+                });
+                if let Some(filtering) = detail.sampler {
+                    entries.push(BindGroupLayoutEntry {
+                        binding: 9,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Sampler(if filtering {
+                            SamplerBindingType::Filtering
+                        } else {
+                            SamplerBindingType::NonFiltering
+                        }),
+                        count: None,
+                    });
+                }
+            }
+            if key.alpha_mask {
+                entries.push(BindGroupLayoutEntry {
+                    binding: 10,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        // The mask is loaded rather than sampled, so any float format works.
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                });
+            }
+            if let Some(lut) = key.lut {
+                entries.push(BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: lut.view_dimension(),
+                        multisampled: false,
+                    },
+                    count: None,
+                });
+                entries.push(BindGroupLayoutEntry {
+                    binding: 7,
                     visibility: ShaderStages::FRAGMENT,
                     ty: BindingType::Sampler(SamplerBindingType::Filtering),
                     count: None,
-                },
-                BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: ShaderStages::VERTEX_FRAGMENT,
+                });
+            }
+            if key.fog {
+                entries.push(BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: ShaderStages::FRAGMENT,
                     ty: BindingType::Buffer {
                         ty: BufferBindingType::Uniform,
                         has_dynamic_offset: true,
-                        min_binding_size: Some(ViewUniform::min_size()),
+                        min_binding_size: Some(SkyboxFog::min_size()),
                     },
                     count: None,
-                },
-            ],
+                });
+            }
+
+            self.render_device
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("skybox_bind_group_layout"),
+                    entries: &entries,
+                })
+        });
+        layout.clone()
+    }
+}
+
+/// The key the [`SkyboxPipeline`] is specialized with.
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct SkyboxPipelineKey {
+    /// Whether the view target is HDR.
+    pub hdr: bool,
+    /// The [`Msaa`] sample count of the view.
+    pub samples: u32,
+    /// The format of the depth texture of the view.
+    pub depth_format: TextureFormat,
+    /// The stencil comparison of the [`SkyboxStencil`], if any.
+    pub stencil_compare: Option<CompareFunction>,
+    /// Whether the view uses an orthographic projection.
+    pub orthographic: bool,
+    /// Whether the images are sampled upside down, see [`Skybox::flip_y`].
+    pub flip_y: bool,
+    /// The conversion of the samples to linear colors, see [`Skybox::color_space`].
+    pub color_conversion: SkyboxColorConversion,
+    /// Whether the luminance of the sky is clamped, see [`Skybox::max_luminance`].
+    pub clamp_luminance: bool,
+    /// Whether the output is dithered, which is only the case for non-HDR targets, see
+    /// [`Skybox::dither`].
+    pub dither: bool,
+    /// Whether the [`Skybox::saturation`] or [`Skybox::contrast`] are adjusted.
+    pub adjust_colors: bool,
+    /// Whether the sky fades near the horizon, see [`Skybox::horizon_fade`].
+    pub horizon_fade: bool,
+    /// Where the far plane is, see [`Skybox::depth_convention`].
+    pub depth_convention: SkyboxDepthConvention,
+    /// Whether the sky is drawn at the far end of a [`Skybox::depth_range`] rather than at the
+    /// far plane.
+    pub depth_range: bool,
+    /// Whether cube samples are kept off the face edges, see [`Skybox::cube_edge_fixup`].
+    pub cube_edge_fixup: bool,
+    /// How the detail of the [`Skybox::layers`] is combined with the sky, if it is bound.
+    pub detail_blend_mode: Option<SkyboxBlendMode>,
+    /// Whether the sky is translucent, see [`Skybox::alpha`].
+    pub alpha_output: bool,
+    /// The custom fragment shader of the skybox, if any.
+    pub fragment_shader: Option<Handle<Shader>>,
+    /// The layout of the skybox bind group.
+    pub layout: SkyboxBindGroupLayoutKey,
+}
+
+impl SkyboxPipelineKey {
+    /// Returns the key of the pipeline drawing `skybox` to `view` with `samples` samples per
+    /// pixel, given the layout of its bind group and the format of its image once it is loaded.
+    fn new(
+        skybox: &Skybox,
+        view: &ExtractedView,
+        samples: u32,
+        layout: SkyboxBindGroupLayoutKey,
+        image_format: Option<TextureFormat>,
+    ) -> Self {
+        let depth_format = CORE_3D_DEPTH_FORMAT;
+        let color_conversion = match (skybox.color_space, image_format) {
+            (Some(color_space), Some(format)) if !layout.solid => {
+                color_space.conversion_from(format)
+            }
+            _ => SkyboxColorConversion::None,
         };
 
         Self {
-            bind_group_layout: render_device
-                .create_bind_group_layout(&bind_group_layout_descriptor),
+            hdr: view.hdr,
+            samples,
+            depth_format,
+            // The stencil test is ignored if the depth format has no stencil aspect.
+            stencil_compare: skybox
+                .stencil
+                .map(|stencil| stencil.compare)
+                .filter(|_| depth_format.has_stencil_aspect()),
+            // Orthographic projections are affine, unlike perspective ones.
+            orthographic: view.projection.w_axis.w == 1.0,
+            flip_y: skybox.flip_y,
+            color_conversion,
+            clamp_luminance: skybox.max_luminance.is_some(),
+            // HDR targets don't band, so they share the pipeline of undithered skyboxes.
+            dither: skybox.dither.is_some() && !view.hdr,
+            adjust_colors: skybox.saturation != 1.0 || skybox.contrast != 1.0,
+            horizon_fade: skybox.horizon_fade.is_some(),
+            depth_convention: skybox.depth_convention,
+            depth_range: skybox.depth_range().is_some(),
+            cube_edge_fixup: skybox.uses_cube_edge_fixup(&layout),
+            detail_blend_mode: skybox
+                .layers
+                .as_ref()
+                .filter(|_| layout.detail.is_some())
+                .map(|layers| layers.blend_mode),
+            alpha_output: skybox.alpha.is_some(),
+            fragment_shader: skybox.fragment_shader.clone(),
+            layout,
         }
     }
-}
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct SkyboxPipelineKey {
-    hdr: bool,
-    samples: u32,
-    depth_format: TextureFormat,
+    /// Returns the shader defs of the skybox shader specialized for this key.
+    fn shader_defs(&self) -> Vec<ShaderDefVal> {
+        let mut shader_defs = Vec::new();
+        if self.layout.projection == SkyboxProjection::Equirectangular {
+            shader_defs.push("EQUIRECTANGULAR".into());
+        }
+        if self.layout.blend {
+            shader_defs.push("BLEND".into());
+        }
+        if self.layout.array {
+            shader_defs.push("CUBE_ARRAY".into());
+        }
+        if self.layout.fog {
+            shader_defs.push("FOG".into());
+        }
+        if self.orthographic {
+            shader_defs.push("ORTHOGRAPHIC".into());
+        }
+        if self.layout.solid {
+            shader_defs.push("SOLID".into());
+        }
+        match self.layout.lut {
+            None => {}
+            Some(SkyboxLut::Curve) => shader_defs.push("LUT_CURVE".into()),
+            Some(SkyboxLut::Cube) => shader_defs.push("LUT_CUBE".into()),
+        }
+        if self.depth_convention == SkyboxDepthConvention::StandardZ {
+            shader_defs.push("STANDARD_Z".into());
+        }
+        if self.depth_range {
+            shader_defs.push("DEPTH_RANGE".into());
+        }
+        if self.flip_y {
+            shader_defs.push("FLIP_Y".into());
+        }
+        if self.cube_edge_fixup {
+            shader_defs.push("CUBE_EDGE_FIXUP".into());
+        }
+        if self
+            .layout
+            .detail
+            .is_some_and(|detail| detail.sampler.is_some())
+        {
+            shader_defs.push("DETAIL_SAMPLER".into());
+        }
+        if let Some(blend_mode) = self.detail_blend_mode {
+            shader_defs.push("DETAIL".into());
+            shader_defs.push(
+                match blend_mode {
+                    SkyboxBlendMode::Add => "DETAIL_ADD",
+                    SkyboxBlendMode::Multiply => "DETAIL_MULTIPLY",
+                    SkyboxBlendMode::Screen => "DETAIL_SCREEN",
+                }
+                .into(),
+            );
+        }
+        match self.color_conversion {
+            SkyboxColorConversion::None => {}
+            SkyboxColorConversion::DecodeSrgb => shader_defs.push("DECODE_SRGB".into()),
+            SkyboxColorConversion::EncodeSrgb => shader_defs.push("ENCODE_SRGB".into()),
+        }
+        if self.clamp_luminance {
+            shader_defs.push("CLAMP_LUMINANCE".into());
+        }
+        if self.dither {
+            shader_defs.push("DITHER".into());
+        }
+        if self.adjust_colors {
+            shader_defs.push("ADJUST_COLORS".into());
+        }
+        if self.horizon_fade {
+            shader_defs.push("HORIZON_FADE".into());
+        }
+        if self.alpha_output {
+            shader_defs.push("ALPHA_OUTPUT".into());
+        }
+        if self.layout.alpha_mask {
+            shader_defs.push("ALPHA_MASK".into());
+        }
+        shader_defs
+    }
 }
 
 impl SpecializedRenderPipeline for SkyboxPipeline {
     type Key = SkyboxPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = key.shader_defs();
+
+        let stencil_face = match key.stencil_compare {
+            Some(compare) => StencilFaceState {
+                compare,
+                fail_op: StencilOperation::Keep,
+                depth_fail_op: StencilOperation::Keep,
+                pass_op: StencilOperation::Keep,
+            },
+            None => StencilFaceState::IGNORE,
+        };
+
         RenderPipelineDescriptor {
             label: Some("skybox_pipeline".into()),
-            layout: vec![self.bind_group_layout.clone()],
+            layout: vec![self.bind_group_layout(key.layout)],
             push_constant_ranges: Vec::new(),
             vertex: VertexState {
                 shader: SKYBOX_SHADER_HANDLE,
-                shader_defs: Vec::new(),
+                shader_defs: shader_defs.clone(),
                 entry_point: "skybox_vertex".into(),
                 buffers: Vec::new(),
             },
             primitive: PrimitiveState::default(),
             depth_stencil: Some(DepthStencilState {
                 format: key.depth_format,
+                // The skybox is drawn on the far plane (0.0 with reverse-z), so the
+                // `GreaterEqual` comparison only passes on background pixels. Standard-z
+                // cameras draw it at 1.0 and compare with `LessEqual` instead.
                 depth_write_enabled: false,
-                depth_compare: CompareFunction::GreaterEqual,
+                depth_compare: key.depth_convention.depth_compare(),
                 stencil: StencilState {
-                    front: StencilFaceState::IGNORE,
-                    back: StencilFaceState::IGNORE,
-                    read_mask: 0,
+                    front: stencil_face,
+                    back: stencil_face,
+                    read_mask: if key.stencil_compare.is_some() { !0 } else { 0 },
                     write_mask: 0,
                 },
                 bias: DepthBiasState {
@@ -163,17 +1815,22 @@ impl SpecializedRenderPipeline for SkyboxPipeline {
                 alpha_to_coverage_enabled: false,
             },
             fragment: Some(FragmentState {
-                shader: SKYBOX_SHADER_HANDLE,
-                shader_defs: Vec::new(),
+                shader: key.fragment_shader.unwrap_or(SKYBOX_SHADER_HANDLE),
+                shader_defs,
                 entry_point: "skybox_fragment".into(),
                 targets: vec![Some(ColorTargetState {
+                    // The exposure is applied in linear space by the shader either way: HDR
+                    // targets are tonemapped afterwards, and sRGB targets convert on write.
                     format: if key.hdr {
                         ViewTarget::TEXTURE_FORMAT_HDR
                     } else {
                         TextureFormat::bevy_default()
                     },
                     // BlendState::REPLACE is not needed here, and None will be potentially much faster in some cases.
-                    blend: None,
+                    // Translucent skies are blended over the background instead.
+                    blend: key
+                        .alpha_output
+                        .then_some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
                     write_mask: ColorWrites::ALL,
                 })],
             }),
@@ -181,60 +1838,1015 @@ impl SpecializedRenderPipeline for SkyboxPipeline {
     }
 }
 
+/// The id of the specialized [`SkyboxPipeline`] of a view, in the render world.
 #[derive(Component)]
 pub struct SkyboxPipelineId(pub CachedRenderPipelineId);
 
+/// The specialized skybox pipelines of the views, along with the keys they were specialized
+/// with.
+///
+/// Views keep their entity across frames, so a view only needs to specialize its pipeline
+/// again when its key changes, e.g. when the [`Msaa`] sample count is changed at runtime.
+#[derive(Default)]
+struct SkyboxViewPipelines {
+    pipelines: HashMap<Entity, (SkyboxPipelineKey, CachedRenderPipelineId)>,
+    previous_pipelines: HashMap<Entity, (SkyboxPipelineKey, CachedRenderPipelineId)>,
+}
+
+impl SkyboxViewPipelines {
+    /// Starts a new frame, after which only the pipelines of the views of that frame are kept.
+    fn begin_frame(&mut self) {
+        self.previous_pipelines = std::mem::take(&mut self.pipelines);
+    }
+
+    /// Returns the pipeline of the view `entity` for `key`, which is the pipeline of the
+    /// previous frame if the key didn't change, or a new one from `specialize` otherwise.
+    ///
+    /// A new pipeline replaces the previous one right away, so the view is never drawn with a
+    /// pipeline of an outdated key, such as one with a different sample count than its target.
+    fn get_or_specialize(
+        &mut self,
+        entity: Entity,
+        key: SkyboxPipelineKey,
+        specialize: impl FnOnce(SkyboxPipelineKey) -> CachedRenderPipelineId,
+    ) -> CachedRenderPipelineId {
+        let pipeline_id = match self.previous_pipelines.remove(&entity) {
+            Some((previous_key, pipeline_id)) if previous_key == key => pipeline_id,
+            _ => specialize(key.clone()),
+        };
+        self.pipelines.insert(entity, (key, pipeline_id));
+        pipeline_id
+    }
+}
+
+/// Offsets the mip biases of the [`SkyboxUniforms`] of each view by its
+/// [`Skybox::fov_mip_bias`], which depends on the projection of the view.
+fn apply_skybox_fov_mip_bias(mut views: Query<(&ExtractedView, &Skybox, &mut SkyboxUniforms)>) {
+    for (view, skybox, mut uniforms) in &mut views {
+        let fov_mip_bias = skybox.fov_mip_bias(&view.projection);
+        if fov_mip_bias == 0.0 {
+            continue;
+        }
+        uniforms.mip_bias = (skybox.mip_bias + fov_mip_bias).clamp(-16.0, 15.99);
+        if let Some(layers) = &skybox.layers {
+            uniforms.detail_mip_bias = (layers.mip_bias + fov_mip_bias).clamp(-16.0, 15.99);
+        }
+    }
+}
+
 fn prepare_skybox_pipelines(
     mut commands: Commands,
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<SkyboxPipeline>>,
     pipeline: Res<SkyboxPipeline>,
+    images: Res<RenderAssets<Image>>,
     msaa: Res<Msaa>,
-    views: Query<(Entity, &ExtractedView), With<Skybox>>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedView,
+            &Skybox,
+            Option<&RenderLayers>,
+            Has<SkyboxFog>,
+        ),
+        SkyboxViewFilter,
+    >,
+    mut warned_missing_stencil: Local<bool>,
+    mut warned_depth_ranges: Local<HashSet<Entity>>,
+    mut view_pipelines: Local<SkyboxViewPipelines>,
 ) {
-    for (entity, view) in &views {
-        let pipeline_id = pipelines.specialize(
-            &pipeline_cache,
-            &pipeline,
-            SkyboxPipelineKey {
-                hdr: view.hdr,
-                samples: msaa.samples(),
-                depth_format: CORE_3D_DEPTH_FORMAT,
-            },
-        );
+    view_pipelines.begin_frame();
+    // Forget the views that are gone, so that the set doesn't grow with every camera ever seen.
+    warned_depth_ranges.retain(|entity| views.contains(*entity));
+
+    for (entity, view, skybox, view_layers, has_fog) in &views {
+        if !skybox.is_visible_in(view_layers) {
+            continue;
+        }
+
+        // Invalid images are reported when preparing the bind groups.
+        let Ok(layout) = SkyboxBindGroupLayoutKey::new(skybox, &images, has_fog) else {
+            continue;
+        };
+
+        let depth_format = CORE_3D_DEPTH_FORMAT;
+        if skybox.stencil.is_some()
+            && !depth_format.has_stencil_aspect()
+            && !*warned_missing_stencil
+        {
+            warn!(
+                "Skybox stencil test ignored: the depth format {depth_format:?} has no stencil aspect."
+            );
+            *warned_missing_stencil = true;
+        }
+
+        match skybox.depth_range {
+            Some(range) if skybox.depth_range().is_none() => {
+                if warned_depth_ranges.insert(entity) {
+                    warn!(
+                        "The skybox depth range {range:?} of {entity:?} is ignored: it must be within 0.0..=1.0 and go from near to far with {:?}.",
+                        skybox.depth_convention
+                    );
+                }
+            }
+            // Warn again if the range becomes invalid later on.
+            _ => {
+                warned_depth_ranges.remove(&entity);
+            }
+        }
+
+        let image_format = images
+            .get(skybox.sampled_images().0)
+            .map(|image| image.texture_format);
+        let key = SkyboxPipelineKey::new(skybox, view, msaa.samples(), layout, image_format);
 
+        let pipeline_id = view_pipelines.get_or_specialize(entity, key, |key| {
+            pipelines.specialize(&pipeline_cache, &pipeline, key)
+        });
+
+        // The render world is cleared every frame, so the id is inserted again regardless.
         commands
             .entity(entity)
             .insert(SkyboxPipelineId(pipeline_id));
     }
 }
 
+/// Identifies the resources bound by a skybox bind group.
+///
+/// Views only differ by the dynamic offsets of their uniforms, so views sharing the same
+/// images and sampler share a bind group. A modified image asset is prepared into new
+/// texture views, which changes the key and thereby invalidates the cached bind group.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+struct SkyboxBindGroupKey {
+    layout: SkyboxBindGroupLayoutKey,
+    texture_view: Option<TextureViewId>,
+    blend_target: Option<TextureViewId>,
+    sampler: Option<SamplerId>,
+    lut: Option<TextureViewId>,
+    detail: Option<TextureViewId>,
+    detail_sampler: Option<SamplerId>,
+    alpha_mask: Option<TextureViewId>,
+    view_uniforms: Option<BufferId>,
+    skybox_uniforms: Option<BufferId>,
+    fog_uniforms: Option<BufferId>,
+}
+
+/// The skybox bind groups used by the views of the current frame.
+///
+/// This is generic over the bind group so that the caching can be tested without a device.
+#[derive(Resource)]
+struct SkyboxBindGroups<T = BindGroup> {
+    bind_groups: HashMap<SkyboxBindGroupKey, T>,
+    previous_bind_groups: HashMap<SkyboxBindGroupKey, T>,
+}
+
+impl<T> Default for SkyboxBindGroups<T> {
+    fn default() -> Self {
+        Self {
+            bind_groups: HashMap::default(),
+            previous_bind_groups: HashMap::default(),
+        }
+    }
+}
+
+impl<T> SkyboxBindGroups<T> {
+    /// Starts a new frame, after which only the bind groups used by the views of that frame are
+    /// kept.
+    ///
+    /// This drops the bind groups referring to reallocated buffers, or to images that were
+    /// modified or removed.
+    fn begin_frame(&mut self) {
+        self.previous_bind_groups = std::mem::take(&mut self.bind_groups);
+    }
+
+    /// Returns the bind group for `key`, reusing the one of the previous frame if it had the
+    /// same key, or a new one from `create` otherwise.
+    fn get_or_create(&mut self, key: SkyboxBindGroupKey, create: impl FnOnce() -> T) -> &T {
+        let previous_bind_groups = &mut self.previous_bind_groups;
+        self.bind_groups
+            .entry(key)
+            .or_insert_with(|| previous_bind_groups.remove(&key).unwrap_or_else(create))
+    }
+}
+
+/// The skybox bind group of a view, along with its dynamic offsets.
 #[derive(Component)]
-pub struct SkyboxBindGroup(pub BindGroup);
+pub struct SkyboxBindGroup {
+    pub bind_group: BindGroup,
+    /// The offsets of the view uniform, the [`SkyboxUniforms`] and the optional
+    /// [`SkyboxFog`], in binding order.
+    pub dynamic_offsets: Vec<u32>,
+}
 
 fn prepare_skybox_bind_groups(
     mut commands: Commands,
-    pipeline: Res<SkyboxPipeline>,
+    mut pipeline: ResMut<SkyboxPipeline>,
+    mut bind_groups: ResMut<SkyboxBindGroups>,
     view_uniforms: Res<ViewUniforms>,
+    skybox_uniforms: Res<ComponentUniforms<SkyboxUniforms>>,
+    fog_uniforms: Res<ComponentUniforms<SkyboxFog>>,
     images: Res<RenderAssets<Image>>,
     render_device: Res<RenderDevice>,
-    views: Query<(Entity, &Skybox)>,
+    render_adapter: Res<RenderAdapter>,
+    views: Query<
+        (
+            Entity,
+            &Skybox,
+            &ViewUniformOffset,
+            &DynamicUniformIndex<SkyboxUniforms>,
+            Option<&DynamicUniformIndex<SkyboxFog>>,
+            Option<&RenderLayers>,
+        ),
+        SkyboxViewFilter,
+    >,
+    mut reported_views: Local<HashSet<Entity>>,
+    mut warned_samplers: Local<HashSet<SkyboxSampler>>,
 ) {
-    for (entity, skybox) in &views {
-        if let (Some(skybox), Some(view_uniforms)) =
-            (images.get(&skybox.0), view_uniforms.uniforms.binding())
-        {
-            let bind_group = render_device.create_bind_group(
-                "skybox_bind_group",
-                &pipeline.bind_group_layout,
-                &BindGroupEntries::sequential((
-                    &skybox.texture_view,
-                    &skybox.sampler,
-                    view_uniforms,
-                )),
+    let (Some(view_uniforms_binding), Some(skybox_uniforms_binding)) =
+        (view_uniforms.uniforms.binding(), skybox_uniforms.binding())
+    else {
+        return;
+    };
+
+    bind_groups.begin_frame();
+    // Forget the views that are gone, so that the set doesn't grow with every camera ever seen.
+    reported_views.retain(|entity| views.contains(*entity));
+
+    for (
+        entity,
+        skybox,
+        view_uniform_offset,
+        skybox_uniform_index,
+        fog_uniform_index,
+        view_layers,
+    ) in &views
+    {
+        if !skybox.is_visible_in(view_layers) {
+            continue;
+        }
+
+        let layout_key =
+            match SkyboxBindGroupLayoutKey::new(skybox, &images, fog_uniform_index.is_some()) {
+                Ok(layout_key) => layout_key,
+                Err(err) => {
+                    if reported_views.insert(entity) {
+                        let image = skybox.sampled_images().0;
+                        match image.path() {
+                            Some(path) => error!(
+                                "The skybox of {entity:?} can't be drawn with image {path}: {err}"
+                            ),
+                            None => error!(
+                                "The skybox of {entity:?} can't be drawn with image {:?}: {err}",
+                                image.id()
+                            ),
+                        }
+                    }
+                    continue;
+                }
+            };
+        // Report the view again if its images become invalid later on.
+        reported_views.remove(&entity);
+
+        let mut fog_uniforms_binding = None;
+        let mut dynamic_offsets = vec![view_uniform_offset.offset, skybox_uniform_index.index()];
+        if layout_key.fog {
+            let (Some(binding), Some(fog_uniform_index)) =
+                (fog_uniforms.binding(), fog_uniform_index)
+            else {
+                continue;
+            };
+            fog_uniforms_binding = Some(binding);
+            dynamic_offsets.push(fog_uniform_index.index());
+        }
+
+        // No images are bound while the loading color is drawn in their place.
+        let mut textures = None;
+        if !layout_key.solid {
+            let (image, blend_target) = skybox.sampled_images();
+            let Some(image) = images.get(image) else {
+                continue;
+            };
+            let blend_target = match blend_target {
+                Some(blend_target) => match images.get(blend_target) {
+                    Some(blend_target) => Some(blend_target),
+                    // Wait for both sides of the transition to be loaded.
+                    None => continue,
+                },
+                None => None,
+            };
+            if warned_samplers.insert(skybox.sampler) {
+                let supports_anisotropy = render_adapter
+                    .get_downlevel_capabilities()
+                    .flags
+                    .contains(DownlevelFlags::ANISOTROPIC_FILTERING);
+                if let Some(warning) = skybox.sampler.anisotropy_warning(supports_anisotropy) {
+                    warn!("{warning}");
+                }
+            }
+            let sampler = match pipeline.sampler(&render_device, skybox.sampler) {
+                Some(sampler) => sampler.clone(),
+                None => image.sampler.clone(),
+            };
+            textures = Some((image, blend_target, sampler));
+        }
+        // The layout only has a table once it is loaded.
+        let lut = match layout_key.lut {
+            Some(_) => match skybox.lut.as_ref().and_then(|lut| images.get(lut)) {
+                Some(lut) => Some((lut, pipeline.lut_sampler(&render_device).clone())),
+                None => continue,
+            },
+            None => None,
+        };
+        let alpha_mask = if layout_key.alpha_mask {
+            let Some(mask) = skybox
+                .alpha
+                .as_ref()
+                .and_then(|alpha| alpha.mask.as_ref())
+                .and_then(|mask| images.get(mask))
+            else {
+                continue;
+            };
+            Some(mask)
+        } else {
+            None
+        };
+        let detail = if layout_key.detail.is_some() {
+            let Some((layers, detail)) = skybox
+                .layers
+                .as_ref()
+                .and_then(|layers| images.get(&layers.detail).map(|detail| (layers, detail)))
+            else {
+                continue;
+            };
+            let sampler =
+                layers
+                    .sampler
+                    .map(|sampler| match pipeline.sampler(&render_device, sampler) {
+                        Some(sampler) => sampler.clone(),
+                        None => detail.sampler.clone(),
+                    });
+            Some((detail, sampler))
+        } else {
+            None
+        };
+
+        let key = SkyboxBindGroupKey {
+            layout: layout_key,
+            texture_view: textures.as_ref().map(|(image, ..)| image.texture_view.id()),
+            blend_target: textures
+                .as_ref()
+                .and_then(|(_, blend_target, _)| *blend_target)
+                .map(|blend_target| blend_target.texture_view.id()),
+            sampler: textures.as_ref().map(|(.., sampler)| sampler.id()),
+            lut: lut.as_ref().map(|(lut, _)| lut.texture_view.id()),
+            detail: detail.as_ref().map(|(detail, _)| detail.texture_view.id()),
+            detail_sampler: detail
+                .as_ref()
+                .and_then(|(_, sampler)| sampler.as_ref())
+                .map(Sampler::id),
+            alpha_mask: alpha_mask.map(|mask| mask.texture_view.id()),
+            view_uniforms: view_uniforms.uniforms.buffer().map(Buffer::id),
+            skybox_uniforms: skybox_uniforms.buffer().map(Buffer::id),
+            fog_uniforms: fog_uniforms_binding
+                .is_some()
+                .then(|| fog_uniforms.buffer().map(Buffer::id))
+                .flatten(),
+        };
+
+        let bind_group = bind_groups.get_or_create(key, || {
+            let mut entries = DynamicBindGroupEntries::new_with_indices((
+                (2, view_uniforms_binding.clone()),
+                (3, skybox_uniforms_binding.clone()),
+            ));
+            if let Some((image, blend_target, sampler)) = &textures {
+                entries = entries.extend_with_indices(((0, &image.texture_view), (1, sampler)));
+                if let Some(blend_target) = blend_target {
+                    entries = entries.extend_with_indices(((4, &blend_target.texture_view),));
+                }
+            }
+            if let Some(fog_uniforms_binding) = fog_uniforms_binding {
+                entries = entries.extend_with_indices(((5, fog_uniforms_binding),));
+            }
+            if let Some((lut, lut_sampler)) = &lut {
+                entries = entries.extend_with_indices(((6, &lut.texture_view), (7, lut_sampler)));
+            }
+            if let Some(mask) = alpha_mask {
+                entries = entries.extend_with_indices(((10, &mask.texture_view),));
+            }
+            if let Some((detail, detail_sampler)) = &detail {
+                entries = entries.extend_with_indices(((8, &detail.texture_view),));
+                if let Some(detail_sampler) = detail_sampler {
+                    entries = entries.extend_with_indices(((9, detail_sampler),));
+                }
+            }
+
+            let layout = pipeline.bind_group_layout(layout_key);
+            render_device.create_bind_group("skybox_bind_group", &layout, &entries)
+        });
+
+        commands.entity(entity).insert(SkyboxBindGroup {
+            bind_group: bind_group.clone(),
+            dynamic_offsets,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use bevy_asset::Handle;
+    use bevy_ecs::{entity::Entity, query::With, world::World};
+    use bevy_math::{Mat4, UVec4};
+    use bevy_render::{
+        extract_component::ExtractComponent,
+        render_asset::RenderAssets,
+        render_resource::{
+            CachedRenderPipelineId, CompareFunction, Extent3d, SamplerId, ShaderDefVal,
+            TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor,
+            TextureViewDimension, TextureViewId,
+        },
+        texture::Image,
+        view::{ColorGrading, ExtractedView, Msaa},
+    };
+    use bevy_transform::components::GlobalTransform;
+
+    use super::{
+        check_texture_binding, check_texture_shape, is_filterable, sky_covers_view, Skybox,
+        SkyboxAlpha, SkyboxBindGroupKey, SkyboxBindGroupLayoutKey, SkyboxBindGroups,
+        SkyboxBlendMode, SkyboxColorConversion, SkyboxColorSpace, SkyboxDepthConvention,
+        SkyboxDepthRange, SkyboxDetailLayoutKey, SkyboxExclude, SkyboxHorizonFade, SkyboxLayers,
+        SkyboxLut, SkyboxPipelineKey, SkyboxProjection, SkyboxResolve, SkyboxSampler,
+        SkyboxStencil, SkyboxViewFilter, SkyboxViewPipelines,
+    };
+    use crate::core_3d::CORE_3D_DEPTH_FORMAT;
+
+    fn size(width: u32, height: u32, layers: u32) -> Extent3d {
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: layers,
+        }
+    }
+
+    #[test]
+    fn cubemap_rejects_2d_image() {
+        let result = check_texture_shape(
+            SkyboxProjection::Cubemap,
+            TextureDimension::D2,
+            size(1024, 512, 1),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cubemap_rejects_non_square_faces() {
+        let result = check_texture_shape(
+            SkyboxProjection::Cubemap,
+            TextureDimension::D2,
+            size(512, 256, 6),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cubemap_accepts_cube_textures() {
+        for layers in [6, 12] {
+            let result = check_texture_shape(
+                SkyboxProjection::Cubemap,
+                TextureDimension::D2,
+                size(256, 256, layers),
             );
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn excluded_views_are_skipped() {
+        let mut world = World::new();
+        let main_camera = world.spawn(Skybox::default()).id();
+        world.spawn((Skybox::default(), SkyboxExclude));
+
+        let mut views = world.query_filtered::<Entity, (With<Skybox>, SkyboxViewFilter)>();
+        let drawn: Vec<_> = views.iter(&world).collect();
+        assert_eq!(drawn, [main_camera]);
+    }
+
+    #[test]
+    fn bc6h_cube_image_can_be_bound() {
+        // A 4x4 cube, one BC6H block of 16 bytes per face. `Image::new` can't check the size
+        // of compressed data.
+        let mut image = Image {
+            data: vec![0; 16 * 6],
+            ..Default::default()
+        };
+        image.texture_descriptor.size = size(4, 4, 6);
+        image.texture_descriptor.format = TextureFormat::Bc6hRgbUfloat;
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        });
+        assert!(image.is_compressed());
+
+        let descriptor = &image.texture_descriptor;
+        assert!(check_texture_shape(
+            SkyboxProjection::Cubemap,
+            descriptor.dimension,
+            descriptor.size
+        )
+        .is_ok());
+        assert!(check_texture_binding(descriptor.usage, descriptor.sample_count).is_ok());
+        // BC6H can be bound with the default filtering sampler.
+        assert_eq!(
+            is_filterable(descriptor.format, SkyboxSampler::default()),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn anisotropy_out_of_range_or_unsupported_warns() {
+        assert!(SkyboxSampler::Anisotropic(8)
+            .anisotropy_warning(true)
+            .is_none());
+        assert!(SkyboxSampler::Anisotropic(32)
+            .anisotropy_warning(true)
+            .is_some());
+        assert!(SkyboxSampler::Anisotropic(0)
+            .anisotropy_warning(true)
+            .is_some());
+        assert!(SkyboxSampler::Anisotropic(8)
+            .anisotropy_warning(false)
+            .is_some());
+        // An anisotropy of 1 is plain trilinear filtering, which every device supports.
+        assert!(SkyboxSampler::Anisotropic(1)
+            .anisotropy_warning(false)
+            .is_none());
+        assert!(SkyboxSampler::Linear.anisotropy_warning(false).is_none());
+        assert!(SkyboxSampler::Anisotropic(32).is_filtering());
+    }
+
+    #[test]
+    fn unsampleable_images_are_rejected() {
+        assert!(is_filterable(TextureFormat::Rgba8Uint, SkyboxSampler::default()).is_err());
+        assert!(is_filterable(TextureFormat::Depth32Float, SkyboxSampler::default()).is_err());
+        assert!(check_texture_binding(TextureUsages::COPY_DST, 1).is_err());
+        assert!(check_texture_binding(TextureUsages::TEXTURE_BINDING, 4).is_err());
+    }
+
+    fn pipeline_key(samples: u32) -> SkyboxPipelineKey {
+        SkyboxPipelineKey {
+            hdr: false,
+            samples,
+            depth_format: CORE_3D_DEPTH_FORMAT,
+            stencil_compare: None,
+            orthographic: false,
+            flip_y: false,
+            color_conversion: SkyboxColorConversion::None,
+            clamp_luminance: false,
+            dither: false,
+            adjust_colors: false,
+            horizon_fade: false,
+            depth_convention: SkyboxDepthConvention::ReverseZ,
+            depth_range: false,
+            cube_edge_fixup: false,
+            detail_blend_mode: None,
+            fragment_shader: None,
+            layout: SkyboxBindGroupLayoutKey {
+                projection: SkyboxProjection::Cubemap,
+                blend: false,
+                array: false,
+                filtering: true,
+                filterable: true,
+                fog: false,
+                solid: false,
+                lut: None,
+                detail: None,
+                alpha_mask: false,
+            },
+            alpha_output: false,
+        }
+    }
+
+    fn view(hdr: bool) -> ExtractedView {
+        ExtractedView {
+            projection: Mat4::perspective_infinite_reverse_rh(FRAC_PI_4, 1.0, 0.1),
+            transform: GlobalTransform::IDENTITY,
+            view_projection: None,
+            hdr,
+            viewport: UVec4::new(0, 0, 1280, 720),
+            color_grading: ColorGrading::default(),
+        }
+    }
+
+    /// The shader defs of the pipeline drawing `skybox` with `layout` to an LDR view.
+    fn shader_defs(skybox: &Skybox, layout: SkyboxBindGroupLayoutKey) -> Vec<ShaderDefVal> {
+        SkyboxPipelineKey::new(skybox, &view(false), 1, layout, None).shader_defs()
+    }
+
+    #[test]
+    fn reloaded_image_creates_a_new_bind_group() {
+        let mut bind_groups = SkyboxBindGroups::default();
+        let key = SkyboxBindGroupKey {
+            layout: pipeline_key(1).layout,
+            texture_view: Some(TextureViewId::new()),
+            blend_target: None,
+            sampler: Some(SamplerId::new()),
+            lut: None,
+            detail: None,
+            detail_sampler: None,
+            alpha_mask: None,
+            view_uniforms: None,
+            skybox_uniforms: None,
+            fog_uniforms: None,
+        };
+
+        bind_groups.begin_frame();
+        assert_eq!(*bind_groups.get_or_create(key, || "original"), "original");
+        bind_groups.begin_frame();
+        assert_eq!(*bind_groups.get_or_create(key, || "recreated"), "original");
+
+        // Reloading the image keeps its handle, but prepares it into a new texture view.
+        let reloaded = SkyboxBindGroupKey {
+            texture_view: Some(TextureViewId::new()),
+            ..key
+        };
+        bind_groups.begin_frame();
+        assert_eq!(
+            *bind_groups.get_or_create(reloaded, || "reloaded"),
+            "reloaded"
+        );
+
+        // The bind group of the previous texture view isn't kept around.
+        bind_groups.begin_frame();
+        assert_eq!(*bind_groups.get_or_create(key, || "recreated"), "recreated");
+    }
+
+    #[test]
+    fn msaa_change_specializes_new_pipeline() {
+        let entity = Entity::from_raw(0);
+        let skybox = Skybox::default();
+        let layout = pipeline_key(1).layout;
+        let mut view_pipelines = SkyboxViewPipelines::default();
+        let mut specialized_samples = Vec::new();
+
+        for msaa in [
+            Msaa::Sample4,
+            Msaa::Sample4,
+            Msaa::Off,
+            Msaa::Off,
+            Msaa::Sample4,
+        ] {
+            let key = SkyboxPipelineKey::new(&skybox, &view(false), msaa.samples(), layout, None);
+            view_pipelines.begin_frame();
+            view_pipelines.get_or_specialize(entity, key, |key| {
+                specialized_samples.push(key.samples);
+                CachedRenderPipelineId::INVALID
+            });
+            // The pipeline of the view always has the current sample count.
+            assert_eq!(view_pipelines.pipelines[&entity].0.samples, msaa.samples());
+        }
+
+        // Only toggling the sample count between `Msaa::Sample4` and `Msaa::Off` specializes.
+        assert_eq!(specialized_samples, [4, 1, 4]);
+    }
+
+    #[test]
+    fn resolve_decides_when_the_sky_lands_in_the_main_texture() {
+        // Without MSAA, the sky is drawn into the main texture whichever pass resolves.
+        assert!(SkyboxResolve::SkyboxPass.writes_main_texture(Msaa::Off));
+        assert!(SkyboxResolve::TransparentPass.writes_main_texture(Msaa::Off));
+
+        assert!(SkyboxResolve::SkyboxPass.writes_main_texture(Msaa::Sample4));
+        assert!(!SkyboxResolve::TransparentPass.writes_main_texture(Msaa::Sample4));
+        assert_eq!(Skybox::default().resolve, SkyboxResolve::SkyboxPass);
+    }
+
+    #[test]
+    fn lut_kind_follows_texture_dimension() {
+        assert_eq!(
+            SkyboxLut::new(TextureDimension::D1, TextureFormat::Rgba16Float),
+            Ok(SkyboxLut::Curve)
+        );
+        assert_eq!(
+            SkyboxLut::new(TextureDimension::D3, TextureFormat::Rgba8Unorm),
+            Ok(SkyboxLut::Cube)
+        );
+        assert!(SkyboxLut::new(TextureDimension::D2, TextureFormat::Rgba16Float).is_err());
+        assert!(SkyboxLut::new(TextureDimension::D3, TextureFormat::Rgba8Uint).is_err());
+    }
+
+    #[test]
+    fn loaded_lut_selects_its_lookup() {
+        fn is_lookup(def: &&ShaderDefVal) -> bool {
+            matches!(def, ShaderDefVal::Bool(name, true) if name.starts_with("LUT_"))
+        }
+
+        let skybox = Skybox::default();
+        let layout = pipeline_key(1).layout;
+        // The sky is drawn without the table until it is loaded, which adds it to the layout.
+        let defs = shader_defs(&skybox, layout);
+        assert_eq!(defs.iter().filter(is_lookup).count(), 0);
+
+        for (lut, lookup) in [
+            (SkyboxLut::Curve, "LUT_CURVE"),
+            (SkyboxLut::Cube, "LUT_CUBE"),
+        ] {
+            let layout = SkyboxBindGroupLayoutKey {
+                lut: Some(lut),
+                ..layout
+            };
+            let defs = shader_defs(&skybox, layout);
+            let lookups: Vec<_> = defs.iter().filter(is_lookup).collect();
+            assert_eq!(lookups, [&ShaderDefVal::from(lookup)]);
+        }
+    }
+
+    #[test]
+    fn horizon_fade_range_is_never_empty() {
+        let fade = SkyboxHorizonFade::default();
+        assert_eq!(fade.shader_end(), fade.end);
+
+        let sharp = SkyboxHorizonFade {
+            start: 0.1,
+            end: 0.1,
+            ..fade
+        };
+        assert!(sharp.shader_end() < sharp.start);
+        assert!(Skybox::default().horizon_fade.is_none());
+    }
 
-            commands.entity(entity).insert(SkyboxBindGroup(bind_group));
+    #[test]
+    fn detail_blend_mode_selects_its_shader_defs() {
+        fn is_blend_mode(def: &&ShaderDefVal) -> bool {
+            matches!(def, ShaderDefVal::Bool(name, true) if name.starts_with("DETAIL_"))
         }
+
+        let loading_layout = pipeline_key(1).layout;
+        let layout = SkyboxBindGroupLayoutKey {
+            detail: Some(SkyboxDetailLayoutKey {
+                filterable: true,
+                sampler: None,
+            }),
+            ..loading_layout
+        };
+        for (blend_mode, def) in [
+            (SkyboxBlendMode::Add, "DETAIL_ADD"),
+            (SkyboxBlendMode::Multiply, "DETAIL_MULTIPLY"),
+            (SkyboxBlendMode::Screen, "DETAIL_SCREEN"),
+        ] {
+            let skybox = Skybox {
+                layers: Some(SkyboxLayers::new(Handle::default(), blend_mode)),
+                ..Default::default()
+            };
+
+            let defs = shader_defs(&skybox, layout);
+            assert!(defs.contains(&"DETAIL".into()));
+            let blend_modes: Vec<_> = defs.iter().filter(is_blend_mode).collect();
+            assert_eq!(blend_modes, [&ShaderDefVal::from(def)]);
+
+            // The sky is drawn without its detail until the detail is loaded.
+            let defs = shader_defs(&skybox, loading_layout);
+            assert!(!defs.contains(&"DETAIL".into()));
+            assert_eq!(defs.iter().filter(is_blend_mode).count(), 0);
+        }
+    }
+
+    #[test]
+    fn detail_sampler_is_only_bound_when_dedicated() {
+        let skybox = Skybox {
+            layers: Some(SkyboxLayers::new(
+                Handle::default(),
+                SkyboxBlendMode::Multiply,
+            )),
+            ..Default::default()
+        };
+
+        for (sampler, dedicated) in [(None, false), (Some(true), true), (Some(false), true)] {
+            let layout = SkyboxBindGroupLayoutKey {
+                detail: Some(SkyboxDetailLayoutKey {
+                    filterable: sampler.unwrap_or(true),
+                    sampler,
+                }),
+                ..pipeline_key(1).layout
+            };
+            // Without a dedicated sampler, the detail shares the sampler of the sky.
+            let defs = shader_defs(&skybox, layout);
+            assert_eq!(defs.contains(&"DETAIL_SAMPLER".into()), dedicated);
+            assert!(defs.contains(&"DETAIL_MULTIPLY".into()));
+        }
+    }
+
+    #[test]
+    fn fov_mip_bias_follows_the_zoom() {
+        let reference = Mat4::perspective_infinite_reverse_rh(FRAC_PI_4, 1.0, 0.1);
+        let zoomed_fov = 2.0 * ((FRAC_PI_4 * 0.5).tan() * 0.5).atan();
+        let zoomed = Mat4::perspective_infinite_reverse_rh(zoomed_fov, 1.0, 0.1);
+        let orthographic = Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 1.0);
+
+        // Disabled by default.
+        assert_eq!(Skybox::default().fov_mip_bias(&zoomed), 0.0);
+
+        let skybox = Skybox {
+            fov_mip_bias: 1.0,
+            ..Default::default()
+        };
+        assert!(skybox.fov_mip_bias(&reference).abs() < 1e-5);
+        // Halving the tangent of the half field of view samples one mip level sharper.
+        assert!((skybox.fov_mip_bias(&zoomed) + 1.0).abs() < 1e-5);
+        // Orthographic cameras use the field of view the sky is spread over.
+        assert!(skybox.fov_mip_bias(&orthographic).abs() < 1e-5);
+    }
+
+    #[test]
+    fn only_unmasked_skyboxes_cover_the_background() {
+        assert!(Skybox::default().covers_background());
+
+        let disabled = Skybox {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(!disabled.covers_background());
+
+        // The stencil test leaves the rest of the background to the clear color.
+        let masked = Skybox {
+            stencil: Some(SkyboxStencil {
+                reference: 1,
+                compare: CompareFunction::Equal,
+            }),
+            ..Default::default()
+        };
+        assert!(!masked.covers_background());
+
+        // The sky is blended over the clear color.
+        let translucent = Skybox {
+            alpha: Some(SkyboxAlpha::default()),
+            ..Default::default()
+        };
+        assert!(!translucent.covers_background());
+    }
+
+    #[test]
+    fn loading_skybox_without_loading_color_keeps_the_clear() {
+        // Nothing can be bound while the image is loading, but the pipeline is specialized
+        // all the same and may be ready.
+        let skybox = Skybox::default();
+        let images = RenderAssets::<Image>::default();
+        assert!(SkyboxBindGroupLayoutKey::new(&skybox, &images, false).is_ok());
+        assert!(!sky_covers_view(&skybox, false, true, false));
+
+        assert!(sky_covers_view(&skybox, false, true, true));
+        assert!(!sky_covers_view(&skybox, false, false, true));
+        // The clear is still needed outside of the viewport.
+        assert!(!sky_covers_view(&skybox, true, true, true));
+    }
+
+    #[test]
+    fn cube_edge_fixup_only_applies_to_cubemaps() {
+        let cubemap = pipeline_key(1).layout;
+        let equirectangular = SkyboxBindGroupLayoutKey {
+            projection: SkyboxProjection::Equirectangular,
+            ..cubemap
+        };
+        let solid = SkyboxBindGroupLayoutKey {
+            solid: true,
+            ..cubemap
+        };
+
+        let skybox = Skybox {
+            cube_edge_fixup: true,
+            ..Default::default()
+        };
+        assert!(skybox.uses_cube_edge_fixup(&cubemap));
+        assert!(!skybox.uses_cube_edge_fixup(&equirectangular));
+        assert!(!skybox.uses_cube_edge_fixup(&solid));
+        assert!(!Skybox::default().uses_cube_edge_fixup(&cubemap));
+    }
+
+    #[test]
+    fn depth_convention_selects_the_far_plane() {
+        let layout = pipeline_key(1).layout;
+        for (depth_convention, standard_z, depth_compare) in [
+            (
+                SkyboxDepthConvention::ReverseZ,
+                false,
+                CompareFunction::GreaterEqual,
+            ),
+            (
+                SkyboxDepthConvention::StandardZ,
+                true,
+                CompareFunction::LessEqual,
+            ),
+        ] {
+            let skybox = Skybox {
+                depth_convention,
+                ..Default::default()
+            };
+            let key = SkyboxPipelineKey::new(&skybox, &view(false), 1, layout, None);
+            assert_eq!(key.depth_convention.depth_compare(), depth_compare);
+            assert_eq!(key.shader_defs().contains(&"STANDARD_Z".into()), standard_z);
+        }
+    }
+
+    #[test]
+    fn depth_ranges_place_the_sky_in_their_segment() {
+        // Two views splitting a reverse-z depth buffer in half.
+        let near_segment = SkyboxDepthRange {
+            near: 1.0,
+            far: 0.5,
+        };
+        let far_segment = SkyboxDepthRange {
+            near: 0.5,
+            far: 0.0,
+        };
+        let view = |depth_range| Skybox {
+            depth_range,
+            ..Default::default()
+        };
+
+        let far_depth = |skybox: &Skybox| {
+            let (_, uniforms) = Skybox::extract_component(skybox).unwrap();
+            uniforms.far_depth
+        };
+        assert_eq!(far_depth(&view(Some(near_segment))), 0.5);
+        assert_eq!(far_depth(&view(Some(far_segment))), 0.0);
+
+        // Both segments share a pipeline, which differs from the one drawing at the far plane.
+        let key = |skybox: &Skybox| SkyboxPipelineKey {
+            depth_range: skybox.depth_range().is_some(),
+            ..pipeline_key(1)
+        };
+        assert!(key(&view(Some(near_segment))) == key(&view(Some(far_segment))));
+        assert!(key(&view(Some(near_segment))) != key(&view(None)));
+
+        // A range going the wrong way for the convention is ignored.
+        let reversed = view(Some(SkyboxDepthRange {
+            near: 0.0,
+            far: 0.5,
+        }));
+        assert_eq!(reversed.depth_range(), None);
+        assert_eq!(far_depth(&reversed), 0.0);
+        assert!(SkyboxDepthRange {
+            near: 0.0,
+            far: 0.5,
+        }
+        .is_valid_for(SkyboxDepthConvention::StandardZ));
+    }
+
+    #[test]
+    fn removed_view_pipelines_are_dropped() {
+        let view = Entity::from_raw(0);
+        let mut view_pipelines = SkyboxViewPipelines::default();
+
+        view_pipelines.begin_frame();
+        view_pipelines
+            .get_or_specialize(view, pipeline_key(4), |_| CachedRenderPipelineId::INVALID);
+        view_pipelines.begin_frame();
+        view_pipelines.begin_frame();
+        assert!(view_pipelines.pipelines.is_empty());
+        assert!(view_pipelines.previous_pipelines.is_empty());
+    }
+
+    #[test]
+    fn color_space_conversion() {
+        let srgb = TextureFormat::Rgba8UnormSrgb;
+        let linear = TextureFormat::Rgba8Unorm;
+        let hdr = TextureFormat::Rgba16Float;
+        assert_eq!(
+            SkyboxColorSpace::Srgb.conversion_from(srgb),
+            SkyboxColorConversion::None
+        );
+        assert_eq!(
+            SkyboxColorSpace::Srgb.conversion_from(linear),
+            SkyboxColorConversion::DecodeSrgb
+        );
+        assert_eq!(
+            SkyboxColorSpace::Linear.conversion_from(srgb),
+            SkyboxColorConversion::EncodeSrgb
+        );
+        assert_eq!(
+            SkyboxColorSpace::Linear.conversion_from(linear),
+            SkyboxColorConversion::None
+        );
+        assert_eq!(
+            SkyboxColorSpace::Linear.conversion_from(hdr),
+            SkyboxColorConversion::None
+        );
+    }
+
+    #[test]
+    fn equirectangular_accepts_2d_image() {
+        let result = check_texture_shape(
+            SkyboxProjection::Equirectangular,
+            TextureDimension::D2,
+            size(1024, 512, 1),
+        );
+        assert!(result.is_ok());
+
+        let result = check_texture_shape(
+            SkyboxProjection::Equirectangular,
+            TextureDimension::D2,
+            size(256, 256, 6),
+        );
+        assert!(result.is_err());
     }
 }
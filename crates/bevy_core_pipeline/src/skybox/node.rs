@@ -0,0 +1,118 @@
+use bevy_ecs::{prelude::World, query::QueryItem};
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment,
+        RenderPassDepthStencilAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{Msaa, ViewDepthTexture, ViewTarget},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+use super::{Skybox, SkyboxBindGroup, SkyboxPipelineId, SkyboxResolve};
+
+/// A [`bevy_render::render_graph::Node`] that draws the [`Skybox`] of a view over the
+/// background of its [`ViewTarget`].
+///
+/// The [`Core3dPlugin`](crate::core_3d::Core3dPlugin) adds it to the 3D render graph as
+/// [`SKYBOX`](crate::core_3d::graph::node::SKYBOX), between the main opaque and the main
+/// transparent passes. Custom passes can be ordered relative to it with render graph edges,
+/// e.g. `add_render_graph_edges(CORE_3D, &[MAIN_OPAQUE_PASS, ATMOSPHERE, SKYBOX])` renders a
+/// custom atmosphere pass before the skybox.
+///
+/// With MSAA, the pass resolves the multisampled target into the main texture unless
+/// [`Skybox::resolve`] is [`SkyboxResolve::TransparentPass`].
+///
+/// Views without a [`Skybox`], or whose skybox is disabled or not prepared yet, are skipped.
+#[derive(Default)]
+pub struct SkyboxNode;
+
+impl ViewNode for SkyboxNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ViewTarget,
+        &'static ViewDepthTexture,
+        &'static Skybox,
+        &'static SkyboxPipelineId,
+        &'static SkyboxBindGroup,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, target, depth, skybox, skybox_pipeline, skybox_bind_group): QueryItem<
+            Self::ViewQuery,
+        >,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        // A disabled skybox keeps its pipeline and bind group around, but isn't drawn.
+        if !skybox.enabled {
+            return Ok(());
+        }
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(skybox_pipeline.0) else {
+            return Ok(());
+        };
+
+        #[cfg(feature = "trace")]
+        let _skybox_pass_span = info_span!("skybox_pass").entered();
+
+        let ops = Operations {
+            load: LoadOp::Load,
+            store: true,
+        };
+        let color_attachment = match target.sampled_main_texture_view() {
+            // Only draw into the multisampled texture, the main transparent pass resolves it.
+            Some(view)
+                if !skybox
+                    .resolve
+                    .writes_main_texture(*world.resource::<Msaa>()) =>
+            {
+                RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops,
+                }
+            }
+            _ => target.get_color_attachment(ops),
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("skybox_pass"),
+            color_attachments: &[Some(color_attachment)],
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                view: &depth.view,
+                // NOTE: The skybox only tests against the depth written by the previous passes.
+                // Store is still set to `true` so that wgpu does not clear the depth buffer,
+                // see https://github.com/bevyengine/bevy/issues/3776
+                depth_ops: Some(Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+        });
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+
+        // Draw the skybox using a fullscreen triangle.
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(
+            0,
+            &skybox_bind_group.bind_group,
+            &skybox_bind_group.dynamic_offsets,
+        );
+        if let Some(stencil) = skybox.stencil {
+            render_pass.set_stencil_reference(stencil.reference);
+        }
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
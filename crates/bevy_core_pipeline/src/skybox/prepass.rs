@@ -0,0 +1,449 @@
+use bevy_asset::Handle;
+use bevy_ecs::{
+    prelude::{Component, Entity},
+    query::{Has, With, Without},
+    system::{Commands, Local, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::{Mat4, Quat};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{ComponentUniforms, DynamicUniformIndex},
+    render_phase::TrackedRenderPass,
+    render_resource::{
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingType, BufferBindingType, BufferId, CachedRenderPipelineId,
+        ColorTargetState, ColorWrites, DepthBiasState, DepthStencilState, FragmentState,
+        MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor, Shader,
+        ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        StencilState, VertexState,
+    },
+    renderer::RenderDevice,
+    view::{Msaa, RenderLayers},
+    Extract,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+use crate::{
+    core_3d::CORE_3D_DEPTH_FORMAT,
+    prepass::{
+        DepthPrepass, MotionVectorPrepass, NormalPrepass, MOTION_VECTOR_PREPASS_FORMAT,
+        NORMAL_PREPASS_FORMAT,
+    },
+};
+
+use super::{Skybox, SkyboxDepthConvention, SkyboxFaces, SkyboxViewFilter};
+
+pub const SKYBOX_PREPASS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(38456210549182736);
+
+/// The per-view uniform of the skybox prepass, mapping the normalized device coordinates of the
+/// sky in the current frame to the ones of the previous frame, along with the depth the sky is
+/// drawn at.
+#[derive(Component, ShaderType, Clone)]
+pub struct SkyboxPrepassUniforms {
+    reprojection: Mat4,
+    far_depth: f32,
+}
+
+/// Returns the depth `skybox` is drawn at in the prepass: the far end of its depth range, or the
+/// far plane.
+fn far_depth(skybox: &Skybox) -> f32 {
+    skybox
+        .depth_range()
+        .map_or(skybox.depth_convention.far_plane(), |range| range.far)
+}
+
+/// Returns the matrix projecting directions of the sky to the clip space of a view, which only
+/// depends on the rotation of the view since the sky is infinitely far away.
+fn clip_from_sky(projection: Mat4, rotation: Quat) -> Mat4 {
+    projection * Mat4::from_quat(rotation.inverse())
+}
+
+/// Returns the matrix reprojecting the sky from the clip space of the current frame to the one of
+/// the previous frame.
+///
+/// A view that didn't move, or that has no previous frame, gets the identity, which is exact so
+/// that the sky of static cameras has no motion at all.
+fn sky_reprojection(previous: Option<Mat4>, current: Mat4) -> Mat4 {
+    match previous {
+        Some(previous) if previous != current => previous * current.inverse(),
+        _ => Mat4::IDENTITY,
+    }
+}
+
+/// Extracts the [`SkyboxPrepassUniforms`] of the cameras with a [`Skybox`] and a
+/// [`MotionVectorPrepass`], or a [`DepthPrepass`] the sky writes its depth into, from the rotation
+/// and projection of each camera in this frame and in the previous one.
+pub(super) fn extract_skybox_prepass_uniforms(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<
+            (
+                Entity,
+                &Camera,
+                &GlobalTransform,
+                &Skybox,
+                Has<MotionVectorPrepass>,
+                Has<DepthPrepass>,
+            ),
+            Without<SkyboxFaces>,
+        >,
+    >,
+    mut previous_views: Local<HashMap<Entity, Mat4>>,
+) {
+    let mut views = HashMap::default();
+    for (entity, camera, transform, skybox, motion_vector_prepass, depth_prepass) in &cameras {
+        if !camera.is_active || !(motion_vector_prepass || (depth_prepass && skybox.write_depth)) {
+            continue;
+        }
+        let mut reprojection = Mat4::IDENTITY;
+        if motion_vector_prepass {
+            let (_, rotation, _) = transform.to_scale_rotation_translation();
+            let view = clip_from_sky(camera.projection_matrix(), rotation);
+            reprojection = sky_reprojection(previous_views.get(&entity).copied(), view);
+            views.insert(entity, view);
+        }
+        commands.get_or_spawn(entity).insert(SkyboxPrepassUniforms {
+            reprojection,
+            far_depth: far_depth(skybox),
+        });
+    }
+    // Cameras that stopped rendering start over from a static sky.
+    *previous_views = views;
+}
+
+/// The pipeline writing the motion vectors and the depth of the sky in the prepass, see
+/// [`Skybox#motion-vectors`] and [`Skybox::write_depth`].
+#[derive(Resource)]
+pub struct SkyboxPrepassPipeline {
+    bind_group_layout: BindGroupLayout,
+}
+
+impl FromWorld for SkyboxPrepassPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let bind_group_layout =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("skybox_prepass_bind_group_layout"),
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0,
+                    // The vertex shader reads the depth of the sky.
+                    visibility: ShaderStages::VERTEX_FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(SkyboxPrepassUniforms::min_size()),
+                    },
+                    count: None,
+                }],
+            });
+        Self { bind_group_layout }
+    }
+}
+
+/// The key the [`SkyboxPrepassPipeline`] is specialized with.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct SkyboxPrepassPipelineKey {
+    /// The [`Msaa`] sample count of the view.
+    pub samples: u32,
+    /// Whether the prepass of the view also writes normals, which the pipeline must have a
+    /// target for.
+    pub normal_prepass: bool,
+    /// Whether the prepass of the view writes motion vectors, which the sky then writes too.
+    pub motion_vector_prepass: bool,
+    /// Whether the sky writes its depth, see [`Skybox::write_depth`].
+    pub write_depth: bool,
+    /// Where the far plane is, see [`Skybox::depth_convention`].
+    pub depth_convention: SkyboxDepthConvention,
+}
+
+impl SkyboxPrepassPipelineKey {
+    /// Returns the key of the pipeline drawing `skybox` into a prepass with the given textures,
+    /// or `None` if the sky writes nothing into it.
+    fn new(
+        skybox: &Skybox,
+        samples: u32,
+        depth_prepass: bool,
+        normal_prepass: bool,
+        motion_vector_prepass: bool,
+    ) -> Option<Self> {
+        let write_depth = skybox.write_depth && depth_prepass;
+        (motion_vector_prepass || write_depth).then_some(Self {
+            samples,
+            normal_prepass,
+            motion_vector_prepass,
+            write_depth,
+            depth_convention: skybox.depth_convention,
+        })
+    }
+
+    /// Returns the color targets of the pipeline, which match the color attachments of the
+    /// prepass without the deferred ones, or an empty list for a depth only prepass.
+    fn targets(&self) -> Vec<Option<ColorTargetState>> {
+        if !self.normal_prepass && !self.motion_vector_prepass {
+            return Vec::new();
+        }
+        vec![
+            self.normal_prepass.then_some(ColorTargetState {
+                format: NORMAL_PREPASS_FORMAT,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            }),
+            self.motion_vector_prepass.then_some(ColorTargetState {
+                format: MOTION_VECTOR_PREPASS_FORMAT,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            }),
+            None,
+            None,
+        ]
+    }
+
+    /// Returns the depth state of the pipeline, which only passes where no geometry was drawn.
+    fn depth_stencil(&self) -> DepthStencilState {
+        DepthStencilState {
+            format: CORE_3D_DEPTH_FORMAT,
+            depth_write_enabled: self.write_depth,
+            depth_compare: self.depth_convention.depth_compare(),
+            stencil: StencilState::default(),
+            bias: DepthBiasState {
+                constant: 0,
+                slope_scale: 0.0,
+                clamp: 0.0,
+            },
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for SkyboxPrepassPipeline {
+    type Key = SkyboxPrepassPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if key.normal_prepass {
+            shader_defs.push("NORMAL_PREPASS".into());
+        }
+        if key.motion_vector_prepass {
+            shader_defs.push("MOTION_VECTOR_PREPASS".into());
+        }
+        let targets = key.targets();
+
+        RenderPipelineDescriptor {
+            label: Some("skybox_prepass_pipeline".into()),
+            layout: vec![self.bind_group_layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: SKYBOX_PREPASS_SHADER_HANDLE,
+                shader_defs: shader_defs.clone(),
+                entry_point: "skybox_prepass_vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: Some(key.depth_stencil()),
+            multisample: MultisampleState {
+                count: key.samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            // The depth is written by the vertex shader alone.
+            fragment: (!targets.is_empty()).then(|| FragmentState {
+                shader: SKYBOX_PREPASS_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "skybox_prepass_fragment".into(),
+                targets,
+            }),
+        }
+    }
+}
+
+/// The id of the specialized [`SkyboxPrepassPipeline`] of a view, in the render world.
+#[derive(Component)]
+pub struct SkyboxPrepassPipelineId(pub CachedRenderPipelineId);
+
+pub(super) fn prepare_skybox_prepass_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SkyboxPrepassPipeline>>,
+    pipeline: Res<SkyboxPrepassPipeline>,
+    msaa: Res<Msaa>,
+    views: Query<
+        (
+            Entity,
+            &Skybox,
+            Option<&RenderLayers>,
+            Has<DepthPrepass>,
+            Has<NormalPrepass>,
+            Has<MotionVectorPrepass>,
+        ),
+        (SkyboxViewFilter, With<SkyboxPrepassUniforms>),
+    >,
+) {
+    for (entity, skybox, view_layers, depth_prepass, normal_prepass, motion_vector_prepass) in
+        &views
+    {
+        // Hidden skies keep the zero motion vectors and the depth the prepass is cleared with.
+        if !skybox.enabled || !skybox.is_visible_in(view_layers) {
+            continue;
+        }
+        let Some(key) = SkyboxPrepassPipelineKey::new(
+            skybox,
+            msaa.samples(),
+            depth_prepass,
+            normal_prepass,
+            motion_vector_prepass,
+        ) else {
+            continue;
+        };
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &pipeline, key);
+        commands
+            .entity(entity)
+            .insert(SkyboxPrepassPipelineId(pipeline_id));
+    }
+}
+
+/// The skybox prepass bind group of a view, along with its dynamic offset.
+#[derive(Component)]
+pub struct SkyboxPrepassBindGroup {
+    pub bind_group: BindGroup,
+    /// The offset of the [`SkyboxPrepassUniforms`] of the view.
+    pub dynamic_offset: u32,
+}
+
+pub(super) fn prepare_skybox_prepass_bind_groups(
+    mut commands: Commands,
+    pipeline: Res<SkyboxPrepassPipeline>,
+    uniforms: Res<ComponentUniforms<SkyboxPrepassUniforms>>,
+    render_device: Res<RenderDevice>,
+    views: Query<
+        (Entity, &DynamicUniformIndex<SkyboxPrepassUniforms>),
+        With<SkyboxPrepassPipelineId>,
+    >,
+    // Views only differ by their dynamic offset, so they share a bind group until the buffer of
+    // the uniforms is reallocated.
+    mut cached_bind_group: Local<Option<(BufferId, BindGroup)>>,
+) {
+    let (Some(binding), Some(buffer)) = (uniforms.binding(), uniforms.buffer()) else {
+        return;
+    };
+    let bind_group = match &*cached_bind_group {
+        Some((buffer_id, bind_group)) if *buffer_id == buffer.id() => bind_group.clone(),
+        _ => {
+            let bind_group = render_device.create_bind_group(
+                "skybox_prepass_bind_group",
+                &pipeline.bind_group_layout,
+                &BindGroupEntries::single(binding),
+            );
+            *cached_bind_group = Some((buffer.id(), bind_group.clone()));
+            bind_group
+        }
+    };
+
+    for (entity, uniform_index) in &views {
+        commands.entity(entity).insert(SkyboxPrepassBindGroup {
+            bind_group: bind_group.clone(),
+            dynamic_offset: uniform_index.index(),
+        });
+    }
+}
+
+/// Draws the motion vectors and the depth of the sky into the prepass of a view, over the pixels
+/// no geometry was drawn to.
+pub(crate) fn draw_skybox_prepass(
+    render_pass: &mut TrackedRenderPass,
+    world: &World,
+    (pipeline_id, bind_group): (&SkyboxPrepassPipelineId, &SkyboxPrepassBindGroup),
+) {
+    let pipeline_cache = world.resource::<PipelineCache>();
+    let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+        return;
+    };
+    render_pass.set_render_pipeline(pipeline);
+    render_pass.set_bind_group(0, &bind_group.bind_group, &[bind_group.dynamic_offset]);
+    render_pass.draw(0..3, 0..1);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Mat4, Quat, Vec3, Vec4};
+    use bevy_render::render_resource::CompareFunction;
+
+    use super::{clip_from_sky, far_depth, sky_reprojection, SkyboxPrepassPipelineKey};
+    use crate::skybox::{Skybox, SkyboxDepthConvention, SkyboxDepthRange};
+
+    fn projection() -> Mat4 {
+        Mat4::perspective_infinite_reverse_rh(std::f32::consts::FRAC_PI_4, 16.0 / 9.0, 0.1)
+    }
+
+    #[test]
+    fn static_sky_has_no_motion() {
+        let view = clip_from_sky(projection(), Quat::from_rotation_y(0.3));
+        assert_eq!(sky_reprojection(None, view), Mat4::IDENTITY);
+        assert_eq!(sky_reprojection(Some(view), view), Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn rotating_camera_reprojects_the_sky() {
+        let previous = clip_from_sky(projection(), Quat::IDENTITY);
+        let current = clip_from_sky(projection(), Quat::from_rotation_y(0.1));
+        let reprojection = sky_reprojection(Some(previous), current);
+
+        // The direction at the center of the previous frame, where the sky is drawn.
+        let direction = Vec3::NEG_Z.extend(0.0);
+        let ndc = |clip: Vec4| clip.truncate().truncate() / clip.w;
+        let current_ndc = ndc(current * direction);
+        // Turning left moves the sky to the right of the screen.
+        assert!(current_ndc.x > 0.1);
+
+        let previous_ndc = ndc(reprojection * current_ndc.extend(0.0).extend(1.0));
+        assert!(previous_ndc.abs().max_element() < 1e-4);
+    }
+
+    #[test]
+    fn depth_is_only_written_into_depth_prepasses() {
+        let skybox = Skybox {
+            write_depth: true,
+            ..Default::default()
+        };
+
+        let key = SkyboxPrepassPipelineKey::new(&skybox, 4, true, false, false).unwrap();
+        assert!(key.write_depth);
+        let depth_stencil = key.depth_stencil();
+        assert!(depth_stencil.depth_write_enabled);
+        // Geometry drawn before the sky keeps its depth.
+        assert_eq!(depth_stencil.depth_compare, CompareFunction::GreaterEqual);
+        // A depth only prepass has no color attachments, which the pipeline must match.
+        assert!(key.targets().is_empty());
+
+        // Without a depth prepass, the sky only writes motion vectors, if any.
+        assert!(SkyboxPrepassPipelineKey::new(&skybox, 4, false, false, false).is_none());
+        let key = SkyboxPrepassPipelineKey::new(&skybox, 4, false, true, true).unwrap();
+        assert!(!key.depth_stencil().depth_write_enabled);
+        assert_eq!(key.targets().len(), 4);
+
+        // The depth is left alone by default.
+        let key = SkyboxPrepassPipelineKey::new(&Skybox::default(), 4, true, false, true).unwrap();
+        assert!(!key.write_depth);
+        assert!(SkyboxPrepassPipelineKey::new(&Skybox::default(), 4, true, false, false).is_none());
+    }
+
+    #[test]
+    fn sky_depth_follows_the_depth_range() {
+        assert_eq!(far_depth(&Skybox::default()), 0.0);
+
+        let standard_z = Skybox {
+            depth_convention: SkyboxDepthConvention::StandardZ,
+            ..Default::default()
+        };
+        assert_eq!(far_depth(&standard_z), 1.0);
+
+        let near_segment = Skybox {
+            depth_range: Some(SkyboxDepthRange {
+                near: 1.0,
+                far: 0.5,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(far_depth(&near_segment), 0.5);
+    }
+}
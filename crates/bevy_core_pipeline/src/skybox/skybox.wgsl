@@ -1,9 +1,77 @@
 #import bevy_render::view::View
-#import bevy_pbr::utils::coords_to_viewport_uv
+#import bevy_pbr::utils::{coords_to_viewport_uv, PI}
+#ifdef DITHER
+#import bevy_core_pipeline::tonemapping::{screen_space_dither, powsafe}
+#endif
 
+struct SkyboxUniforms {
+    transform: mat3x3<f32>,
+    brightness: f32,
+    blend: f32,
+    tint: vec4<f32>,
+    layer: u32,
+    mip_bias: f32,
+    orthographic_scale: f32,
+    loading_color: vec4<f32>,
+    pan: vec2<f32>,
+    max_luminance: f32,
+    dither_strength: f32,
+    saturation: f32,
+    contrast: f32,
+    horizon_fade_color: vec4<f32>,
+    horizon_fade_start: f32,
+    horizon_fade_end: f32,
+    detail_mip_bias: f32,
+    alpha: f32,
+    far_depth: f32,
+}
+
+#ifdef FOG
+struct SkyboxFog {
+    color: vec4<f32>,
+}
+#endif
+
+#ifndef SOLID
+#ifdef EQUIRECTANGULAR
+@group(0) @binding(0) var skybox: texture_2d<f32>;
+#else ifdef CUBE_ARRAY
+@group(0) @binding(0) var skybox: texture_cube_array<f32>;
+#else
 @group(0) @binding(0) var skybox: texture_cube<f32>;
+#endif
 @group(0) @binding(1) var skybox_sampler: sampler;
+#endif
 @group(0) @binding(2) var<uniform> view: View;
+@group(0) @binding(3) var<uniform> uniforms: SkyboxUniforms;
+#ifdef BLEND
+#ifdef EQUIRECTANGULAR
+@group(0) @binding(4) var skybox_blend_target: texture_2d<f32>;
+#else ifdef CUBE_ARRAY
+@group(0) @binding(4) var skybox_blend_target: texture_cube_array<f32>;
+#else
+@group(0) @binding(4) var skybox_blend_target: texture_cube<f32>;
+#endif
+#endif
+#ifdef FOG
+@group(0) @binding(5) var<uniform> fog: SkyboxFog;
+#endif
+#ifdef LUT_CURVE
+@group(0) @binding(6) var skybox_lut: texture_1d<f32>;
+@group(0) @binding(7) var skybox_lut_sampler: sampler;
+#else ifdef LUT_CUBE
+@group(0) @binding(6) var skybox_lut: texture_3d<f32>;
+@group(0) @binding(7) var skybox_lut_sampler: sampler;
+#endif
+#ifdef DETAIL
+@group(0) @binding(8) var skybox_detail: texture_cube<f32>;
+#endif
+#ifdef DETAIL_SAMPLER
+@group(0) @binding(9) var skybox_detail_sampler: sampler;
+#endif
+#ifdef ALPHA_MASK
+@group(0) @binding(10) var skybox_alpha_mask: texture_2d<f32>;
+#endif
 
 fn coords_to_ray_direction(position: vec2<f32>, viewport: vec4<f32>) -> vec3<f32> {
     // Using world positions of the fragment and camera to calculate a ray direction
@@ -12,14 +80,27 @@ fn coords_to_ray_direction(position: vec2<f32>, viewport: vec4<f32>) -> vec3<f32
     // In view space, the camera is at the origin, so the view space ray direction is
     // along the direction of the fragment position - (0,0,0) which is just the
     // fragment position.
+    let ndc_xy = coords_to_viewport_uv(position, viewport) * vec2(2.0, -2.0) + vec2(-1.0, 1.0);
+#ifdef ORTHOGRAPHIC
+    // All rays of an orthographic projection are parallel, so they would all sample the
+    // same texel. Spread them over a perspective frustum around the camera forward
+    // direction instead, scaled by the tangent of half the configured field of view.
+    let aspect = viewport.z / viewport.w;
+    let view_ray_direction = vec3(
+        ndc_xy * vec2(aspect, 1.0) * uniforms.orthographic_scale,
+        -1.0,
+    );
+#else
     // Use the position on the near clipping plane to avoid -inf world position
     // because the far plane of an infinite reverse projection is at infinity.
-    let view_position_homogeneous = view.inverse_projection * vec4(
-        coords_to_viewport_uv(position, viewport) * vec2(2.0, -2.0) + vec2(-1.0, 1.0),
-        1.0,
-        1.0,
-    );
+#ifdef STANDARD_Z
+    let near_z = 0.0;
+#else
+    let near_z = 1.0;
+#endif
+    let view_position_homogeneous = view.inverse_projection * vec4(ndc_xy, near_z, 1.0);
     let view_ray_direction = view_position_homogeneous.xyz / view_position_homogeneous.w;
+#endif
     // Transforming the view space ray direction by the view matrix, transforms the
     // direction to world space. Note that the w element is set to 0.0, as this is a
     // vector direction, not a position, That causes the matrix multiplication to ignore
@@ -29,6 +110,187 @@ fn coords_to_ray_direction(position: vec2<f32>, viewport: vec4<f32>) -> vec3<f32
     return normalize(ray_direction);
 }
 
+#ifndef SOLID
+// Pans the images across the sky, by turning the sample direction the opposite way, first
+// around +Y by the azimuth offset and then around +X by the elevation offset.
+fn pan_direction(direction: vec3<f32>, pan: vec2<f32>) -> vec3<f32> {
+    let azimuth = vec2(cos(pan.x), sin(pan.x));
+    let turned = vec3(
+        direction.x * azimuth.x - direction.z * azimuth.y,
+        direction.y,
+        direction.x * azimuth.y + direction.z * azimuth.x,
+    );
+    let elevation = vec2(cos(pan.y), sin(pan.y));
+    return vec3(
+        turned.x,
+        turned.y * elevation.x + turned.z * elevation.y,
+        turned.z * elevation.x - turned.y * elevation.y,
+    );
+}
+
+#ifdef EQUIRECTANGULAR
+struct EquirectangularCoords {
+    uv: vec2<f32>,
+    ddx: vec2<f32>,
+    ddy: vec2<f32>,
+}
+
+fn equirectangular_coords(direction: vec3<f32>) -> EquirectangularCoords {
+    // Longitude around the Y axis, with the center of the image facing -Z,
+    // and latitude from the top (+Y) to the bottom (-Y) of the image.
+    let uv = vec2(
+        atan2(direction.x, -direction.z) / (2.0 * PI) + 0.5,
+        acos(clamp(direction.y, -1.0, 1.0)) / PI,
+    );
+
+    // The longitude wraps from 1.0 back to 0.0 at +Z, so its screen space derivatives
+    // blow up along that line, which would select the smallest mip and leave a visible
+    // seam. Derive the gradient from a copy of the longitude whose wrap is moved to the
+    // opposite side of the sphere instead, whichever of the two is continuous here.
+    let u_shifted = fract(uv.x + 0.5);
+    let du_dx = select(dpdx(uv.x), dpdx(u_shifted), abs(dpdx(u_shifted)) < abs(dpdx(uv.x)));
+    let du_dy = select(dpdy(uv.x), dpdy(u_shifted), abs(dpdy(u_shifted)) < abs(dpdy(uv.x)));
+
+    // Scaling the gradients by 2^bias offsets the selected mip level by the bias.
+    let bias_scale = exp2(uniforms.mip_bias);
+    return EquirectangularCoords(
+        uv,
+        vec2(du_dx, dpdx(uv.y)) * bias_scale,
+        vec2(du_dy, dpdy(uv.y)) * bias_scale,
+    );
+}
+#endif
+
+#ifdef CUBE_EDGE_FIXUP
+// Pulls the cube `coords` towards the center of their face by half a texel of the base mip
+// level, so that filtering never reads across the edge of the face, even without seamless
+// cube filtering. This stretches the faces by a texel, which is only noticeable on tiny
+// cubemaps.
+fn cube_edge_fixup(coords: vec3<f32>) -> vec3<f32> {
+    let size = f32(textureDimensions(skybox).x);
+    let magnitude = abs(coords);
+    let major_axis = max(magnitude.x, max(magnitude.y, magnitude.z));
+    // Only the coordinates across the face are scaled, the major axis selects the face.
+    return select(coords * (size - 1.0) / size, coords, magnitude == vec3(major_axis));
+}
+#endif
+
+fn sample_skybox(direction: vec3<f32>) -> vec4<f32> {
+#ifdef EQUIRECTANGULAR
+    let coords = equirectangular_coords(direction);
+    var color = textureSampleGrad(skybox, skybox_sampler, coords.uv, coords.ddx, coords.ddy);
+#ifdef BLEND
+    let target_color = textureSampleGrad(
+        skybox_blend_target,
+        skybox_sampler,
+        coords.uv,
+        coords.ddx,
+        coords.ddy,
+    );
+    color = mix(color, target_color, uniforms.blend);
+#endif
+#else
+    // Cube maps are left-handed so we negate the z coordinate.
+    var coords = direction * vec3(1.0, 1.0, -1.0);
+#ifdef CUBE_EDGE_FIXUP
+    coords = cube_edge_fixup(coords);
+#endif
+    // The biased level of detail is clamped to the mips the texture actually has.
+    let bias = uniforms.mip_bias;
+#ifdef CUBE_ARRAY
+    // Hold the last frame rather than sampling past the end of the array.
+    let layer = min(uniforms.layer, textureNumLayers(skybox) - 1u);
+    var color = textureSampleBias(skybox, skybox_sampler, coords, layer, bias);
+#ifdef BLEND
+    let target_color = textureSampleBias(skybox_blend_target, skybox_sampler, coords, layer, bias);
+    color = mix(color, target_color, uniforms.blend);
+#endif
+#else
+    var color = textureSampleBias(skybox, skybox_sampler, coords, bias);
+#ifdef BLEND
+    let target_color = textureSampleBias(skybox_blend_target, skybox_sampler, coords, bias);
+    color = mix(color, target_color, uniforms.blend);
+#endif
+#endif
+#endif
+    return color;
+}
+#endif
+
+#ifdef DETAIL
+// Combines the detail layer sampled along `direction` with `color`, masked by its alpha.
+fn apply_detail(color: vec4<f32>, direction: vec3<f32>) -> vec4<f32> {
+    // Cube maps are left-handed so we negate the z coordinate.
+    let coords = direction * vec3(1.0, 1.0, -1.0);
+#ifdef DETAIL_SAMPLER
+    let detail = textureSampleBias(
+        skybox_detail, skybox_detail_sampler, coords, uniforms.detail_mip_bias
+    );
+#else
+    let detail = textureSampleBias(skybox_detail, skybox_sampler, coords, uniforms.detail_mip_bias);
+#endif
+#ifdef DETAIL_ADD
+    let layered = color.rgb + detail.rgb;
+#else ifdef DETAIL_MULTIPLY
+    let layered = color.rgb * detail.rgb;
+#else ifdef DETAIL_SCREEN
+    let layered = 1.0 - (1.0 - color.rgb) * (1.0 - detail.rgb);
+#endif
+    return vec4(mix(color.rgb, layered, detail.a), color.a);
+}
+#endif
+
+#ifdef DECODE_SRGB
+// The image is sRGB encoded, but its format is linear, so the GPU doesn't decode it.
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, color <= vec3(0.04045));
+}
+#endif
+
+#ifdef ENCODE_SRGB
+// The image is linear, but its format is sRGB, so undo the decoding of the GPU.
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3(0.0031308));
+}
+#endif
+
+#ifdef ADJUST_COLORS
+fn adjust_colors(color: vec3<f32>) -> vec3<f32> {
+    // Scale the difference to the gray of the same luminance.
+    let luminance = dot(color, vec3(0.2126, 0.7152, 0.0722));
+    let saturated = max(mix(vec3(luminance), color, uniforms.saturation), vec3(0.0));
+    // Scale the ratio to middle gray in logarithmic space, which suits HDR colors.
+    let middle_gray = 0.18;
+    return middle_gray * pow(saturated / middle_gray, vec3(uniforms.contrast));
+}
+#endif
+
+#ifdef LUT_CURVE
+// Maps each channel of `color` through the curve of the table.
+fn apply_lut(color: vec3<f32>) -> vec3<f32> {
+    // Map 0.0 and 1.0 to the centers of the first and last texels.
+    let size = f32(textureDimensions(skybox_lut));
+    let coords = saturate(color) * ((size - 1.0) / size) + 0.5 / size;
+    return vec3(
+        textureSample(skybox_lut, skybox_lut_sampler, coords.r).r,
+        textureSample(skybox_lut, skybox_lut_sampler, coords.g).g,
+        textureSample(skybox_lut, skybox_lut_sampler, coords.b).b,
+    );
+}
+#else ifdef LUT_CUBE
+// Maps `color` through the color cube of the table.
+fn apply_lut(color: vec3<f32>) -> vec3<f32> {
+    // Map 0.0 and 1.0 to the centers of the first and last texels.
+    let size = vec3<f32>(textureDimensions(skybox_lut));
+    let coords = saturate(color) * ((size - 1.0) / size) + 0.5 / size;
+    return textureSampleLevel(skybox_lut, skybox_lut_sampler, coords, 0.0).rgb;
+}
+#endif
+
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
 };
@@ -47,20 +309,108 @@ struct VertexOutput {
 @vertex
 fn skybox_vertex(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
     // See the explanation above for how this works.
-    let clip_position = vec4(
+    var clip_position = vec4(
         f32(vertex_index & 1u),
         f32((vertex_index >> 1u) & 1u),
         0.25,
         0.5
     ) * 4.0 - vec4(1.0);
+#ifdef DEPTH_RANGE
+    // The far end of the depth segment of the view, the w of the triangle is 1.0.
+    clip_position.z = uniforms.far_depth;
+#else ifdef STANDARD_Z
+    // The far plane is at a depth of 1.0 instead of 0.0 without reverse-z.
+    clip_position.z = clip_position.w;
+#endif
 
     return VertexOutput(clip_position);
 }
 
 @fragment
 fn skybox_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    let ray_direction = coords_to_ray_direction(in.position.xy, view.viewport);
+    let world_ray_direction = coords_to_ray_direction(in.position.xy, view.viewport);
+    // Apply the skybox orientation on top of the view transform.
+    let ray_direction = uniforms.transform * world_ray_direction;
 
-    // Cube maps are left-handed so we negate the z coordinate.
-    return textureSample(skybox, skybox_sampler, ray_direction * vec3(1.0, 1.0, -1.0));
+#ifdef SOLID
+    // The images are still loading, draw the loading color in the meantime.
+    var color = uniforms.loading_color;
+#else
+    // Panning is independent of the orientation, and happens in the space of the images.
+    let panned_direction = pan_direction(ray_direction, uniforms.pan);
+#ifdef FLIP_Y
+    // Mirror the image vertically, for images authored with the opposite up direction.
+    let sample_direction = panned_direction * vec3(1.0, -1.0, 1.0);
+#else
+    let sample_direction = panned_direction;
+#endif
+    var color = sample_skybox(sample_direction);
+#ifdef DETAIL
+    color = apply_detail(color, sample_direction);
+#endif
+#ifdef DECODE_SRGB
+    color = vec4(srgb_to_linear(color.rgb), color.a);
+#else ifdef ENCODE_SRGB
+    color = vec4(linear_to_srgb(color.rgb), color.a);
+#endif
+#ifdef ADJUST_COLORS
+    color = vec4(adjust_colors(color.rgb), color.a);
+#endif
+#ifdef LUT_CURVE
+    color = vec4(apply_lut(color.rgb), color.a);
+#else ifdef LUT_CUBE
+    color = vec4(apply_lut(color.rgb), color.a);
+#endif
+    color *= uniforms.brightness * uniforms.tint;
+#endif
+
+#ifdef CLAMP_LUMINANCE
+    // Scale down overly bright pixels, e.g. the sun, keeping their hue, so they don't blow
+    // out bloom.
+    let luminance = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+    if luminance > uniforms.max_luminance {
+        color = vec4(color.rgb * (uniforms.max_luminance / luminance), color.a);
+    }
+#endif
+
+#ifdef HORIZON_FADE
+    // Fade by the elevation of the ray in the world, regardless of the skybox orientation.
+    let world_elevation = asin(clamp(world_ray_direction.y, -1.0, 1.0));
+    let fade_amount = uniforms.horizon_fade_color.a * saturate(
+        (uniforms.horizon_fade_start - world_elevation)
+            / (uniforms.horizon_fade_start - uniforms.horizon_fade_end)
+    );
+    color = vec4(mix(color.rgb, uniforms.horizon_fade_color.rgb, fade_amount), color.a);
+#endif
+
+#ifdef FOG
+    // The sky is infinitely far away, so distance fog covers it completely at and below
+    // the horizon. Fade it out with the elevation of the ray to keep the zenith visible.
+    let elevation = clamp(ray_direction.y, 0.0, 1.0);
+    let fog_amount = fog.color.a * pow(1.0 - elevation, 8.0);
+    color = vec4(mix(color.rgb, fog.color.rgb, fog_amount), color.a);
+#endif
+
+#ifdef DITHER
+    // The 8 bit target quantizes smooth gradients into bands. Dither in roughly perceptual
+    // space, where the quantization steps are, since the target converts linear colors to sRGB.
+    var dithered = powsafe(color.rgb, 1.0 / 2.2);
+    dithered += screen_space_dither(in.position.xy) * uniforms.dither_strength;
+    color = vec4(powsafe(dithered, 2.2), color.a);
+#endif
+
+#ifdef ALPHA_OUTPUT
+    var alpha = uniforms.alpha;
+#ifdef ALPHA_MASK
+    // Stretch the mask over the viewport, reading the texel under the pixel.
+    let mask_size = textureDimensions(skybox_alpha_mask);
+    let mask_uv = coords_to_viewport_uv(in.position.xy, view.viewport);
+    let mask_texel = min(vec2<u32>(mask_uv * vec2<f32>(mask_size)), mask_size - 1u);
+    alpha *= textureLoad(skybox_alpha_mask, mask_texel, 0).r;
+#endif
+    // Premultiplied, to be blended over the background.
+    return vec4(color.rgb * alpha, alpha);
+#else
+    return color;
+#endif
 }
@@ -0,0 +1,53 @@
+struct SkyboxPrepassUniforms {
+    reprojection: mat4x4<f32>,
+    far_depth: f32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: SkyboxPrepassUniforms;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+};
+
+struct FragmentOutput {
+#ifdef NORMAL_PREPASS
+    @location(0) normal: vec4<f32>,
+#endif
+#ifdef MOTION_VECTOR_PREPASS
+    @location(1) motion_vector: vec2<f32>,
+#endif
+}
+
+// The same fullscreen triangle as `skybox_vertex`, at the depth the sky is drawn at.
+@vertex
+fn skybox_prepass_vertex(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var clip_position = vec4(
+        f32(vertex_index & 1u),
+        f32((vertex_index >> 1u) & 1u),
+        0.25,
+        0.5
+    ) * 4.0 - vec4(1.0);
+    clip_position.z = uniforms.far_depth;
+
+    // The w of the triangle is 1.0, so its clip space xy are the normalized device coordinates.
+    return VertexOutput(clip_position, clip_position.xy);
+}
+
+@fragment
+fn skybox_prepass_fragment(in: VertexOutput) -> FragmentOutput {
+    var out: FragmentOutput;
+#ifdef NORMAL_PREPASS
+    // The sky has no normal, so it keeps the value the normals are cleared with.
+    out.normal = vec4(0.0, 0.0, 0.0, 1.0);
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+    // The identity reprojection of static cameras gives exactly zero motion.
+    let previous_clip_position = uniforms.reprojection * vec4(in.ndc, 0.0, 1.0);
+    let previous_ndc = previous_clip_position.xy / previous_clip_position.w;
+    // Same convention as the motion vectors of meshes, in UV space.
+    out.motion_vector = (in.ndc - previous_ndc) * vec2(0.5, -0.5);
+#endif
+    return out;
+}
@@ -14,7 +14,7 @@ use proc_macro2::Span;
 use quote::{format_ident, quote};
 use syn::{
     parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned, token::Comma,
-    ConstParam, DeriveInput, GenericParam, Ident, Index, TypeParam,
+    ConstParam, DeriveInput, Fields, GenericParam, Ident, Index, TypeParam,
 };
 
 enum BundleFieldKind {
@@ -25,6 +25,11 @@ enum BundleFieldKind {
 const BUNDLE_ATTRIBUTE_NAME: &str = "bundle";
 const BUNDLE_ATTRIBUTE_IGNORE_NAME: &str = "ignore";
 
+const SYSTEM_PARAM_ATTRIBUTE_NAME: &str = "system_param";
+const SYSTEM_PARAM_ATTRIBUTE_IGNORE_NAME: &str = "ignore";
+const SYSTEM_PARAM_ATTRIBUTE_BUILDER_NAME: &str = "builder";
+const PARAM_SET_ATTRIBUTE_NAME: &str = "param_set";
+
 #[proc_macro_derive(Bundle, attributes(bundle))]
 pub fn derive_bundle(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -146,9 +151,11 @@ fn get_idents(fmt_string: fn(usize) -> String, count: usize) -> Vec<Ident> {
 #[proc_macro]
 pub fn impl_param_set(_input: TokenStream) -> TokenStream {
     let mut tokens = TokenStream::new();
-    let max_params = 8;
+    // Keep in sync with the name of `ParamSetUpTo16NestForMore`.
+    let max_params = 16;
     let params = get_idents(|i| format!("P{i}"), max_params);
     let metas = get_idents(|i| format!("m{i}"), max_params);
+    let states = get_idents(|i| format!("s{i}"), max_params);
     let mut param_fn_muts = Vec::new();
     for (i, param) in params.iter().enumerate() {
         let fn_name = Ident::new(&format!("p{i}"), Span::call_site());
@@ -175,11 +182,68 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
         });
     }
 
-    for param_count in 1..=max_params {
+    // The empty set is implemented as well, so that generic code doesn't need to special-case
+    // it. It has no accessors, and its state is empty.
+    for param_count in 0..=max_params {
         let param = &params[0..param_count];
         let meta = &metas[0..param_count];
+        let state = &states[0..param_count];
         let param_fn_mut = &param_fn_muts[0..param_count];
+        let same_param = vec![quote!(P); param_count];
+        let index = (0..param_count).map(Index::from);
+        let arm = 0..param_count;
+        // The empty set has no parameters to access. Without any parameter, `P` of `get_mut`
+        // wouldn't even be constrained by the type of the set.
+        let all_read = (param_count > 0).then(|| {
+            quote! {
+                impl<'w, 's, #(#param: ReadOnlySystemParam,)*> ParamSet<'w, 's, (#(#param,)*)>
+                {
+                    /// Gets shared access to all parameters in this [`ParamSet`] at once.
+                    ///
+                    /// This is only available if all parameters are [`ReadOnlySystemParam`]s,
+                    /// which never conflict with each other since they only read the world.
+                    pub fn all_read<'a>(&'a mut self) -> (#(SystemParamItem<'a, 'a, #param>,)*) {
+                        let (#(#state,)*) = &mut *self.param_states;
+                        // SAFETY: systems run without conflicts with other systems.
+                        // The parameters only read the world, so they don't conflict with each other,
+                        // and each of them borrows its own state.
+                        // ParamSets are guaranteed to not conflict with other SystemParams
+                        unsafe {
+                            (#(#param::get_param(#state, &self.system_meta, self.world, self.change_tick),)*)
+                        }
+                    }
+                }
+            }
+        });
+        let get_mut = (param_count > 0).then(|| {
+            quote! {
+                impl<'w, 's, P: SystemParam> ParamSet<'w, 's, (#(#same_param,)*)>
+                {
+                    /// Gets exclusive access to the parameter at `index` in this [`ParamSet`],
+                    /// or `None` if `index` is out of range.
+                    ///
+                    /// This is only available if all parameters have the same type, which allows
+                    /// choosing the parameter at runtime, e.g. to loop over them.
+                    /// No other parameters may be accessed while this one is active.
+                    pub fn get_mut<'a>(&'a mut self, index: usize) -> Option<SystemParamItem<'a, 'a, P>> {
+                        match index {
+                            #(
+                                // SAFETY: systems run without conflicts with other systems.
+                                // Conflicting params in ParamSet are not accessible at the same time
+                                // ParamSets are guaranteed to not conflict with other SystemParams
+                                #arm => Some(unsafe {
+                                    P::get_param(&mut self.param_states.#index, &self.system_meta, self.world, self.change_tick)
+                                }),
+                            )*
+                            _ => None,
+                        }
+                    }
+                }
+            }
+        });
         tokens.extend(TokenStream::from(quote! {
+            impl<#(#param: SystemParam,)*> ParamSetUpTo16NestForMore for (#(#param,)*) {}
+
             // SAFETY: All parameters are constrained to ReadOnlySystemParam, so World is only read
             unsafe impl<'w, 's, #(#param,)*> ReadOnlySystemParam for ParamSet<'w, 's, (#(#param,)*)>
             where #(#param: ReadOnlySystemParam,)*
@@ -189,12 +253,16 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
             // with any prior access, a panic will occur.
             unsafe impl<'_w, '_s, #(#param: SystemParam,)*> SystemParam for ParamSet<'_w, '_s, (#(#param,)*)>
             {
-                type State = (#(#param::State,)*);
+                /// The states of the parameters, and whether each of them only reads the world.
+                type State = ((#(#param::State,)*), [bool; #param_count]);
                 type Item<'w, 's> = ParamSet<'w, 's, (#(#param,)*)>;
 
+                #[allow(unused_variables)]
                 fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
                     #(
-                        // Pretend to add each param to the system alone, see if it conflicts
+                        // Pretend to add each param to the system alone, see if it conflicts.
+                        // A nested ParamSet does the same for its own members against the
+                        // access of the system so far, and adds all of their access to `#meta`.
                         let mut #meta = system_meta.clone();
                         #meta.component_access_set.clear();
                         #meta.archetype_component_access.clear();
@@ -205,6 +273,7 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
                     if false #(|| !#meta.is_send())* {
                         system_meta.set_non_send();
                     }
+                    let read_only = [#(!#meta.component_access_set.combined_access().has_any_write(),)*];
                     #(
                         system_meta
                             .component_access_set
@@ -213,15 +282,24 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
                             .archetype_component_access
                             .extend(&#meta.archetype_component_access);
                     )*
-                    (#(#param,)*)
+                    ((#(#param,)*), read_only)
                 }
 
                 fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
-                    <(#(#param,)*) as SystemParam>::new_archetype(state, archetype, system_meta);
+                    <(#(#param,)*) as SystemParam>::new_archetype(&mut state.0, archetype, system_meta);
                 }
 
                 fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
-                    <(#(#param,)*) as SystemParam>::apply(state, system_meta, world);
+                    <(#(#param,)*) as SystemParam>::apply(&mut state.0, system_meta, world);
+                }
+
+                #[inline]
+                unsafe fn validate_param(
+                    state: &Self::State,
+                    system_meta: &SystemMeta,
+                    world: UnsafeWorldCell,
+                ) -> bool {
+                    <(#(#param,)*) as SystemParam>::validate_param(&state.0, system_meta, world)
                 }
 
                 #[inline]
@@ -231,8 +309,10 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
                     world: UnsafeWorldCell<'w>,
                     change_tick: Tick,
                 ) -> Self::Item<'w, 's> {
+                    let (param_states, read_only) = state;
                     ParamSet {
-                        param_states: state,
+                        param_states,
+                        read_only,
                         system_meta: system_meta.clone(),
                         world,
                         change_tick,
@@ -242,8 +322,15 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
 
             impl<'w, 's, #(#param: SystemParam,)*> ParamSet<'w, 's, (#(#param,)*)>
             {
+                /// The number of parameters in this [`ParamSet`].
+                pub const LEN: usize = #param_count;
+
                 #(#param_fn_mut)*
             }
+
+            #all_read
+
+            #get_mut
         }));
     }
 
@@ -268,20 +355,55 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
         .into();
     };
     let path = bevy_ecs_path();
+    let struct_attributes = match SystemParamAttributes::parse(&ast.attrs) {
+        Ok(attributes) => attributes,
+        Err(err) => return err.into_compile_error().into(),
+    };
 
     let mut field_locals = Vec::new();
     let mut fields = Vec::new();
     let mut field_types = Vec::new();
+    let mut field_visibilities = Vec::new();
+    let mut builder_members = Vec::new();
+    let mut builder_params = Vec::new();
+    let mut ignored_fields = Vec::new();
+    let mut param_set_accessors = Vec::new();
     for (i, field) in field_definitions.iter().enumerate() {
-        field_locals.push(format_ident!("f{i}"));
+        let attributes = match SystemParamFieldAttributes::parse(field) {
+            Ok(attributes) => attributes,
+            Err(err) => return err.into_compile_error().into(),
+        };
+        let local = format_ident!("f{i}");
         let i = Index::from(i);
-        fields.push(
-            field
-                .ident
-                .as_ref()
-                .map(|f| quote! { #f })
-                .unwrap_or_else(|| quote! { #i }),
-        );
+        let member = field
+            .ident
+            .as_ref()
+            .map(|f| quote! { #f })
+            .unwrap_or_else(|| quote! { #i });
+        if attributes.ignore {
+            ignored_fields.push(member);
+            continue;
+        }
+        match system_param_field_accessors(&path, field, &member, &attributes.param_set) {
+            Ok(accessors) => param_set_accessors.extend(accessors),
+            Err(err) => return err.into_compile_error().into(),
+        }
+        // The builder only has the fields that aren't ignored, so its tuple fields are renumbered.
+        let builder_member = match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => {
+                let index = Index::from(builder_members.len());
+                quote! { #index }
+            }
+        };
+        builder_members.push(builder_member);
+        builder_params.push(ensure_no_collision(
+            format_ident!("B{}", builder_params.len()),
+            token_stream.clone(),
+        ));
+        field_visibilities.push(&field.vis);
+        field_locals.push(local);
+        fields.push(member);
         field_types.push(&field.ty);
     }
 
@@ -304,7 +426,7 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
         }
     }
 
-    let (_impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let lifetimeless_generics: Vec<_> = generics
         .params
@@ -347,6 +469,16 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
 
     let mut tuple_types: Vec<_> = field_types.iter().map(|x| quote! { #x }).collect();
     let mut tuple_patterns: Vec<_> = field_locals.iter().map(|x| quote! { #x }).collect();
+    let mut tuple_builds: Vec<_> = field_locals
+        .iter()
+        .zip(&builder_params)
+        .zip(&field_types)
+        .map(|((local, builder), field_type)| {
+            quote! {
+                <#builder as #path::system::SystemParamBuilder<#field_type>>::build(#local, world, meta)
+            }
+        })
+        .collect();
 
     // If the number of fields exceeds the 16-parameter limit,
     // fold the fields into tuples of tuples until we are below the limit.
@@ -357,6 +489,9 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
 
         let end = Vec::from_iter(tuple_patterns.drain(..LIMIT));
         tuple_patterns.push(parse_quote!( (#(#end,)*) ));
+
+        let end = Vec::from_iter(tuple_builds.drain(..LIMIT));
+        tuple_builds.push(parse_quote!( (#(#end,)*) ));
     }
 
     // Create a where clause for the `ReadOnlySystemParam` impl.
@@ -376,7 +511,59 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
     let state_struct_visibility = &ast.vis;
     let state_struct_name = ensure_no_collision(format_ident!("FetchState"), token_stream);
 
+    // The builder type is declared outside of the anonymous scope so that it can be named by users,
+    // while its `SystemParamBuilder` impl needs the `FetchState` struct from inside of it.
+    let (builder_struct, builder_impl) = if struct_attributes.builder {
+        let builder_name = format_ident!("{}Builder", struct_name);
+        let builder_doc = format!(
+            "A `SystemParamBuilder` for [`{struct_name}`], with a builder for each of its fields."
+        );
+        let builder_fields = if matches!(field_definitions, Fields::Unnamed(_)) {
+            quote! { (#(#field_visibilities #builder_params,)*); }
+        } else {
+            quote! { { #(#field_visibilities #builder_members: #builder_params,)* } }
+        };
+        let builder_struct = quote! {
+            #[doc = #builder_doc]
+            #[derive(::std::default::Default, ::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy)]
+            #state_struct_visibility struct #builder_name <#(#builder_params,)*> #builder_fields
+        };
+
+        let mut builder_generics = generics.clone();
+        builder_generics
+            .params
+            .extend(builder_params.iter().zip(&field_types).map(
+                |(builder, field_type)| -> GenericParam {
+                    parse_quote!(#builder: #path::system::SystemParamBuilder<#field_type>)
+                },
+            ));
+        let (builder_impl_generics, _, builder_where_clause) = builder_generics.split_for_impl();
+        let builder_impl = quote! {
+            // SAFETY: Each field's builder registers the world accesses of that field, like the
+            // builder for the tuple of all of the fields would.
+            unsafe impl #builder_impl_generics #path::system::SystemParamBuilder<#struct_name #ty_generics>
+                for #builder_name <#(#builder_params,)*> #builder_where_clause
+            {
+                fn build(
+                    self,
+                    world: &mut #path::world::World,
+                    meta: &mut #path::system::SystemMeta,
+                ) -> <#struct_name #ty_generics as #path::system::SystemParam>::State {
+                    let #builder_name { #(#builder_members: #field_locals,)* } = self;
+                    #state_struct_name {
+                        state: (#(#tuple_builds,)*),
+                    }
+                }
+            }
+        };
+        (builder_struct, builder_impl)
+    } else {
+        Default::default()
+    };
+
     TokenStream::from(quote! {
+        #builder_struct
+
         // We define the FetchState struct in an anonymous scope to avoid polluting the user namespace.
         // The struct can still be accessed via SystemParam::State, e.g. EventReaderState can be accessed via
         // <EventReader<'static, 'static, T> as SystemParam>::State
@@ -410,6 +597,14 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
                     <#fields_alias::<'_, '_, #punctuated_generic_idents> as #path::system::SystemParam>::apply(&mut state.state, system_meta, world);
                 }
 
+                unsafe fn validate_param(
+                    state: &Self::State,
+                    system_meta: &#path::system::SystemMeta,
+                    world: #path::world::unsafe_world_cell::UnsafeWorldCell,
+                ) -> bool {
+                    <#fields_alias::<'_, '_, #punctuated_generic_idents> as #path::system::SystemParam>::validate_param(&state.state, system_meta, world)
+                }
+
                 unsafe fn get_param<'w, 's>(
                     state: &'s mut Self::State,
                     system_meta: &#path::system::SystemMeta,
@@ -421,16 +616,158 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
                     >::get_param(&mut state.state, system_meta, world, change_tick);
                     #struct_name {
                         #(#fields: #field_locals,)*
+                        #(#ignored_fields: ::std::default::Default::default(),)*
                     }
                 }
             }
 
             // Safety: Each field is `ReadOnlySystemParam`, so this can only read from the `World`
             unsafe impl<'w, 's, #punctuated_generics> #path::system::ReadOnlySystemParam for #struct_name #ty_generics #read_only_where_clause {}
+
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #(#param_set_accessors)*
+            }
+
+            #builder_impl
         };
     })
 }
 
+/// The `#[system_param(...)]` attributes of a derived `SystemParam` struct.
+#[derive(Default)]
+struct SystemParamAttributes {
+    /// Whether to generate a `SystemParamBuilder` with a builder for each field.
+    builder: bool,
+}
+
+impl SystemParamAttributes {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(SYSTEM_PARAM_ATTRIBUTE_NAME))
+        {
+            attr.parse_nested_meta(|nested| {
+                if nested.path.is_ident(SYSTEM_PARAM_ATTRIBUTE_BUILDER_NAME) {
+                    attributes.builder = true;
+                    Ok(())
+                } else {
+                    Err(nested.error(format!(
+                        "Invalid system_param attribute. Use `{SYSTEM_PARAM_ATTRIBUTE_BUILDER_NAME}`"
+                    )))
+                }
+            })?;
+        }
+        Ok(attributes)
+    }
+}
+
+/// The `#[system_param(...)]` attributes of a field of a derived `SystemParam`.
+#[derive(Default)]
+struct SystemParamFieldAttributes {
+    /// The field isn't a `SystemParam`, and is initialized with `Default` instead.
+    ignore: bool,
+    /// The names of the parameters of a `ParamSet` field.
+    param_set: Vec<Ident>,
+}
+
+impl SystemParamFieldAttributes {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(SYSTEM_PARAM_ATTRIBUTE_NAME))
+        {
+            attr.parse_nested_meta(|nested| {
+                if nested.path.is_ident(SYSTEM_PARAM_ATTRIBUTE_IGNORE_NAME) {
+                    attributes.ignore = true;
+                    Ok(())
+                } else if nested.path.is_ident(PARAM_SET_ATTRIBUTE_NAME) {
+                    nested.parse_nested_meta(|name| {
+                        attributes.param_set.push(name.path.require_ident()?.clone());
+                        Ok(())
+                    })
+                } else {
+                    Err(nested.error(format!(
+                        "Invalid system_param attribute. Use `{SYSTEM_PARAM_ATTRIBUTE_IGNORE_NAME}` or `{PARAM_SET_ATTRIBUTE_NAME}(...)`"
+                    )))
+                }
+            })?;
+        }
+        if attributes.ignore && !attributes.param_set.is_empty() {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("An ignored field can't have `{PARAM_SET_ATTRIBUTE_NAME}` accessors"),
+            ));
+        }
+        Ok(attributes)
+    }
+}
+
+/// Generates the accessors requested by the `#[system_param(param_set(...))]` attribute of a
+/// `ParamSet` field of a derived `SystemParam`.
+fn system_param_field_accessors(
+    path: &syn::Path,
+    field: &syn::Field,
+    member: &proc_macro2::TokenStream,
+    names: &[Ident],
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // The parameters are the elements of the tuple passed to `ParamSet`.
+    let param_types = match &field.ty {
+        syn::Type::Path(ty) => ty.path.segments.last().and_then(|segment| {
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            args.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::Type(syn::Type::Tuple(tuple)) => Some(&tuple.elems),
+                _ => None,
+            })
+        }),
+        _ => None,
+    };
+    let Some(param_types) = param_types else {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            format!("`{PARAM_SET_ATTRIBUTE_NAME}` expects a field of type `ParamSet<(...)>`"),
+        ));
+    };
+    if param_types.len() != names.len() {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            format!(
+                "`{PARAM_SET_ATTRIBUTE_NAME}` has {} name(s), but the `ParamSet` has {} parameter(s)",
+                names.len(),
+                param_types.len(),
+            ),
+        ));
+    }
+
+    let vis = &field.vis;
+    Ok(names
+        .iter()
+        .zip(param_types)
+        .enumerate()
+        .map(|(i, (name, ty))| {
+            let fn_name = format_ident!("p{i}");
+            let comment = format!(
+                "Gets exclusive access to the `{name}` parameter of the `ParamSet` in `{member}`."
+            );
+            quote! {
+                #[doc = #comment]
+                /// No other parameters of the set may be accessed while this one is active.
+                #vis fn #name(&mut self) -> #path::system::SystemParamItem<'_, '_, #ty> {
+                    self.#member.#fn_name()
+                }
+            }
+        })
+        .collect())
+}
+
 /// Implement `WorldQuery` to use a struct as a parameter in a query
 #[proc_macro_derive(WorldQuery, attributes(world_query))]
 pub fn derive_world_query(input: TokenStream) -> TokenStream {
@@ -477,11 +477,32 @@ impl<'a> From<TicksMut<'a>> for Ticks<'a> {
 ///
 /// If you need a unique mutable borrow, use [`ResMut`] instead.
 ///
+/// Like [`Ref`] for components, this implements [`DetectChanges`], which tells whether the
+/// resource was added or changed since the last run of the system, and when it last changed.
+/// This only requires read access, so systems reacting to changes of a resource can still run
+/// in parallel with other systems reading it.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Resource)]
+/// # struct Config;
+/// #
+/// fn react_to_config(config: Res<Config>) {
+///     if config.is_changed() {
+///         // Apply the new configuration.
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_read_only_system(react_to_config);
+/// ```
+///
 /// # Panics
 ///
-/// Panics when used as a [`SystemParameter`](crate::system::SystemParam) if the resource does not exist.
+/// Systems using this parameter are skipped by the schedule while the resource does not exist.
+/// Getting the parameter anyway, such as by running the system with [`System::run`](crate::system::System::run),
+/// panics.
 ///
-/// Use `Option<Res<T>>` instead if the resource might not always exist.
+/// Use `Option<Res<T>>` instead if the system should run whether or not the resource exists.
 pub struct Res<'w, T: ?Sized + Resource> {
     pub(crate) value: &'w T,
     pub(crate) ticks: Ticks<'w>,
@@ -517,6 +538,17 @@ impl<'w, T: Resource> From<ResMut<'w, T>> for Res<'w, T> {
     }
 }
 
+impl<'w, T: Resource> From<Res<'w, T>> for Ref<'w, T> {
+    /// Convert a `Res` into a `Ref`. This allows keeping the change-detection feature of `Ref`
+    /// while losing the specificity of `Res`, e.g. to share code with component queries.
+    fn from(res: Res<'w, T>) -> Self {
+        Self {
+            value: res.value,
+            ticks: res.ticks,
+        }
+    }
+}
+
 impl<'w, 'a, T: Resource> IntoIterator for &'a Res<'w, T>
 where
     &'a T: IntoIterator,
@@ -539,9 +571,11 @@ impl_debug!(Res<'w, T>, Resource);
 ///
 /// # Panics
 ///
-/// Panics when used as a [`SystemParam`](crate::system::SystemParam) if the resource does not exist.
+/// Systems using this parameter are skipped by the schedule while the resource does not exist.
+/// Getting the parameter anyway, such as by running the system with [`System::run`](crate::system::System::run),
+/// panics.
 ///
-/// Use `Option<ResMut<T>>` instead if the resource might not always exist.
+/// Use `Option<ResMut<T>>` instead if the system should run whether or not the resource exists.
 pub struct ResMut<'a, T: ?Sized + Resource> {
     pub(crate) value: &'a mut T,
     pub(crate) ticks: TicksMut<'a>,
@@ -595,11 +629,16 @@ impl<'a, T: Resource> From<ResMut<'a, T>> for Mut<'a, T> {
 /// the scheduler to instead run the system on the main thread so that it doesn't send the resource
 /// over to another thread.
 ///
+/// This registers write access to the resource, so it conflicts with any other access to it.
+/// Prefer [`NonSend`](crate::system::NonSend) for systems that only read the resource.
+///
 /// # Panics
 ///
-/// Panics when used as a `SystemParameter` if the resource does not exist.
+/// Systems using this parameter are skipped by the schedule while the resource does not exist.
+/// Getting the parameter anyway, such as by running the system with [`System::run`](crate::system::System::run),
+/// panics.
 ///
-/// Use `Option<NonSendMut<T>>` instead if the resource might not always exist.
+/// Use `Option<NonSendMut<T>>` instead if the system should run whether or not the resource exists.
 pub struct NonSendMut<'a, T: ?Sized + 'static> {
     pub(crate) value: &'a mut T,
     pub(crate) ticks: TicksMut<'a>,
@@ -921,7 +960,8 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         change_detection::{
-            Mut, NonSendMut, Ref, ResMut, TicksMut, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE,
+            Mut, NonSendMut, Ref, Res, ResMut, Ticks, TicksMut, CHECK_TICK_THRESHOLD,
+            MAX_CHANGE_AGE,
         },
         component::{Component, ComponentTicks, Tick},
         system::{IntoSystem, Query, System},
@@ -1059,6 +1099,64 @@ mod tests {
         assert_eq!(4, into_mut.ticks.this_run.get());
     }
 
+    #[test]
+    fn ref_from_res() {
+        let component_ticks = ComponentTicks {
+            added: Tick::new(1),
+            changed: Tick::new(2),
+        };
+        let ticks = Ticks {
+            added: &component_ticks.added,
+            changed: &component_ticks.changed,
+            last_run: Tick::new(3),
+            this_run: Tick::new(4),
+        };
+        let res = Res {
+            value: &R {},
+            ticks,
+        };
+
+        let into_ref: Ref<R> = res.into();
+        assert_eq!(1, into_ref.ticks.added.get());
+        assert_eq!(2, into_ref.ticks.changed.get());
+        assert_eq!(3, into_ref.ticks.last_run.get());
+        assert_eq!(4, into_ref.ticks.this_run.get());
+    }
+
+    #[test]
+    fn res_change_detection() {
+        #[derive(Resource, Default)]
+        struct Changes(Vec<(bool, Tick)>);
+
+        fn record(res: Res<R2>, mut changes: ResMut<Changes>) {
+            changes.0.push((res.is_changed(), res.last_changed()));
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Changes>();
+        world.insert_resource(R2(0));
+
+        let mut system = IntoSystem::into_system(record);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        system.run((), &mut world);
+        world.resource_mut::<R2>().0 = 1;
+        system.run((), &mut world);
+
+        let changes = &world.resource::<Changes>().0;
+        assert_eq!(
+            changes
+                .iter()
+                .map(|(changed, _)| *changed)
+                .collect::<Vec<_>>(),
+            [true, false, true]
+        );
+        assert_eq!(changes[0].1, changes[1].1);
+        assert!(changes[2]
+            .1
+            .is_newer_than(changes[1].1, world.read_change_tick()));
+    }
+
     #[test]
     fn mut_new() {
         let mut component_ticks = ComponentTicks {
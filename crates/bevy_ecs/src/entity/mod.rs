@@ -773,6 +773,23 @@ impl Entities {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
+
+    /// The count of freed entity IDs that are available to be reused by the next allocations
+    /// or reservations.
+    #[inline]
+    pub fn free_count(&self) -> usize {
+        self.free_cursor.load(Ordering::Relaxed).max(0) as usize
+    }
+
+    /// The count of entities that have been reserved with
+    /// [`reserve_entity`](Entities::reserve_entity) or
+    /// [`reserve_entities`](Entities::reserve_entities) but not [flushed](Entities::flush) yet.
+    #[inline]
+    pub fn reserved_count(&self) -> usize {
+        // Reservations either take IDs from the end of the freelist, or push the cursor below
+        // zero for brand new IDs, so both are counted by how far it moved from the end.
+        (self.pending.len() as IdCursor - self.free_cursor.load(Ordering::Relaxed)) as usize
+    }
 }
 
 // This type is repr(C) to ensure that the layout and values within it can be safe to fully fill
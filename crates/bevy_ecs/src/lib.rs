@@ -50,7 +50,8 @@ pub mod prelude {
         },
         system::{
             Commands, Deferred, In, IntoSystem, Local, NonSend, NonSendMut, ParallelCommands,
-            ParamSet, Query, ReadOnlySystem, Res, ResMut, Resource, System, SystemParamFunction,
+            ParamSet, Populated, Query, ReadOnlySystem, Res, ResMut, Resource, Single, System,
+            SystemParamFunction,
         },
         world::{EntityMut, EntityRef, EntityWorldMut, FromWorld, World},
     };
@@ -419,6 +419,21 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.access.read_all();
     }
 
+    /// Returns `true` if a set of elements satisfies the `With` and `Without` filters of any of
+    /// the filter sets.
+    pub(crate) fn matches_filters(&self, set_contains_id: &impl Fn(T) -> bool) -> bool {
+        self.filter_sets.iter().any(|filter| {
+            filter
+                .with
+                .ones()
+                .all(|index| set_contains_id(T::get_sparse_set_index(index)))
+                && filter
+                    .without
+                    .ones()
+                    .all(|index| !set_contains_id(T::get_sparse_set_index(index)))
+        })
+    }
+
     /// Sets the underlying unfiltered access as having mutable access to all indexed elements.
     pub fn write_all(&mut self) {
         self.access.write_all();
@@ -482,6 +497,12 @@ impl<T: SparseSetIndex> FilteredAccessSet<T> {
         &self.combined_access
     }
 
+    /// Returns the filtered accesses in this set, in the order they were added.
+    #[inline]
+    pub fn filtered_accesses(&self) -> &[FilteredAccess<T>] {
+        &self.filtered_accesses
+    }
+
     /// Returns `true` if this and `other` can be active at the same time.
     ///
     /// Access conflict resolution happen in two steps:
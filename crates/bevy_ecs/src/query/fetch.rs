@@ -5,7 +5,10 @@ use crate::{
     entity::Entity,
     query::{Access, DebugCheckedUnwrap, FilteredAccess},
     storage::{ComponentSparseSet, Table, TableRow},
-    world::{unsafe_world_cell::UnsafeWorldCell, EntityMut, EntityRef, Mut, Ref, World},
+    world::{
+        unsafe_world_cell::UnsafeWorldCell, EntityMut, EntityRef, FilteredEntityRef, Mut, Ref,
+        World,
+    },
 };
 pub use bevy_ecs_macros::WorldQuery;
 use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
@@ -606,6 +609,93 @@ unsafe impl WorldQuery for EntityRef<'_> {
 /// SAFETY: Access is read-only.
 unsafe impl ReadOnlyWorldQuery for EntityRef<'_> {}
 
+/// SAFETY: `Self` is the same as `Self::ReadOnly`
+unsafe impl WorldQuery for FilteredEntityRef<'_> {
+    type Fetch<'w> = (UnsafeWorldCell<'w>, Access<ComponentId>);
+    type Item<'w> = FilteredEntityRef<'w>;
+    type ReadOnly = Self;
+    /// The components that can be read, along with the filters on which entities match.
+    /// [`init_state`](WorldQuery::init_state) creates an empty access matching every entity,
+    /// a [`QueryParamBuilder`](crate::system::QueryParamBuilder) creates it from component ids.
+    type State = FilteredAccess<ComponentId>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    const IS_DENSE: bool = true;
+
+    const IS_ARCHETYPAL: bool = true;
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        (world, state.access().clone())
+    }
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _state: &Self::State,
+        _archetype: &'w Archetype,
+        _table: &Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(_fetch: &mut Self::Fetch<'w>, _state: &Self::State, _table: &'w Table) {
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        (world, access): &mut Self::Fetch<'w>,
+        entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+        // SAFETY: `fetch` must be called with an entity that exists in the world
+        let cell = world.get_entity(entity).debug_checked_unwrap();
+        // SAFETY: Read-only access to every component in `access` has been registered.
+        FilteredEntityRef::new(cell, access.clone())
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        assert!(
+            access.access().is_compatible(state.access()),
+            "FilteredEntityRef conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+        );
+        access.extend(state);
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        for component_id in archetype.components() {
+            if state.access().has_read(component_id) {
+                access.add_read(archetype.get_archetype_component_id(component_id).unwrap());
+            }
+        }
+    }
+
+    fn init_state(_world: &mut World) -> Self::State {
+        FilteredAccess::default()
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        state.matches_filters(set_contains_id)
+    }
+}
+
+/// SAFETY: Access is read-only.
+unsafe impl ReadOnlyWorldQuery for FilteredEntityRef<'_> {}
+
 /// SAFETY: The accesses of `Self::ReadOnly` are a subset of the accesses of `Self`
 unsafe impl<'a> WorldQuery for EntityMut<'a> {
     type Fetch<'w> = UnsafeWorldCell<'w>;
@@ -101,6 +101,12 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     /// Creates a new [`QueryState`] from a given [`World`] and inherits the result of `world.id()`.
     pub fn new(world: &mut World) -> Self {
         let fetch_state = Q::init_state(world);
+        Self::new_with_fetch_state(world, fetch_state)
+    }
+
+    /// Creates a new [`QueryState`] like [`QueryState::new`], but with the given state of `Q`
+    /// instead of the one created by [`WorldQuery::init_state`].
+    pub(crate) fn new_with_fetch_state(world: &mut World, fetch_state: Q::State) -> Self {
         let filter_state = F::init_state(world);
 
         let mut component_access = FilteredAccess::default();
@@ -276,3 +276,127 @@ unsafe impl<'a> SystemParam for &'a RemovedComponentEvents {
         world.removed_components()
     }
 }
+
+/// A [`SystemParam`] that grants access to the entities that had any of a set of components,
+/// chosen at runtime, removed.
+///
+/// This is the dynamic counterpart of [`RemovedComponents`], e.g. for tools logging the
+/// removals of many component types. The components are given by their [`ComponentId`]s with a
+/// [`DynamicRemovedComponentsBuilder`](crate::system::DynamicRemovedComponentsBuilder).
+/// Without a builder, no removals are read.
+///
+/// Like [`RemovedComponents`], this doesn't access any component values, so it never conflicts
+/// with other parameters.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::{
+/// #     prelude::*,
+/// #     removal_detection::DynamicRemovedComponents,
+/// #     system::{DynamicRemovedComponentsBuilder, SystemParamBuilder},
+/// # };
+/// #
+/// # #[derive(Component)]
+/// # struct Health;
+/// # #[derive(Component)]
+/// # struct Shield;
+/// #
+/// let mut world = World::new();
+/// let health = world.init_component::<Health>();
+/// let shield = world.init_component::<Shield>();
+///
+/// let mut system = (DynamicRemovedComponentsBuilder::new().add(health).add(shield),)
+///     .build_state(&mut world)
+///     .build_system(|mut removed: DynamicRemovedComponents| {
+///         for (entity, component_id) in removed.read() {
+///             println!("{entity:?} lost {component_id:?}");
+///         }
+///     });
+/// # system.run((), &mut world);
+/// ```
+pub struct DynamicRemovedComponents<'w, 's> {
+    readers: &'s mut [(ComponentId, ManualEventReader<RemovedComponentEntity>)],
+    event_sets: &'w RemovedComponentEvents,
+}
+
+impl<'w, 's> DynamicRemovedComponents<'w, 's> {
+    /// Returns the ids of the components whose removals are read.
+    pub fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.readers.iter().map(|(component_id, _)| *component_id)
+    }
+
+    /// Iterates over the removals this parameter has not seen yet, along with the id of the
+    /// removed component. This updates the event counters, which means subsequent reads will
+    /// not include removals that happened before now.
+    ///
+    /// The removals are grouped by component, in the order the components were given to the
+    /// builder, and ordered as they happened within each component.
+    pub fn read(&mut self) -> impl Iterator<Item = (Entity, ComponentId)> + '_ {
+        let event_sets = self.event_sets;
+        self.readers
+            .iter_mut()
+            .filter_map(move |(component_id, reader)| {
+                let component_id = *component_id;
+                event_sets.get(component_id).map(move |events| {
+                    reader
+                        .read(events)
+                        .map(move |removed| (removed.0, component_id))
+                })
+            })
+            .flatten()
+    }
+
+    /// Determines the number of removals available to be read without consuming any.
+    pub fn len(&self) -> usize {
+        self.readers
+            .iter()
+            .filter_map(|(component_id, reader)| {
+                self.event_sets
+                    .get(*component_id)
+                    .map(|events| reader.len(events))
+            })
+            .sum()
+    }
+
+    /// Returns `true` if there are no removals available to read.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Consumes all available removals.
+    pub fn clear(&mut self) {
+        for (component_id, reader) in self.readers.iter_mut() {
+            if let Some(events) = self.event_sets.get(*component_id) {
+                reader.clear(events);
+            }
+        }
+    }
+}
+
+// SAFETY: Only reads World removed component events
+unsafe impl ReadOnlySystemParam for DynamicRemovedComponents<'_, '_> {}
+
+// SAFETY: no component value access. The builder only chooses the components whose removal
+// events are read.
+unsafe impl SystemParam for DynamicRemovedComponents<'_, '_> {
+    type State = Vec<(ComponentId, ManualEventReader<RemovedComponentEntity>)>;
+    type Item<'w, 's> = DynamicRemovedComponents<'w, 's>;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {
+        Vec::new()
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        DynamicRemovedComponents {
+            readers: state,
+            event_sets: world.removed_components(),
+        }
+    }
+}
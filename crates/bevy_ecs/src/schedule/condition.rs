@@ -63,7 +63,7 @@ pub trait Condition<Marker, In = ()>: sealed::Condition<Marker, In> {
     ///
     /// # Examples
     ///
-    /// ```should_panic
+    /// ```
     /// use bevy_ecs::prelude::*;
     ///
     /// #[derive(Resource, PartialEq)]
@@ -73,8 +73,8 @@ pub trait Condition<Marker, In = ()>: sealed::Condition<Marker, In> {
     /// # let mut world = World::new();
     /// # fn my_system() {}
     /// app.add_systems(
-    ///     // The `resource_equals` run condition will panic since we don't initialize `R`,
-    ///     // just like if we used `Res<R>` in a system.
+    ///     // The `resource_equals` run condition is not met since we don't initialize `R`,
+    ///     // just like a system using `Res<R>` would be skipped.
     ///     my_system.run_if(resource_equals(R(0))),
     /// );
     /// # app.run(&mut world);
@@ -267,9 +267,8 @@ pub mod common_conditions {
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
     /// if the resource is equal to `value`.
     ///
-    /// # Panics
-    ///
-    /// The condition will panic if the resource does not exist.
+    /// The condition is considered not met while the resource does not exist, since its
+    /// [`Res`] parameter can't be acquired.
     ///
     /// # Example
     ///
@@ -398,9 +397,8 @@ pub mod common_conditions {
     /// is checked after the resource was added, it will return `true`.
     /// Change detection behaves like this everywhere in Bevy.
     ///
-    /// # Panics
-    ///
-    /// The condition will panic if the resource does not exist.
+    /// The condition is considered not met while the resource does not exist, since its
+    /// [`Res`] parameter can't be acquired.
     ///
     /// # Example
     ///
@@ -685,9 +683,8 @@ pub mod common_conditions {
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
     /// if the state machine is currently in `state`.
     ///
-    /// # Panics
-    ///
-    /// The condition will panic if the resource does not exist.
+    /// The condition is considered not met while the resource does not exist, since its
+    /// [`Res`] parameter can't be acquired.
     ///
     /// # Example
     ///
@@ -803,9 +800,8 @@ pub mod common_conditions {
     /// To do things on transitions to/from specific states, use their respective OnEnter/OnExit
     /// schedules. Use this run condition if you want to detect any change, regardless of the value.
     ///
-    /// # Panics
-    ///
-    /// The condition will panic if the resource does not exist.
+    /// The condition is considered not met while the resource does not exist, since its
+    /// [`Res`] parameter can't be acquired.
     ///
     /// # Example
     ///
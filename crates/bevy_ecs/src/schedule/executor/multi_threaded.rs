@@ -331,8 +331,10 @@ impl MultiThreadedExecutor {
             self.ready_systems.set(system_index, false);
 
             // SAFETY: `can_run` returned true, which means that:
-            // - It must have called `update_archetype_component_access` for each run condition.
-            // - There can be no systems running whose accesses would conflict with any conditions.
+            // - It must have called `update_archetype_component_access` for each run condition,
+            //   and for the system unless it was skipped.
+            // - There can be no systems running whose accesses would conflict with any conditions
+            //   or with the system.
             if !self.should_run(system_index, system, conditions, world_cell) {
                 self.skip_system_and_signal_dependents(system_index);
                 continue;
@@ -430,12 +432,13 @@ impl MultiThreadedExecutor {
     /// * `world` must have permission to read any world data required by
     ///   the system's conditions: this includes conditions for the system
     ///   itself, and conditions for any of the system's sets.
+    /// * `world` must have permission to read any world data required by the system.
     /// * `update_archetype_component` must have been called with `world`
-    ///   for each run condition in `conditions`.
+    ///   for each run condition in `conditions`, and for the system unless it was skipped.
     unsafe fn should_run(
         &mut self,
         system_index: usize,
-        _system: &BoxedSystem,
+        system: &BoxedSystem,
         conditions: &mut Conditions,
         world: UnsafeWorldCell,
     ) -> bool {
@@ -476,6 +479,18 @@ impl MultiThreadedExecutor {
 
         should_run &= system_conditions_met;
 
+        if should_run {
+            // SAFETY:
+            // - The caller ensures that `world` has permission to read any data
+            //   required by the system.
+            // - `update_archetype_component_access` has been called for the system.
+            let valid_params = unsafe { system.validate_param_unsafe(world) };
+            if !valid_params {
+                self.skipped_systems.insert(system_index);
+            }
+            should_run &= valid_params;
+        }
+
         should_run
     }
 
@@ -717,7 +732,8 @@ unsafe fn evaluate_and_fold_conditions(
         .map(|condition| {
             // SAFETY: The caller ensures that `world` has permission to
             // access any data required by the condition.
-            unsafe { condition.run_unsafe((), world) }
+            // Conditions whose parameters can't be acquired are considered not met.
+            unsafe { condition.validate_param_unsafe(world) && condition.run_unsafe((), world) }
         })
         .fold(true, |acc, res| acc && res)
 }
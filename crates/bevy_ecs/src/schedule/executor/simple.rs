@@ -77,6 +77,11 @@ impl SystemExecutor for SimpleExecutor {
             }
 
             let system = &mut schedule.systems[system_index];
+            // Systems whose parameters can't be acquired are skipped for this run.
+            if !system.validate_param(world) {
+                continue;
+            }
+
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 system.run((), world);
             }));
@@ -109,6 +114,7 @@ fn evaluate_and_fold_conditions(conditions: &mut [BoxedCondition], world: &mut W
     #[allow(clippy::unnecessary_fold)]
     conditions
         .iter_mut()
-        .map(|condition| condition.run((), world))
+        // Conditions whose parameters can't be acquired are considered not met.
+        .map(|condition| condition.validate_param(world) && condition.run((), world))
         .fold(true, |acc, res| acc && res)
 }
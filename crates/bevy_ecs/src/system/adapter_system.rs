@@ -117,6 +117,12 @@ where
             .adapt(input, |input| self.system.run(input, world))
     }
 
+    #[inline]
+    unsafe fn validate_param_unsafe(&self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: `system.validate_param_unsafe` has the same invariants as `self.validate_param_unsafe`.
+        self.system.validate_param_unsafe(world)
+    }
+
     #[inline]
     fn apply_deferred(&mut self, world: &mut crate::prelude::World) {
         self.system.apply_deferred(world);
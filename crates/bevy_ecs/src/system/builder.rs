@@ -0,0 +1,946 @@
+use bevy_utils::{all_tuples, synccell::SyncCell};
+
+use crate::{
+    component::ComponentId,
+    entity::Entity,
+    event::ManualEventReader,
+    prelude::FromWorld,
+    query::{Access, FilteredAccess, QueryState, ReadOnlyWorldQuery},
+    removal_detection::{DynamicRemovedComponents, RemovedComponentEntity},
+    system::{
+        init_filtered_resources, init_query_param, Deferred, DynSystemParam, DynSystemParamState,
+        EntityRefParam, FilteredResources, FilteredResourcesMut, Local, Query, SystemBuffer,
+        SystemMeta, SystemParam, SystemState,
+    },
+    world::{FilteredEntityRef, World},
+};
+
+/// A builder that can create a [`SystemParam`]'s state, as an alternative to
+/// [`SystemParam::init_state`].
+///
+/// Builders allow configuring the state of the parameters of a single system instance, such as
+/// the initial value of a [`Local`] with [`LocalBuilder`]. Parameters that don't need any
+/// configuration use [`ParamBuilder`], and a tuple of builders builds a tuple of parameters.
+///
+/// The built [`SystemState`] can be turned into a system with [`SystemState::build_system`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{
+/// #     prelude::*,
+/// #     system::{LocalBuilder, ParamBuilder, SystemParamBuilder},
+/// # };
+/// #
+/// # #[derive(Resource, Default)]
+/// # struct Total(u64);
+/// #
+/// fn count_up(mut counter: Local<u64>, mut total: ResMut<Total>) {
+///     *counter += 1;
+///     total.0 += *counter;
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Total>();
+///
+/// // Start counting at 10 instead of the default value of 0.
+/// let mut system = (LocalBuilder(10), ParamBuilder)
+///     .build_state(&mut world)
+///     .build_system(count_up);
+///
+/// system.run((), &mut world);
+/// assert_eq!(world.resource::<Total>().0, 11);
+/// ```
+///
+/// # Safety
+///
+/// The implementor must ensure that:
+/// - [`build`](SystemParamBuilder::build) registers all [`World`] accesses used by
+///   [`SystemParam::get_param`] with the provided [`SystemMeta`], like
+///   [`SystemParam::init_state`] does.
+/// - None of the registered world accesses conflict with any prior accesses registered in the
+///   [`SystemMeta`].
+pub unsafe trait SystemParamBuilder<P: SystemParam>: Sized {
+    /// Registers any [`World`] access used by this [`SystemParam`]
+    /// and creates a new instance of this param's [`State`](SystemParam::State).
+    fn build(self, world: &mut World, meta: &mut SystemMeta) -> P::State;
+
+    /// Creates a [`SystemState`] from this builder.
+    fn build_state(self, world: &mut World) -> SystemState<P> {
+        SystemState::from_builder(world, self)
+    }
+}
+
+/// A [`SystemParamBuilder`] for any [`SystemParam`] that uses its default
+/// [`init_state`](SystemParam::init_state).
+#[derive(Default, Debug, Copy, Clone)]
+pub struct ParamBuilder;
+
+// SAFETY: Calls `SystemParam::init_state`.
+unsafe impl<P: SystemParam> SystemParamBuilder<P> for ParamBuilder {
+    fn build(self, world: &mut World, meta: &mut SystemMeta) -> P::State {
+        P::init_state(world, meta)
+    }
+}
+
+/// A [`SystemParamBuilder`] for a [`Local`] that starts out with the given value instead of
+/// [`FromWorld::from_world`].
+#[derive(Default, Debug, Copy, Clone)]
+pub struct LocalBuilder<T>(pub T);
+
+// SAFETY: `Local` doesn't access the world.
+unsafe impl<'s, T: FromWorld + Send + 'static> SystemParamBuilder<Local<'s, T>>
+    for LocalBuilder<T>
+{
+    fn build(self, _world: &mut World, _meta: &mut SystemMeta) -> SyncCell<T> {
+        SyncCell::new(self.0)
+    }
+}
+
+/// A [`SystemParamBuilder`] for a [`Local`] that starts out with the value returned by the given
+/// closure instead of [`FromWorld::from_world`].
+///
+/// The closure is called once when the system state is built, so it can compute the value from
+/// expensive world data without implementing [`FromWorld`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{
+/// #     component::ComponentId,
+/// #     prelude::*,
+/// #     system::{LazyLocalBuilder, SystemParamBuilder},
+/// # };
+/// #
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// #
+/// # #[derive(Component)]
+/// # struct Armor(u32);
+/// #
+/// let mut world = World::new();
+///
+/// // Look up the ids of the components once, instead of on every run.
+/// let mut system = (LazyLocalBuilder(|world: &mut World| {
+///     vec![world.init_component::<Health>(), world.init_component::<Armor>()]
+/// }),)
+/// .build_state(&mut world)
+/// .build_system(|ids: Local<Vec<ComponentId>>| ids.clone());
+///
+/// let ids = system.run((), &mut world);
+/// assert_eq!(world.components().component_id::<Armor>(), Some(ids[1]));
+/// ```
+#[derive(Default, Debug, Copy, Clone)]
+pub struct LazyLocalBuilder<F>(pub F);
+
+// SAFETY: `Local` doesn't access the world.
+unsafe impl<'s, T, F> SystemParamBuilder<Local<'s, T>> for LazyLocalBuilder<F>
+where
+    T: FromWorld + Send + 'static,
+    F: FnOnce(&mut World) -> T,
+{
+    fn build(self, world: &mut World, _meta: &mut SystemMeta) -> SyncCell<T> {
+        SyncCell::new((self.0)(world))
+    }
+}
+
+/// A [`SystemParamBuilder`] for a [`Deferred`] whose buffer starts out with some capacity, to
+/// avoid reallocating it while a hot system fills it with many mutations.
+///
+/// The capacity is passed to [`SystemBuffer::reserve`], which is only a hint that buffers may
+/// ignore. Without a capacity, the buffer is created by [`FromWorld::from_world`] alone, like
+/// with [`ParamBuilder`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{
+/// #     prelude::*,
+/// #     system::{CommandQueue, Deferred, DeferredBuilder, SystemParamBuilder},
+/// # };
+/// #
+/// # #[derive(Component)]
+/// # struct Particle;
+/// #
+/// let mut world = World::new();
+///
+/// // Reserve room for the spawn commands of the particles.
+/// let mut system = (DeferredBuilder::with_capacity(1024),)
+///     .build_state(&mut world)
+///     .build_system(|mut queue: Deferred<CommandQueue>| {
+///         for _ in 0..16 {
+///             queue.push(|world: &mut World| {
+///                 world.spawn(Particle);
+///             });
+///         }
+///     });
+///
+/// system.run((), &mut world);
+/// system.apply_deferred(&mut world);
+/// assert_eq!(world.query::<&Particle>().iter(&world).count(), 16);
+/// ```
+#[derive(Default, Debug, Copy, Clone)]
+pub struct DeferredBuilder {
+    capacity: Option<usize>,
+}
+
+impl DeferredBuilder {
+    /// Creates a builder that reserves `capacity` in the buffer with [`SystemBuffer::reserve`].
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+        }
+    }
+}
+
+// SAFETY: `Deferred` doesn't access the world.
+unsafe impl<'s, T: SystemBuffer> SystemParamBuilder<Deferred<'s, T>> for DeferredBuilder {
+    fn build(self, world: &mut World, _meta: &mut SystemMeta) -> SyncCell<T> {
+        let mut buffer = T::from_world(world);
+        if let Some(capacity) = self.capacity {
+            buffer.reserve(capacity);
+        }
+        SyncCell::new(buffer)
+    }
+}
+
+/// A [`SystemParamBuilder`] for a [`Query`] of [`FilteredEntityRef`]s, which reads components
+/// and matches entities given by [`ComponentId`]s chosen at runtime.
+///
+/// The accesses are registered like those of any other [`Query`], so conflicts with the other
+/// parameters of the system are detected the same way.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{
+/// #     prelude::*,
+/// #     system::{QueryParamBuilder, SystemParamBuilder},
+/// #     world::FilteredEntityRef,
+/// # };
+/// #
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// #
+/// let mut world = World::new();
+/// world.spawn(Health(10));
+///
+/// // The component to read is only known at runtime.
+/// let health = world.init_component::<Health>();
+///
+/// let mut system = (QueryParamBuilder::new().ref_id(health),)
+///     .build_state(&mut world)
+///     .build_system(move |query: Query<FilteredEntityRef>| {
+///         for entity in &query {
+///             // The pointer could be passed to a reflection-based editor instead.
+///             assert!(entity.get_by_id(health).is_some());
+///         }
+///     });
+///
+/// system.run((), &mut world);
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct QueryParamBuilder {
+    access: FilteredAccess<ComponentId>,
+}
+
+impl QueryParamBuilder {
+    /// Creates a builder for a query that matches every entity and can't read any component.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the component and only matches entities that have it, like `&T`.
+    pub fn ref_id(mut self, component_id: ComponentId) -> Self {
+        self.access.add_read(component_id);
+        self
+    }
+
+    /// Reads the component if the entity has it, like `Option<&T>`.
+    pub fn optional_ref_id(mut self, component_id: ComponentId) -> Self {
+        self.access.access_mut().add_read(component_id);
+        self
+    }
+
+    /// Only matches entities that have the component, like [`With<T>`](crate::query::With).
+    pub fn with_id(mut self, component_id: ComponentId) -> Self {
+        self.access.and_with(component_id);
+        self
+    }
+
+    /// Only matches entities that don't have the component, like
+    /// [`Without<T>`](crate::query::Without).
+    pub fn without_id(mut self, component_id: ComponentId) -> Self {
+        self.access.and_without(component_id);
+        self
+    }
+}
+
+// SAFETY: Registers the accesses of the query state like the `SystemParam` impl of `Query`.
+unsafe impl<'w, 's, F: ReadOnlyWorldQuery + 'static>
+    SystemParamBuilder<Query<'w, 's, FilteredEntityRef<'static>, F>> for QueryParamBuilder
+{
+    fn build(
+        self,
+        world: &mut World,
+        meta: &mut SystemMeta,
+    ) -> QueryState<FilteredEntityRef<'static>, F> {
+        let state = QueryState::new_with_fetch_state(world, self.access);
+        init_query_param(world, meta, &state);
+        state
+    }
+}
+
+/// A [`SystemParamBuilder`] for an [`EntityRefParam`], which reads components given by
+/// [`ComponentId`]s of a specific entity.
+///
+/// The accesses are registered like those of a [`Query`] built with a [`QueryParamBuilder`], so
+/// conflicts with the other parameters of the system are detected the same way.
+///
+/// See the [`EntityRefParam`] docs for an example.
+#[derive(Debug, Clone)]
+pub struct EntityRefParamBuilder {
+    entity: Entity,
+    query: QueryParamBuilder,
+}
+
+impl EntityRefParamBuilder {
+    /// Creates a builder for the given entity that can't read any component.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            query: QueryParamBuilder::new(),
+        }
+    }
+
+    /// Reads the component, and skips the system while the entity doesn't have it, like `&T`.
+    pub fn ref_id(mut self, component_id: ComponentId) -> Self {
+        self.query = self.query.ref_id(component_id);
+        self
+    }
+
+    /// Reads the component if the entity has it, like `Option<&T>`.
+    pub fn optional_ref_id(mut self, component_id: ComponentId) -> Self {
+        self.query = self.query.optional_ref_id(component_id);
+        self
+    }
+}
+
+// SAFETY: Registers the accesses of the query state like the `SystemParam` impl of `Query`.
+unsafe impl<'w> SystemParamBuilder<EntityRefParam<'w>> for EntityRefParamBuilder {
+    fn build(
+        self,
+        world: &mut World,
+        meta: &mut SystemMeta,
+    ) -> (Entity, QueryState<FilteredEntityRef<'static>>) {
+        let state =
+            SystemParamBuilder::<Query<FilteredEntityRef<'static>>>::build(self.query, world, meta);
+        (self.entity, state)
+    }
+}
+
+/// A [`SystemParamBuilder`] for [`DynamicRemovedComponents`], which reads the removals of the
+/// components given by [`ComponentId`]s chosen at runtime.
+///
+/// See the [`DynamicRemovedComponents`] docs for an example.
+#[derive(Default, Debug, Clone)]
+pub struct DynamicRemovedComponentsBuilder {
+    component_ids: Vec<ComponentId>,
+}
+
+impl DynamicRemovedComponentsBuilder {
+    /// Creates a builder that doesn't read the removals of any component.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the removals of the component, like
+    /// [`RemovedComponents`](crate::removal_detection::RemovedComponents).
+    pub fn add(mut self, component_id: ComponentId) -> Self {
+        if !self.component_ids.contains(&component_id) {
+            self.component_ids.push(component_id);
+        }
+        self
+    }
+}
+
+// SAFETY: Reading removal events doesn't access the world, like the `SystemParam` impl of
+// `RemovedComponents`.
+unsafe impl<'w, 's> SystemParamBuilder<DynamicRemovedComponents<'w, 's>>
+    for DynamicRemovedComponentsBuilder
+{
+    fn build(
+        self,
+        _world: &mut World,
+        _meta: &mut SystemMeta,
+    ) -> Vec<(ComponentId, ManualEventReader<RemovedComponentEntity>)> {
+        self.component_ids
+            .into_iter()
+            .map(|component_id| (component_id, ManualEventReader::default()))
+            .collect()
+    }
+}
+
+/// A [`SystemParamBuilder`] for [`FilteredResources`], which reads the resources given by
+/// [`ComponentId`]s chosen at runtime.
+///
+/// The accesses are registered like those of [`Res`](crate::system::Res), so conflicts with the
+/// other parameters of the system are detected the same way.
+#[derive(Default, Debug, Clone)]
+pub struct FilteredResourcesBuilder {
+    access: Access<ComponentId>,
+}
+
+impl FilteredResourcesBuilder {
+    /// Creates a builder that can't read any resource.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the resource, like [`Res`](crate::system::Res).
+    pub fn add_read(mut self, component_id: ComponentId) -> Self {
+        self.access.add_read(component_id);
+        self
+    }
+}
+
+// SAFETY: Registers read access to the resources like the `SystemParam` impl of `Res`.
+unsafe impl<'w, 's> SystemParamBuilder<FilteredResources<'w, 's>> for FilteredResourcesBuilder {
+    fn build(self, world: &mut World, meta: &mut SystemMeta) -> Access<ComponentId> {
+        init_filtered_resources(world, meta, &self.access);
+        self.access
+    }
+}
+
+/// A [`SystemParamBuilder`] for [`FilteredResourcesMut`], which reads and writes the resources
+/// given by [`ComponentId`]s chosen at runtime.
+///
+/// The accesses are registered like those of [`Res`](crate::system::Res) and
+/// [`ResMut`](crate::system::ResMut), so conflicts with the other parameters of the system are
+/// detected the same way.
+#[derive(Default, Debug, Clone)]
+pub struct FilteredResourcesMutBuilder {
+    access: Access<ComponentId>,
+}
+
+impl FilteredResourcesMutBuilder {
+    /// Creates a builder that can't access any resource.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the resource, like [`Res`](crate::system::Res).
+    pub fn add_read(mut self, component_id: ComponentId) -> Self {
+        self.access.add_read(component_id);
+        self
+    }
+
+    /// Reads and writes the resource, like [`ResMut`](crate::system::ResMut).
+    pub fn add_write(mut self, component_id: ComponentId) -> Self {
+        self.access.add_write(component_id);
+        self
+    }
+}
+
+// SAFETY: Registers read and write access to the resources like the `SystemParam` impls of `Res`
+// and `ResMut`.
+unsafe impl<'w, 's> SystemParamBuilder<FilteredResourcesMut<'w, 's>>
+    for FilteredResourcesMutBuilder
+{
+    fn build(self, world: &mut World, meta: &mut SystemMeta) -> Access<ComponentId> {
+        init_filtered_resources(world, meta, &self.access);
+        self.access
+    }
+}
+
+/// A [`SystemParamBuilder`] for a [`DynSystemParam`], which wraps the builder of the
+/// underlying parameter.
+///
+/// See the [`DynSystemParam`] docs for an example.
+pub struct DynParamBuilder<'a>(
+    Box<dyn FnOnce(&mut World, &mut SystemMeta) -> DynSystemParamState + 'a>,
+);
+
+impl<'a> DynParamBuilder<'a> {
+    /// Creates a builder for a [`DynSystemParam`] that can be downcast to `T`, using the given
+    /// builder of `T`.
+    pub fn new<T: SystemParam + 'static>(builder: impl SystemParamBuilder<T> + 'a) -> Self {
+        Self(Box::new(|world, meta| {
+            DynSystemParamState::new::<T>(builder.build(world, meta))
+        }))
+    }
+}
+
+// SAFETY: The wrapped builder registers the world accesses of the underlying parameter, which are
+// the only accesses `DynSystemParam` performs.
+unsafe impl<'a, 'w, 's> SystemParamBuilder<DynSystemParam<'w, 's>> for DynParamBuilder<'a> {
+    fn build(self, world: &mut World, meta: &mut SystemMeta) -> DynSystemParamState {
+        (self.0)(world, meta)
+    }
+}
+
+macro_rules! impl_system_param_builder_tuple {
+    ($(($param: ident, $builder: ident)),*) => {
+        // SAFETY: Each builder registers the world accesses of its own parameter.
+        unsafe impl<$($param: SystemParam,)* $($builder: SystemParamBuilder<$param>,)*>
+            SystemParamBuilder<($($param,)*)> for ($($builder,)*)
+        {
+            #[allow(non_snake_case)]
+            #[allow(unused_variables)]
+            fn build(
+                self,
+                world: &mut World,
+                meta: &mut SystemMeta,
+            ) -> <($($param,)*) as SystemParam>::State {
+                let ($($builder,)*) = self;
+                ($($builder.build(world, meta),)*)
+            }
+        }
+    };
+}
+
+all_tuples!(impl_system_param_builder_tuple, 0, 16, P, B);
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{
+        prelude::{Component, Query},
+        schedule::Schedule,
+        system::{IntoSystem, Local, ResMut, Resource, System},
+    };
+
+    use super::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Resource, Default)]
+    struct Total(usize);
+
+    fn local_system(local: Local<u64>) -> u64 {
+        *local
+    }
+
+    fn query_system(query: Query<()>) -> usize {
+        query.iter().count()
+    }
+
+    fn multi_param_system(a: Local<u64>, b: Local<u64>) -> u64 {
+        *a + *b + 1
+    }
+
+    #[test]
+    fn local_builder() {
+        let mut world = World::new();
+
+        let mut system = (LocalBuilder(10),)
+            .build_state(&mut world)
+            .build_system(local_system);
+
+        let result = system.run((), &mut world);
+        assert_eq!(result, 10);
+    }
+
+    #[test]
+    fn lazy_local_builder() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn(A);
+
+        let mut system = (LazyLocalBuilder(|world: &mut World| {
+            world.query::<&A>().iter(world).count() as u64
+        }),)
+            .build_state(&mut world)
+            .build_system(local_system);
+
+        // The closure only runs once, when the state is built.
+        world.spawn(A);
+        let result = system.run((), &mut world);
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn deferred_builder() {
+        #[derive(Default)]
+        struct Reserved(Vec<usize>);
+
+        impl SystemBuffer for Reserved {
+            fn apply(&mut self, _system_meta: &SystemMeta, _world: &mut World) {}
+
+            fn reserve(&mut self, additional: usize) {
+                self.0.push(additional);
+            }
+        }
+
+        fn reserved(buffer: Deferred<Reserved>) -> Vec<usize> {
+            buffer.0.clone()
+        }
+
+        let mut world = World::new();
+
+        let mut system = (DeferredBuilder::with_capacity(64),)
+            .build_state(&mut world)
+            .build_system(reserved);
+        assert_eq!(system.run((), &mut world), [64]);
+
+        // Without a capacity, the buffer isn't asked to reserve anything.
+        let mut system = (DeferredBuilder::default(),)
+            .build_state(&mut world)
+            .build_system(reserved);
+        assert!(system.run((), &mut world).is_empty());
+    }
+
+    #[test]
+    fn param_builder() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn_empty();
+
+        let mut system = (ParamBuilder,)
+            .build_state(&mut world)
+            .build_system(query_system);
+
+        let result = system.run((), &mut world);
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn multi_param_builder() {
+        let mut world = World::new();
+
+        let mut system = (LocalBuilder(5), ParamBuilder)
+            .build_state(&mut world)
+            .build_system(multi_param_system);
+
+        let result = system.run((), &mut world);
+        assert_eq!(result, 6);
+    }
+
+    #[test]
+    fn query_param_builder() {
+        #[derive(Component)]
+        struct B(usize);
+
+        #[derive(Component)]
+        struct C;
+
+        let mut world = World::new();
+        world.spawn((A, B(1)));
+        world.spawn((A, B(2), C));
+        world.spawn(B(4));
+        world.spawn(A);
+
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+        let c = world.init_component::<C>();
+
+        let mut system = (QueryParamBuilder::new().ref_id(b).with_id(a).without_id(c),)
+            .build_state(&mut world)
+            .build_system(move |query: Query<FilteredEntityRef>| {
+                query
+                    .iter()
+                    .map(|entity| {
+                        // Only the components in the access can be read.
+                        assert!(entity.contains_id(a));
+                        assert!(entity.get_by_id(a).is_none());
+                        entity.get::<B>().unwrap().0
+                    })
+                    .sum::<usize>()
+            });
+
+        assert_eq!(system.run((), &mut world), 1);
+    }
+
+    #[test]
+    fn optional_query_param_builder() {
+        #[derive(Component)]
+        struct B(usize);
+
+        let mut world = World::new();
+        world.spawn(B(1));
+        world.spawn(A);
+
+        let b = world.init_component::<B>();
+
+        let mut system = (QueryParamBuilder::new().optional_ref_id(b),)
+            .build_state(&mut world)
+            .build_system(move |query: Query<FilteredEntityRef>| {
+                query
+                    .iter()
+                    .map(|entity| entity.get::<B>().map_or(10, |b| b.0))
+                    .sum::<usize>()
+            });
+
+        assert_eq!(system.run((), &mut world), 11);
+    }
+
+    #[test]
+    #[should_panic = "error[B0001]"]
+    fn conflicting_query_param_builder() {
+        #[derive(Component)]
+        struct B(usize);
+
+        let mut world = World::new();
+        let b = world.init_component::<B>();
+
+        (QueryParamBuilder::new().ref_id(b), ParamBuilder)
+            .build_state(&mut world)
+            .build_system(|_: Query<FilteredEntityRef>, _: Query<&mut B>| {});
+    }
+
+    #[test]
+    fn entity_ref_param_builder() {
+        #[derive(Component)]
+        struct B(usize);
+
+        let mut world = World::new();
+        world.init_resource::<Total>();
+        let focused = world.spawn((A, B(2))).id();
+        world.spawn(B(4));
+
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        let mut system = (EntityRefParamBuilder::new(focused).ref_id(b), ParamBuilder)
+            .build_state(&mut world)
+            .build_system(move |entity: EntityRefParam, mut total: ResMut<Total>| {
+                // Only the components in the access can be read.
+                assert!(entity.contains_id(a));
+                assert!(entity.get_by_id(a).is_none());
+                total.0 += entity.get::<B>().unwrap().0;
+            });
+        system.initialize(&mut world);
+        assert!(system.validate_param(&world));
+        system.run((), &mut world);
+        assert_eq!(world.resource::<Total>().0, 2);
+
+        // The system is skipped while the entity is missing a required component, or is gone.
+        world.entity_mut(focused).remove::<B>();
+        assert!(!system.validate_param(&world));
+        world.despawn(focused);
+        assert!(!system.validate_param(&world));
+    }
+
+    #[test]
+    fn entity_ref_param_without_builder_is_skipped() {
+        let mut world = World::new();
+        world.spawn(A);
+
+        let mut system = IntoSystem::into_system(|_: EntityRefParam| {});
+        system.initialize(&mut world);
+        assert!(!system.validate_param(&world));
+    }
+
+    #[test]
+    #[should_panic = "error[B0001]"]
+    fn conflicting_entity_ref_param_builder() {
+        #[derive(Component)]
+        struct B(usize);
+
+        let mut world = World::new();
+        let entity = world.spawn(B(1)).id();
+        let b = world.init_component::<B>();
+
+        (EntityRefParamBuilder::new(entity).ref_id(b), ParamBuilder)
+            .build_state(&mut world)
+            .build_system(|_: EntityRefParam, _: Query<&mut B>| {});
+    }
+
+    #[test]
+    fn dynamic_removed_components_builder() {
+        #[derive(Component)]
+        struct B;
+
+        #[derive(Component)]
+        struct C;
+
+        #[derive(Resource, Default)]
+        struct Removed(Vec<(Entity, ComponentId)>);
+
+        let mut world = World::new();
+        world.init_resource::<Removed>();
+        let b = world.init_component::<B>();
+        let c = world.init_component::<C>();
+        let first = world.spawn((A, B, C)).id();
+        let second = world.spawn(C).id();
+
+        let mut system = (
+            DynamicRemovedComponentsBuilder::new().add(b).add(c),
+            ParamBuilder,
+        )
+            .build_state(&mut world)
+            .build_system(
+                |mut removed: DynamicRemovedComponents, mut log: ResMut<Removed>| {
+                    assert_eq!(removed.len(), 3);
+                    log.0.extend(removed.read());
+                    assert!(removed.is_empty());
+                },
+            );
+        system.initialize(&mut world);
+
+        // Only the removals of the given components are read, grouped by component.
+        world.entity_mut(first).remove::<(A, B)>();
+        world.despawn(second);
+        world.despawn(first);
+        system.run((), &mut world);
+        assert_eq!(
+            world.resource::<Removed>().0,
+            [(first, b), (second, c), (first, c)]
+        );
+    }
+
+    #[test]
+    fn filtered_resources_builder() {
+        #[derive(Resource)]
+        struct B(usize);
+
+        let mut world = World::new();
+        world.insert_resource(Total(1));
+        world.insert_resource(B(2));
+
+        let total = world.components().resource_id::<Total>().unwrap();
+
+        let mut system = (FilteredResourcesBuilder::new().add_read(total),)
+            .build_state(&mut world)
+            .build_system(move |resources: FilteredResources| {
+                // Only the resources in the access can be read.
+                assert!(resources.get::<B>().is_none());
+                assert!(resources.get_by_id(total).is_some());
+                resources.get::<Total>().unwrap().0
+            });
+
+        assert_eq!(system.run((), &mut world), 1);
+    }
+
+    #[test]
+    fn filtered_resources_mut_builder() {
+        #[derive(Resource)]
+        struct B(usize);
+
+        let mut world = World::new();
+        world.insert_resource(Total(1));
+        world.insert_resource(B(2));
+
+        let total = world.components().resource_id::<Total>().unwrap();
+        let b = world.components().resource_id::<B>().unwrap();
+
+        let mut system = (FilteredResourcesMutBuilder::new()
+            .add_write(total)
+            .add_read(b),)
+            .build_state(&mut world)
+            .build_system(|mut resources: FilteredResourcesMut| {
+                // Resources that are only read can't be written.
+                assert!(resources.get_mut::<B>().is_none());
+                let b = resources.get::<B>().unwrap().0;
+                resources.get_mut::<Total>().unwrap().0 += b;
+            });
+
+        system.run((), &mut world);
+        assert_eq!(world.resource::<Total>().0, 3);
+    }
+
+    #[test]
+    #[should_panic = "error[B0002]"]
+    fn conflicting_filtered_resources_builder() {
+        let mut world = World::new();
+        world.init_resource::<Total>();
+        let total = world.components().resource_id::<Total>().unwrap();
+
+        (
+            FilteredResourcesBuilder::new().add_read(total),
+            ParamBuilder,
+        )
+            .build_state(&mut world)
+            .build_system(|_: FilteredResources, _: ResMut<Total>| {});
+    }
+
+    #[test]
+    fn dyn_param_builder() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn_empty();
+
+        let mut system = (
+            DynParamBuilder::new(LocalBuilder(3_usize)),
+            DynParamBuilder::new::<Query<()>>(ParamBuilder),
+            DynParamBuilder::new::<()>(ParamBuilder),
+        )
+            .build_state(&mut world)
+            .build_system(
+                |mut p0: DynSystemParam, mut p1: DynSystemParam, mut p2: DynSystemParam| {
+                    let local = *p0.downcast_mut::<Local<usize>>().unwrap();
+                    let query_count = p1.downcast_mut::<Query<()>>().unwrap().iter().count();
+                    p2.downcast_mut::<()>().unwrap();
+                    assert!(p0.is::<Local<usize>>());
+                    assert!(p0.downcast_mut::<Query<()>>().is_none());
+                    local + query_count
+                },
+            );
+
+        assert_eq!(system.run((), &mut world), 5);
+    }
+
+    #[test]
+    #[should_panic = "error[B0002]"]
+    fn conflicting_dyn_param_builder() {
+        let mut world = World::new();
+
+        (
+            DynParamBuilder::new::<ResMut<Total>>(ParamBuilder),
+            ParamBuilder,
+        )
+            .build_state(&mut world)
+            .build_system(|_: DynSystemParam, _: ResMut<Total>| {});
+    }
+
+    #[test]
+    fn derived_param_builder() {
+        #[derive(SystemParam)]
+        #[system_param(builder)]
+        struct Counter<'w, 's> {
+            count: Local<'s, usize>,
+            total: ResMut<'w, Total>,
+            #[system_param(ignore)]
+            step: usize,
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Total>();
+
+        let mut system = (CounterBuilder {
+            count: LocalBuilder(5),
+            total: ParamBuilder,
+        },)
+            .build_state(&mut world)
+            .build_system(|mut counter: Counter| {
+                *counter.count += counter.step + 1;
+                counter.total.0 += *counter.count;
+            });
+
+        system.run((), &mut world);
+        system.run((), &mut world);
+        assert_eq!(world.resource::<Total>().0, 6 + 7);
+    }
+
+    #[test]
+    fn built_system_in_schedule() {
+        fn count(mut local: Local<usize>, mut total: ResMut<Total>) {
+            *local += 1;
+            total.0 += *local;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Total>();
+
+        let system = (LocalBuilder(100), ParamBuilder)
+            .build_state(&mut world)
+            .build_system(count);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Total>().0, 101 + 102);
+    }
+}
@@ -175,6 +175,14 @@ where
         )
     }
 
+    #[inline]
+    unsafe fn validate_param_unsafe(&self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: The world accesses for both underlying systems have been registered, and
+        // `update_archetype_component_access` forwards to the implementations for `self.a`
+        // and `self.b`.
+        self.a.validate_param_unsafe(world) && self.b.validate_param_unsafe(world)
+    }
+
     fn run<'w>(&mut self, input: Self::In, world: &'w mut World) -> Self::Out {
         // SAFETY: Converting `&mut T` -> `&UnsafeCell<T>`
         // is explicitly allowed in the docs for `UnsafeCell`.
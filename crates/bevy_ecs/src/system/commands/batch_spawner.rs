@@ -0,0 +1,100 @@
+use crate::{
+    self as bevy_ecs,
+    bundle::Bundle,
+    prelude::World,
+    system::{Deferred, SystemBuffer, SystemMeta, SystemParam},
+};
+
+/// The buffer of a [`BatchSpawner`], holding the bundles waiting to be spawned.
+struct BatchSpawnBuffer<B: Bundle> {
+    bundles: Vec<B>,
+}
+
+impl<B: Bundle> Default for BatchSpawnBuffer<B> {
+    fn default() -> Self {
+        Self {
+            bundles: Vec::new(),
+        }
+    }
+}
+
+impl<B: Bundle> SystemBuffer for BatchSpawnBuffer<B> {
+    #[inline]
+    fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+        if self.bundles.is_empty() {
+            return;
+        }
+        // Draining keeps the allocation around for the next run of the system.
+        world.spawn_batch(self.bundles.drain(..));
+    }
+}
+
+/// A [`SystemParam`] spawning many entities with the same [`Bundle`] type, in a single batch.
+///
+/// Each [`Commands::spawn`](super::Commands::spawn) reserves an entity and queues a command of
+/// its own, which adds up for systems spawning thousands of entities per frame. A
+/// `BatchSpawner` only collects the bundles instead, and spawns all of them with
+/// [`World::spawn_batch`] when the buffers of its system are applied, like the commands of the
+/// system: at the next [`apply_deferred`](crate::schedule::apply_deferred) after the system, or
+/// at the end of the schedule.
+///
+/// The entities don't exist before then, so unlike with [`Commands`](super::Commands), their
+/// [`Entity`](crate::entity::Entity) isn't known to the system. The bundles are spawned in the
+/// order they were added. Like all buffers of a system, they are applied in the order of the
+/// parameters of the system, so a `BatchSpawner` after [`Commands`](super::Commands) spawns its
+/// entities after the commands of the system are applied, and before them otherwise.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::BatchSpawner;
+/// #[derive(Component)]
+/// struct Particle {
+///     lifetime: f32,
+/// }
+///
+/// fn emit_particles(mut particles: BatchSpawner<Particle>) {
+///     particles.spawn_batch((0..1000).map(|i| Particle {
+///         lifetime: i as f32 / 1000.0,
+///     }));
+/// }
+/// # let mut world = World::new();
+/// # let mut schedule = Schedule::default();
+/// # schedule.add_systems(emit_particles);
+/// # schedule.run(&mut world);
+/// # assert_eq!(world.query::<&Particle>().iter(&world).count(), 1000);
+/// ```
+#[derive(SystemParam)]
+pub struct BatchSpawner<'s, B: Bundle> {
+    buffer: Deferred<'s, BatchSpawnBuffer<B>>,
+}
+
+impl<'s, B: Bundle> BatchSpawner<'s, B> {
+    /// Queues an entity with the given `bundle` to be spawned.
+    #[inline]
+    pub fn spawn(&mut self, bundle: B) {
+        self.buffer.bundles.push(bundle);
+    }
+
+    /// Queues an entity to be spawned for each bundle of `bundles`.
+    pub fn spawn_batch(&mut self, bundles: impl IntoIterator<Item = B>) {
+        self.buffer.bundles.extend(bundles);
+    }
+
+    /// Reserves capacity for at least `additional` more bundles, e.g. when the number of
+    /// entities to spawn is known upfront.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buffer.bundles.reserve(additional);
+    }
+
+    /// Returns the number of entities queued by this system since its buffers were last applied.
+    pub fn len(&self) -> usize {
+        self.buffer.bundles.len()
+    }
+
+    /// Returns `true` if no entity is queued.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.bundles.is_empty()
+    }
+}
@@ -90,6 +90,22 @@ impl CommandQueue {
         }
     }
 
+    /// Reserves capacity for at least `additional` more bytes of queued commands, to avoid
+    /// reallocating while pushing them.
+    ///
+    /// Each command takes the size of its type plus a pointer-sized header.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.bytes.reserve(additional);
+    }
+
+    /// Moves all the commands of `other` to the end of this queue, leaving `other` empty.
+    #[inline]
+    pub fn append(&mut self, other: &mut CommandQueue) {
+        // Commands are read unaligned, so their bytes can be moved anywhere in the buffer.
+        self.bytes.append(&mut other.bytes);
+    }
+
     /// Execute the queued [`Command`]s in the world.
     /// This clears the queue.
     #[inline]
@@ -168,6 +184,20 @@ mod test {
         fn apply(self, _: &mut World) {}
     }
 
+    #[test]
+    fn test_command_queue_reserve() {
+        let mut queue = CommandQueue::default();
+        assert_eq!(queue.bytes.capacity(), 0);
+
+        queue.reserve(256);
+        assert!(queue.bytes.capacity() >= 256);
+
+        // Applying the queue keeps the capacity for the next commands.
+        queue.push(SpawnCommand);
+        queue.apply(&mut World::new());
+        assert!(queue.bytes.capacity() >= 256);
+    }
+
     #[test]
     fn test_command_queue_inner_drop() {
         let mut queue = CommandQueue::default();
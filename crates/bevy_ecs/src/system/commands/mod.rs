@@ -1,5 +1,7 @@
+mod batch_spawner;
 mod command_queue;
 mod parallel_scope;
+mod scoped;
 
 use crate::{
     self as bevy_ecs,
@@ -8,13 +10,15 @@ use crate::{
     system::{RunSystem, SystemId},
     world::{EntityWorldMut, FromWorld, World},
 };
+pub use batch_spawner::*;
 use bevy_ecs_macros::SystemParam;
 use bevy_utils::tracing::{error, info};
 pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
+pub use scoped::*;
 use std::marker::PhantomData;
 
-use super::{Deferred, Resource, SystemBuffer, SystemMeta};
+use super::{Deferred, MergeSystemBuffer, Resource, SystemBuffer, SystemMeta};
 
 /// A [`World`] mutation.
 ///
@@ -122,6 +126,18 @@ impl SystemBuffer for CommandQueue {
         let _span_guard = _system_meta.commands_span.enter();
         self.apply(world);
     }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+impl MergeSystemBuffer for CommandQueue {
+    #[inline]
+    fn merge(&mut self, other: &mut Self) {
+        self.append(other);
+    }
 }
 
 impl<'w, 's> Commands<'w, 's> {
@@ -1149,7 +1165,11 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         component::Component,
-        system::{CommandQueue, Commands, Resource},
+        schedule::{apply_deferred, IntoSystemConfigs, Schedule},
+        system::{
+            apply_scoped_commands, BatchSpawner, CommandQueue, Commands, Query, ResMut, Resource,
+            ScopedCommands,
+        },
         world::World,
     };
     use std::sync::{
@@ -1303,4 +1323,102 @@ mod tests {
         assert!(!world.contains_resource::<W<i32>>());
         assert!(world.contains_resource::<W<f64>>());
     }
+
+    #[test]
+    fn append_command_queue() {
+        let mut world = World::default();
+        let mut first = CommandQueue::default();
+        let mut second = CommandQueue::default();
+        first.push(|world: &mut World| world.insert_resource(W(vec![1])));
+        second.push(|world: &mut World| world.resource_mut::<W<Vec<i32>>>().0.push(2));
+        second.push(simple_command);
+
+        first.append(&mut second);
+        second.apply(&mut world);
+        assert!(!world.contains_resource::<W<Vec<i32>>>());
+
+        first.apply(&mut world);
+        assert_eq!(world.resource::<W<Vec<i32>>>().0, [1, 2]);
+        assert_eq!(world.entities().len(), 1);
+    }
+
+    #[test]
+    fn scoped_commands() {
+        struct Late;
+
+        fn spawn(mut commands: Commands, mut late: ScopedCommands<Late>) {
+            commands.spawn(W(0u32));
+            late.commands().spawn(W(1u32));
+        }
+
+        fn count(query: Query<&W<u32>>, mut counts: ResMut<W<Vec<usize>>>) {
+            counts.0.push(query.iter().count());
+        }
+
+        let mut world = World::default();
+        world.insert_resource(W(Vec::<usize>::new()));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                apply_scoped_commands::<Late>,
+                count,
+                spawn,
+                apply_deferred,
+                count,
+                apply_scoped_commands::<Late>,
+                count,
+            )
+                .chain(),
+        );
+
+        // The regular commands are applied at `apply_deferred`, which only hands the scoped ones
+        // over, so they are applied by the second `apply_scoped_commands`.
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<W<Vec<usize>>>().0, [0, 1, 2]);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<W<Vec<usize>>>().0, [0, 1, 2, 2, 3, 4]);
+    }
+
+    #[test]
+    fn batch_spawner() {
+        const COUNT: u32 = 10_000;
+
+        fn spawn(mut spawner: BatchSpawner<(W<u32>, W<u64>)>) {
+            spawner.reserve(COUNT as usize);
+            spawner.spawn((W(0), W(0)));
+            spawner.spawn_batch((1..COUNT).map(|i| (W(i), W(i as u64))));
+            assert_eq!(spawner.len(), COUNT as usize);
+        }
+
+        fn count(query: Query<&W<u32>>, mut counts: ResMut<W<Vec<usize>>>) {
+            counts.0.push(query.iter().count());
+        }
+
+        let mut world = World::default();
+        world.insert_resource(W(Vec::<usize>::new()));
+        let mut schedule = Schedule::default();
+        schedule.add_systems((spawn, count, apply_deferred, count).chain());
+
+        // The entities are spawned at once by the sync point, and the buffer is drained so each
+        // run spawns a new batch.
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<W<Vec<usize>>>().0, [0, COUNT as usize]);
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<W<Vec<usize>>>().0,
+            [0, COUNT as usize, COUNT as usize, 2 * COUNT as usize]
+        );
+
+        let mut entities = world.query::<(&W<u32>, &W<u64>)>();
+        assert!(entities.iter(&world).all(|(a, b)| a.0 as u64 == b.0));
+        // The whole batch lands in a single archetype.
+        assert_eq!(
+            world
+                .archetypes()
+                .iter()
+                .filter(|archetype| !archetype.is_empty())
+                .count(),
+            1
+        );
+    }
 }
@@ -0,0 +1,137 @@
+use std::marker::PhantomData;
+
+use crate::{
+    self as bevy_ecs,
+    entity::Entities,
+    prelude::World,
+    system::{Deferred, Resource, SystemBuffer, SystemMeta, SystemParam},
+};
+
+use super::{CommandQueue, Commands};
+
+/// The commands of the [`ScopedCommands`] with the label `L` that are waiting for
+/// [`apply_scoped_commands`].
+#[derive(Resource)]
+struct ScopedCommandQueue<L: Send + Sync + 'static> {
+    queue: CommandQueue,
+    marker: PhantomData<fn() -> L>,
+}
+
+impl<L: Send + Sync + 'static> Default for ScopedCommandQueue<L> {
+    fn default() -> Self {
+        Self {
+            queue: CommandQueue::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The buffer of a [`ScopedCommands`] system parameter, which hands its commands over to the
+/// [`ScopedCommandQueue`] of its label instead of applying them.
+struct ScopedCommandBuffer<L: Send + Sync + 'static> {
+    queue: CommandQueue,
+    marker: PhantomData<fn() -> L>,
+}
+
+impl<L: Send + Sync + 'static> Default for ScopedCommandBuffer<L> {
+    fn default() -> Self {
+        Self {
+            queue: CommandQueue::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<L: Send + Sync + 'static> SystemBuffer for ScopedCommandBuffer<L> {
+    #[inline]
+    fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+        world
+            .get_resource_or_insert_with(ScopedCommandQueue::<L>::default)
+            .queue
+            .append(&mut self.queue);
+    }
+}
+
+/// An alternative to [`Commands`] whose commands are applied by [`apply_scoped_commands`] with
+/// the same label `L`, instead of at the next [`apply_deferred`](crate::schedule::apply_deferred).
+///
+/// The label is any type, usually a marker struct naming the point of the schedule the
+/// commands should be applied at. This gives finer control over when the effects of the
+/// commands, like spawned entities, become visible to other systems.
+///
+/// # Ordering
+///
+/// - Like the buffer of any system parameter, the buffer of `ScopedCommands` is handled when
+///   the buffers of its system are applied: at the next
+///   [`apply_deferred`](crate::schedule::apply_deferred) after the system, or at the end of the
+///   schedule. Instead of being applied then, the commands are appended to a queue shared by all
+///   the systems using the label `L`.
+/// - [`apply_scoped_commands::<L>`](apply_scoped_commands) applies the commands of that queue,
+///   in the order they were appended. To apply the commands of a system, it must be ordered
+///   after the sync point of that system. Commands that weren't handed over yet stay queued
+///   until the next run of `apply_scoped_commands::<L>`, even in a later frame.
+/// - The commands are therefore never applied earlier than they would be with [`Commands`],
+///   only later: the regular [`Commands`] of the same system are applied first.
+/// - Entities are reserved right away though, like with [`Commands`]. [`Commands::spawn`]
+///   returns an [`Entity`](crate::entity::Entity) that the next flush of the [`World`] turns
+///   into an empty entity, usually well before `apply_scoped_commands::<L>` inserts its
+///   components. If `apply_scoped_commands::<L>` never runs, such entities stay empty and are
+///   never despawned.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{apply_scoped_commands, ScopedCommands};
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// // Names the point where the enemies spawned this frame appear.
+/// struct EnemyWave;
+///
+/// fn spawn_enemy(mut commands: ScopedCommands<EnemyWave>) {
+///     commands.commands().spawn(Enemy);
+/// }
+///
+/// fn count_enemies(enemies: Query<&Enemy>) {
+///     assert_eq!(enemies.iter().count(), 0);
+/// }
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(
+///     (
+///         spawn_enemy,
+///         apply_deferred,
+///         // The enemy entity exists, but it doesn't have its `Enemy` component yet.
+///         count_enemies,
+///         apply_scoped_commands::<EnemyWave>,
+///     )
+///         .chain(),
+/// );
+/// # let mut world = World::new();
+/// # schedule.run(&mut world);
+/// # assert_eq!(world.query::<&Enemy>().iter(&world).count(), 1);
+/// ```
+#[derive(SystemParam)]
+pub struct ScopedCommands<'w, 's, L: Send + Sync + 'static> {
+    buffer: Deferred<'s, ScopedCommandBuffer<L>>,
+    entities: &'w Entities,
+}
+
+impl<'w, 's, L: Send + Sync + 'static> ScopedCommands<'w, 's, L> {
+    /// Returns the [`Commands`] writing to the buffer of this parameter.
+    pub fn commands(&mut self) -> Commands<'w, '_> {
+        Commands::new_from_entities(&mut self.buffer.queue, self.entities)
+    }
+}
+
+/// Applies the commands of the [`ScopedCommands`] with the label `L`, which were handed over
+/// by the sync points of their systems.
+///
+/// See [`ScopedCommands`] for the ordering guarantees.
+pub fn apply_scoped_commands<L: Send + Sync + 'static>(world: &mut World) {
+    let Some(mut scoped) = world.get_resource_mut::<ScopedCommandQueue<L>>() else {
+        return;
+    };
+    let mut queue = std::mem::take(&mut scoped.queue);
+    queue.apply(world);
+}
@@ -92,6 +92,12 @@ where
         panic!("Cannot run exclusive systems with a shared World reference");
     }
 
+    #[inline]
+    unsafe fn validate_param_unsafe(&self, _world: UnsafeWorldCell) -> bool {
+        // Exclusive system params have no way to fail.
+        true
+    }
+
     fn run(&mut self, input: Self::In, world: &mut World) -> Self::Out {
         #[cfg(feature = "trace")]
         let _span_guard = self.system_meta.system_span.enter();
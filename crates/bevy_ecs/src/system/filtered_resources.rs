@@ -0,0 +1,255 @@
+use bevy_ptr::Ptr;
+
+use crate::{
+    change_detection::{MutUntyped, Ticks, TicksMut},
+    component::{ComponentId, Tick, TickCells},
+    query::Access,
+    system::{ReadOnlySystemParam, Res, ResMut, Resource, SystemMeta, SystemParam},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// A [`SystemParam`] that reads a set of resources chosen at runtime.
+///
+/// The resources are given by their [`ComponentId`]s with a
+/// [`FilteredResourcesBuilder`](super::FilteredResourcesBuilder), which registers read access
+/// to each of them like a [`Res`] would. Without a builder, no resource can be read.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{
+/// #     prelude::*,
+/// #     system::{FilteredResources, FilteredResourcesBuilder, SystemParamBuilder},
+/// # };
+/// #
+/// # #[derive(Resource)]
+/// # struct Score(u32);
+/// #
+/// let mut world = World::new();
+/// world.insert_resource(Score(5));
+///
+/// // The resource to read is only known at runtime.
+/// let score = world.components().resource_id::<Score>().unwrap();
+///
+/// let mut system = (FilteredResourcesBuilder::new().add_read(score),)
+///     .build_state(&mut world)
+///     .build_system(|resources: FilteredResources| {
+///         assert_eq!(resources.get::<Score>().unwrap().0, 5);
+///     });
+///
+/// system.run((), &mut world);
+/// ```
+pub struct FilteredResources<'w, 's> {
+    world: UnsafeWorldCell<'w>,
+    access: &'s Access<ComponentId>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl<'w, 's> FilteredResources<'w, 's> {
+    /// Returns the resources that can be read.
+    pub fn access(&self) -> &Access<ComponentId> {
+        self.access
+    }
+
+    /// Gets a reference to the resource of type `R`.
+    ///
+    /// Returns `None` if the resource doesn't exist, or if it isn't in the access.
+    pub fn get<R: Resource>(&self) -> Option<Res<'w, R>> {
+        let component_id = self.world.components().resource_id::<R>()?;
+        let (ptr, ticks) = self.get_with_ticks(component_id)?;
+        Some(Res {
+            // SAFETY: `component_id` is the id of `R`.
+            value: unsafe { ptr.deref() },
+            // SAFETY: The ticks are only read.
+            ticks: unsafe { Ticks::from_tick_cells(ticks, self.last_run, self.this_run) },
+        })
+    }
+
+    /// Gets a pointer to the resource with the given [`ComponentId`].
+    ///
+    /// Returns `None` if the resource doesn't exist, or if it isn't in the access.
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<Ptr<'w>> {
+        self.get_with_ticks(component_id).map(|(ptr, _)| ptr)
+    }
+
+    fn get_with_ticks(&self, component_id: ComponentId) -> Option<(Ptr<'w>, TickCells<'w>)> {
+        if !self.access.has_read(component_id) {
+            return None;
+        }
+        // SAFETY: Read access to the resource is registered, and `&self` ensures no mutable
+        // access to it exists.
+        unsafe { self.world.get_resource_with_ticks(component_id) }
+    }
+}
+
+/// A [`SystemParam`] that reads and writes a set of resources chosen at runtime.
+///
+/// The resources are given by their [`ComponentId`]s with a
+/// [`FilteredResourcesMutBuilder`](super::FilteredResourcesMutBuilder), which registers read or
+/// write access to each of them like a [`Res`] or [`ResMut`] would. Without a builder, no
+/// resource can be accessed.
+///
+/// See [`FilteredResources`] for an example of building a system with such a parameter.
+pub struct FilteredResourcesMut<'w, 's> {
+    world: UnsafeWorldCell<'w>,
+    access: &'s Access<ComponentId>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl<'w, 's> FilteredResourcesMut<'w, 's> {
+    /// Returns the resources that can be read and written.
+    pub fn access(&self) -> &Access<ComponentId> {
+        self.access
+    }
+
+    /// Gets read-only access to all of the resources of this parameter.
+    pub fn as_readonly(&self) -> FilteredResources<'_, 's> {
+        FilteredResources {
+            world: self.world,
+            access: self.access,
+            last_run: self.last_run,
+            this_run: self.this_run,
+        }
+    }
+
+    /// Gets a reference to the resource of type `R`.
+    ///
+    /// Returns `None` if the resource doesn't exist, or if it isn't in the access.
+    pub fn get<R: Resource>(&self) -> Option<Res<'_, R>> {
+        self.as_readonly().get()
+    }
+
+    /// Gets a pointer to the resource with the given [`ComponentId`].
+    ///
+    /// Returns `None` if the resource doesn't exist, or if it isn't in the access.
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<Ptr<'_>> {
+        self.as_readonly().get_by_id(component_id)
+    }
+
+    /// Gets a mutable reference to the resource of type `R`.
+    ///
+    /// Returns `None` if the resource doesn't exist, or if it can't be written.
+    pub fn get_mut<R: Resource>(&mut self) -> Option<ResMut<'_, R>> {
+        let component_id = self.world.components().resource_id::<R>()?;
+        let value = self.get_mut_by_id(component_id)?;
+        Some(ResMut {
+            // SAFETY: `component_id` is the id of `R`.
+            value: unsafe { value.value.deref_mut() },
+            ticks: value.ticks,
+        })
+    }
+
+    /// Gets a mutable pointer to the resource with the given [`ComponentId`].
+    ///
+    /// Returns `None` if the resource doesn't exist, or if it can't be written.
+    pub fn get_mut_by_id(&mut self, component_id: ComponentId) -> Option<MutUntyped<'_>> {
+        if !self.access.has_write(component_id) {
+            return None;
+        }
+        // SAFETY: Write access to the resource is registered, and `&mut self` ensures no other
+        // access to it exists.
+        let (ptr, ticks) = unsafe { self.world.get_resource_with_ticks(component_id) }?;
+        Some(MutUntyped {
+            // SAFETY: We have exclusive access to the resource.
+            value: unsafe { ptr.assert_unique() },
+            // SAFETY: We have exclusive access to the ticks.
+            ticks: unsafe { TicksMut::from_tick_cells(ticks, self.last_run, self.this_run) },
+        })
+    }
+}
+
+// SAFETY: Only reads the resources in its access.
+unsafe impl ReadOnlySystemParam for FilteredResources<'_, '_> {}
+
+// SAFETY: The builder registers read access to every resource in the state. `init_state`
+// creates an empty access.
+unsafe impl SystemParam for FilteredResources<'_, '_> {
+    type State = Access<ComponentId>;
+    type Item<'w, 's> = FilteredResources<'w, 's>;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {
+        Access::default()
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        FilteredResources {
+            world,
+            access: state,
+            last_run: system_meta.last_run,
+            this_run: change_tick,
+        }
+    }
+}
+
+// SAFETY: The builder registers read or write access to every resource in the state.
+// `init_state` creates an empty access.
+unsafe impl SystemParam for FilteredResourcesMut<'_, '_> {
+    type State = Access<ComponentId>;
+    type Item<'w, 's> = FilteredResourcesMut<'w, 's>;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {
+        Access::default()
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        FilteredResourcesMut {
+            world,
+            access: state,
+            last_run: system_meta.last_run,
+            this_run: change_tick,
+        }
+    }
+}
+
+/// Registers access to the resources of `access` in `system_meta`, like [`Res`] and [`ResMut`]
+/// do, panicking if it conflicts with the accesses of previous parameters.
+pub(crate) fn init_filtered_resources(
+    world: &mut World,
+    system_meta: &mut SystemMeta,
+    access: &Access<ComponentId>,
+) {
+    for component_id in access.reads_and_writes() {
+        let archetype_component_id = world.initialize_resource_internal(component_id).id();
+        let name = world.components().get_info(component_id).unwrap().name();
+        let combined_access = system_meta.component_access_set.combined_access();
+        if access.has_write(component_id) {
+            assert!(
+                !combined_access.has_read(component_id),
+                "error[B0002]: FilteredResourcesMut in system {} writes {name}, which conflicts with a previous access. Consider removing the duplicate access.",
+                system_meta.name,
+            );
+            system_meta
+                .component_access_set
+                .add_unfiltered_write(component_id);
+            system_meta
+                .archetype_component_access
+                .add_write(archetype_component_id);
+        } else {
+            assert!(
+                !combined_access.has_write(component_id),
+                "error[B0002]: Filtered resources in system {} read {name}, which conflicts with a previous mutable access. Consider removing the duplicate access.",
+                system_meta.name,
+            );
+            system_meta
+                .component_access_set
+                .add_unfiltered_read(component_id);
+            system_meta
+                .archetype_component_access
+                .add_read(archetype_component_id);
+        }
+    }
+}
@@ -3,22 +3,45 @@ use crate::{
     component::{ComponentId, Tick},
     prelude::FromWorld,
     query::{Access, FilteredAccessSet},
-    system::{check_system_change_tick, ReadOnlySystemParam, System, SystemParam, SystemParamItem},
+    system::{
+        check_system_change_tick, ReadOnlySystemParam, System, SystemParam, SystemParamBuilder,
+        SystemParamItem,
+    },
     world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
 };
 
 use bevy_utils::all_tuples;
-use std::{any::TypeId, borrow::Cow, marker::PhantomData};
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Span};
 
 use super::{In, IntoSystem, ReadOnlySystem};
 
+/// An identifier of a system instance, which stays the same across all of its runs.
+///
+/// Unlike the name of a system, it is different for each instance of the same system, such as
+/// when the same function is added to a schedule twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SystemInstanceId(u64);
+
+impl SystemInstanceId {
+    fn new() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 /// The metadata of a [`System`].
 #[derive(Clone)]
 pub struct SystemMeta {
     pub(crate) name: Cow<'static, str>,
+    instance_id: SystemInstanceId,
     pub(crate) component_access_set: FilteredAccessSet<ComponentId>,
     pub(crate) archetype_component_access: Access<ArchetypeComponentId>,
     // NOTE: this must be kept private. making a SystemMeta non-send is irreversible to prevent
@@ -29,6 +52,8 @@ pub struct SystemMeta {
     pub(crate) system_span: Span,
     #[cfg(feature = "trace")]
     pub(crate) commands_span: Span,
+    #[cfg(feature = "param_timings")]
+    pub(crate) param_timings: Option<super::SystemParamTimings>,
 }
 
 impl SystemMeta {
@@ -36,6 +61,7 @@ impl SystemMeta {
         let name = std::any::type_name::<T>();
         Self {
             name: name.into(),
+            instance_id: SystemInstanceId::new(),
             archetype_component_access: Access::default(),
             component_access_set: FilteredAccessSet::default(),
             is_send: true,
@@ -44,6 +70,35 @@ impl SystemMeta {
             system_span: info_span!("system", name = name),
             #[cfg(feature = "trace")]
             commands_span: info_span!("system_commands", name = name),
+            #[cfg(feature = "param_timings")]
+            param_timings: None,
+        }
+    }
+
+    /// Starts recording the timings of the parameters of the system into the
+    /// [`SystemParamTimings`](super::SystemParamTimings) of `world`.
+    #[cfg(feature = "param_timings")]
+    pub(crate) fn init_param_timings(&mut self, world: &mut World) {
+        if self.param_timings.is_none() {
+            self.param_timings = Some(
+                world
+                    .get_resource_or_insert_with(super::SystemParamTimings::default)
+                    .clone(),
+            );
+        }
+    }
+
+    #[cfg(feature = "param_timings")]
+    pub(crate) fn record_get_param<P>(&self, elapsed: bevy_utils::Duration) {
+        if let Some(timings) = &self.param_timings {
+            timings.record_get_param(&self.name, std::any::type_name::<P>(), elapsed);
+        }
+    }
+
+    #[cfg(feature = "param_timings")]
+    pub(crate) fn record_new_archetype<P>(&self, elapsed: bevy_utils::Duration) {
+        if let Some(timings) = &self.param_timings {
+            timings.record_new_archetype(&self.name, std::any::type_name::<P>(), elapsed);
         }
     }
 
@@ -53,6 +108,12 @@ impl SystemMeta {
         &self.name
     }
 
+    /// Returns the identifier of this system instance.
+    #[inline]
+    pub fn instance_id(&self) -> SystemInstanceId {
+        self.instance_id
+    }
+
     /// Returns true if the system is [`Send`].
     #[inline]
     pub fn is_send(&self) -> bool {
@@ -66,6 +127,20 @@ impl SystemMeta {
     pub fn set_non_send(&mut self) {
         self.is_send = false;
     }
+
+    /// Returns the component [`FilteredAccess`](crate::query::FilteredAccess) of each parameter of
+    /// the system, registered when the system was initialized.
+    #[inline]
+    pub fn component_access_set(&self) -> &FilteredAccessSet<ComponentId> {
+        &self.component_access_set
+    }
+
+    /// Returns the archetype component [`Access`] of the system, which is updated when new
+    /// archetypes are created.
+    #[inline]
+    pub fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
 }
 
 // TODO: Actually use this in FunctionSystem. We should probably only do this once Systems are constructed using a World reference
@@ -187,6 +262,54 @@ impl<Param: SystemParam> SystemState<Param> {
         }
     }
 
+    /// Creates a new [`SystemState`] with the state of the parameters created by the given
+    /// [`SystemParamBuilder`] instead of [`SystemParam::init_state`].
+    pub fn from_builder(world: &mut World, builder: impl SystemParamBuilder<Param>) -> Self {
+        let mut meta = SystemMeta::new::<Param>();
+        meta.last_run = world.change_tick().relative_to(Tick::MAX);
+        // Builders skip the `init_state` of the parameter tuple, where the timings of other
+        // systems start being recorded.
+        #[cfg(feature = "param_timings")]
+        meta.init_param_timings(world);
+        let param_state = builder.build(world, &mut meta);
+        Self {
+            meta,
+            param_state,
+            world_id: world.id(),
+            archetype_generation: ArchetypeGeneration::initial(),
+        }
+    }
+
+    /// Creates a [`FunctionSystem`] from this [`SystemState`], which keeps the state of its
+    /// parameters.
+    ///
+    /// The system is already initialized, and can only be used with the [`World`] this
+    /// [`SystemState`] was created with. See [`SystemParamBuilder`] for an example.
+    pub fn build_system<Marker, F: SystemParamFunction<Marker, Param = Param>>(
+        self,
+        func: F,
+    ) -> FunctionSystem<Marker, F> {
+        let mut system_meta = SystemMeta::new::<F>();
+        // Keep the id the parameters were built with.
+        system_meta.instance_id = self.meta.instance_id;
+        system_meta.component_access_set = self.meta.component_access_set;
+        system_meta.archetype_component_access = self.meta.archetype_component_access;
+        system_meta.is_send = self.meta.is_send;
+        system_meta.last_run = self.meta.last_run;
+        #[cfg(feature = "param_timings")]
+        {
+            system_meta.param_timings = self.meta.param_timings;
+        }
+        FunctionSystem {
+            func,
+            param_state: Some(self.param_state),
+            system_meta,
+            world_id: Some(self.world_id),
+            archetype_generation: self.archetype_generation,
+            marker: PhantomData,
+        }
+    }
+
     /// Gets the metadata for this instance.
     #[inline]
     pub fn meta(&self) -> &SystemMeta {
@@ -423,6 +546,16 @@ where
     // When lines get too long, rustfmt can sometimes refuse to format them.
     // Work around this by storing the message separately.
     const PARAM_MESSAGE: &'static str = "System's param_state was not found. Did you forget to initialize this system before running it?";
+
+    /// Returns the [`SystemMeta`] of the system, which describes the world accesses of its
+    /// parameters once the system is initialized.
+    ///
+    /// This is read-only metadata, to inspect which components and resources each parameter
+    /// reads and writes.
+    #[inline]
+    pub fn system_meta(&self) -> &SystemMeta {
+        &self.system_meta
+    }
 }
 
 impl<Marker, F> System for FunctionSystem<Marker, F>
@@ -486,6 +619,17 @@ where
         out
     }
 
+    #[inline]
+    unsafe fn validate_param_unsafe(&self, world: UnsafeWorldCell) -> bool {
+        let param_state = self.param_state.as_ref().expect(Self::PARAM_MESSAGE);
+        // SAFETY:
+        // - The caller has invoked `update_archetype_component_access`, which will panic
+        //   if the world does not match.
+        // - All world accesses used by `F::Param` have been registered, so the caller
+        //   will ensure that there are no data access conflicts.
+        F::Param::validate_param(param_state, &self.system_meta, world)
+    }
+
     fn get_last_run(&self) -> Tick {
         self.system_meta.last_run
     }
@@ -502,9 +646,18 @@ where
 
     #[inline]
     fn initialize(&mut self, world: &mut World) {
-        self.world_id = Some(world.id());
+        if let Some(id) = self.world_id {
+            // Systems built from a `SystemState` already have their param state.
+            assert_eq!(
+                id,
+                world.id(),
+                "System built with a different world than the one it was added to.",
+            );
+        } else {
+            self.world_id = Some(world.id());
+            self.param_state = Some(F::Param::init_state(world, &mut self.system_meta));
+        }
         self.system_meta.last_run = world.change_tick().relative_to(Tick::MAX);
-        self.param_state = Some(F::Param::init_state(world, &mut self.system_meta));
     }
 
     fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
@@ -85,7 +85,11 @@
 //! - [`Query`]
 //! - [`Res`] and `Option<Res>`
 //! - [`ResMut`] and `Option<ResMut>`
+//! - [`ResOrSkip`] and [`ResExists`]
+//! - [`ResInit`] and [`ResMutInit`]
+//! - [`Maybe`] of any other system parameter
 //! - [`Commands`]
+//! - [`BatchSpawner`]
 //! - [`Local`]
 //! - [`EventReader`](crate::event::EventReader)
 //! - [`EventWriter`](crate::event::EventWriter)
@@ -94,20 +98,27 @@
 //! - [`&World`](crate::world::World)
 //! - [`RemovedComponents`](crate::removal_detection::RemovedComponents)
 //! - [`SystemName`]
-//! - [`SystemChangeTick`]
+//! - [`SystemInfo`]
+//! - [`SystemChangeTick`], [`CurrentTick`] and [`TicksSinceLastRun`]
 //! - [`Archetypes`](crate::archetype::Archetypes) (Provides Archetype metadata)
 //! - [`Bundles`](crate::bundle::Bundles) (Provides Bundles metadata)
 //! - [`Components`](crate::component::Components) (Provides Components metadata)
+//! - [`ComponentRegistry`] (Provides Components metadata with type lookups)
 //! - [`Entities`](crate::entity::Entities) (Provides Entities metadata)
+//! - [`EntityStats`] (Provides entity and archetype counts)
 //! - All tuples between 1 to 16 elements where each element implements [`SystemParam`]
 //! - [`()` (unit primitive type)](https://doc.rust-lang.org/stable/std/primitive.unit.html)
 
 mod adapter_system;
+mod builder;
 mod combinator;
 mod commands;
 mod exclusive_function_system;
 mod exclusive_system_param;
+mod filtered_resources;
 mod function_system;
+#[cfg(feature = "param_timings")]
+mod param_timings;
 mod query;
 #[allow(clippy::module_inception)]
 mod system;
@@ -117,11 +128,15 @@ mod system_registry;
 use std::borrow::Cow;
 
 pub use adapter_system::*;
+pub use builder::*;
 pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
 pub use exclusive_system_param::*;
+pub use filtered_resources::*;
 pub use function_system::*;
+#[cfg(feature = "param_timings")]
+pub use param_timings::*;
 pub use query::*;
 pub use system::*;
 pub use system_param::*;
@@ -546,12 +561,17 @@ mod tests {
         query::{Added, Changed, Or, With, Without},
         removal_detection::RemovedComponents,
         schedule::{
-            apply_deferred, common_conditions::resource_exists, Condition, IntoSystemConfigs,
-            Schedule,
+            apply_deferred,
+            common_conditions::{resource_equals, resource_exists},
+            Condition, ExecutorKind, IntoSystemConfigs, Schedule,
         },
         system::{
-            Commands, In, IntoSystem, Local, NonSend, NonSendMut, ParamSet, Query, Res, ResMut,
-            Resource, System, SystemState,
+            assert_is_read_only_system, Commands, ComponentRegistry, CurrentTick, Deferred,
+            EntityCount, EntityStats, FirstRun, In, IntoSystem, Local, MatchedArchetypes, Maybe,
+            MergeSystemBuffer, NonSend, NonSendMut, ParamSet, Populated, Query, Res, ResExists,
+            ResInit, ResMut, ResMutInit, ResOrSkip, Resource, Single, System, SystemBuffer,
+            SystemChangeTick, SystemInfo, SystemInstanceId, SystemMeta, SystemState,
+            TicksSinceLastRun,
         },
         world::{FromWorld, World},
     };
@@ -1004,6 +1024,631 @@ mod tests {
         run_system(&mut world, sys);
     }
 
+    #[test]
+    fn max_query_set_system() {
+        #[allow(clippy::type_complexity)]
+        fn sys(
+            mut set: ParamSet<(
+                Query<&mut A>,
+                Query<&A>,
+                Query<&mut A>,
+                Query<&A>,
+                Query<&mut A>,
+                Query<&A>,
+                Query<&mut A>,
+                Query<&A>,
+                Query<&mut A>,
+                Query<&A>,
+                Query<&mut A>,
+                Query<&A>,
+                Query<&mut A>,
+                Query<&A>,
+                Query<&mut A>,
+                Query<&mut B>,
+            )>,
+            mut ran: ResMut<SystemRan>,
+        ) {
+            assert_eq!(set.p8().iter_mut().count(), 1);
+            assert_eq!(set.p15().iter_mut().count(), 1);
+            *ran = SystemRan::Yes;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(SystemRan::No);
+        world.spawn((A, B));
+        run_system(&mut world, sys);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+    }
+
+    #[test]
+    fn indexed_query_set_system() {
+        fn sys(
+            mut set: ParamSet<(Query<&mut W<u32>>, Query<&mut W<u32>>, Query<&mut W<u32>>)>,
+            mut ran: ResMut<SystemRan>,
+        ) {
+            for index in 0..3 {
+                for mut w in set.get_mut(index).unwrap().iter_mut() {
+                    w.0 += 1;
+                }
+            }
+            assert!(set.get_mut(3).is_none());
+            assert_eq!(set.p0().single().0, 3);
+            *ran = SystemRan::Yes;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(SystemRan::No);
+        world.spawn(W(0u32));
+        run_system(&mut world, sys);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+    }
+
+    #[test]
+    fn single_system_skipped() {
+        #[derive(Resource, Default)]
+        struct Counter(usize);
+
+        fn count(single: Single<&W<usize>>, mut counter: ResMut<Counter>) {
+            assert_eq!(single.0, 1);
+            counter.0 += 1;
+        }
+
+        for executor in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::default();
+            world.init_resource::<Counter>();
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems(count);
+
+            // Skipped without a matching entity.
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0, 0);
+
+            world.spawn(W(1usize));
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0, 1);
+
+            // Skipped with more than one matching entity.
+            let other = world.spawn(W(2usize)).id();
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0, 1);
+
+            world.despawn(other);
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0, 2);
+        }
+    }
+
+    #[test]
+    fn populated_system_skipped() {
+        #[derive(Resource, Default)]
+        struct Sum(usize);
+
+        fn sum(populated: Populated<&W<usize>>, mut total: ResMut<Sum>) {
+            assert!(!populated.is_empty());
+            total.0 += populated.iter().map(|w| w.0).sum::<usize>();
+        }
+
+        for executor in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::default();
+            world.init_resource::<Sum>();
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems(sum);
+
+            // Skipped without a matching entity.
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Sum>().0, 0);
+
+            let entity = world.spawn(W(1usize)).id();
+            world.spawn(W(2usize));
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Sum>().0, 3);
+
+            world.despawn(entity);
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Sum>().0, 5);
+        }
+    }
+
+    #[test]
+    fn missing_resource_system_skipped() {
+        #[derive(Resource, Default)]
+        struct Counter(usize);
+
+        #[derive(Resource, PartialEq)]
+        struct Increment(usize);
+
+        struct NonSendIncrement(usize);
+
+        fn count(increment: Res<Increment>, mut counter: ResMut<Counter>) {
+            counter.0 += increment.0;
+        }
+
+        fn count_non_send(increment: NonSend<NonSendIncrement>, mut counter: ResMut<Counter>) {
+            counter.0 += increment.0;
+        }
+
+        for executor in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::default();
+            world.init_resource::<Counter>();
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems((
+                count,
+                count_non_send,
+                // The condition isn't met while `Increment` doesn't exist.
+                count.run_if(resource_equals(Increment(1))),
+            ));
+
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0, 0);
+
+            world.insert_resource(Increment(1));
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0, 2);
+
+            world.insert_non_send_resource(NonSendIncrement(10));
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0, 14);
+        }
+    }
+
+    #[test]
+    fn deferred_apply_order() {
+        #[derive(Resource, Default)]
+        struct Log(Vec<&'static str>);
+
+        #[derive(Default)]
+        struct Push(Option<&'static str>);
+
+        impl SystemBuffer for Push {
+            fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+                if let Some(entry) = self.0.take() {
+                    world.resource_mut::<Log>().0.push(entry);
+                }
+            }
+        }
+
+        fn first(mut a: Deferred<Push>, mut b: Deferred<Push>) {
+            a.0 = Some("first a");
+            b.0 = Some("first b");
+        }
+
+        fn second(mut a: Deferred<Push>) {
+            a.0 = Some("second");
+        }
+
+        for executor in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::default();
+            world.init_resource::<Log>();
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            // Added in reverse to show that the ordering constraints decide the apply order.
+            schedule.add_systems((second.after(first), first));
+
+            schedule.run(&mut world);
+            schedule.run(&mut world);
+            assert_eq!(
+                world.resource::<Log>().0,
+                ["first a", "first b", "second", "first a", "first b", "second"]
+            );
+        }
+    }
+
+    #[test]
+    fn deferred_merge() {
+        #[derive(Resource, Default)]
+        struct Log(Vec<&'static str>);
+
+        #[derive(Default)]
+        struct Push(Vec<&'static str>);
+
+        impl SystemBuffer for Push {
+            fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+                world.resource_mut::<Log>().0.append(&mut self.0);
+            }
+        }
+
+        impl MergeSystemBuffer for Push {
+            fn merge(&mut self, other: &mut Self) {
+                self.0.append(&mut other.0);
+            }
+        }
+
+        fn merge(mut a: Deferred<Push>, mut b: Deferred<Push>, mut c: Deferred<Push>) {
+            a.0.push("a");
+            b.0.push("b");
+            c.0.extend(["c 1", "c 2"]);
+            // Everything is applied through `a`, in the order `a`, `c`, `b`.
+            c.merge(&mut b);
+            a.merge(&mut c);
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Log>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(merge);
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, ["a", "c 1", "c 2", "b"]);
+    }
+
+    #[test]
+    fn matched_archetypes_system() {
+        #[derive(Resource, Default)]
+        struct Matched(Vec<(usize, usize, usize)>);
+
+        fn inspect(
+            with_a: MatchedArchetypes<&A>,
+            with_b: MatchedArchetypes<Entity, With<B>>,
+            // Matching archetypes doesn't access the components of the filter.
+            _: Query<&mut B>,
+            mut matched: ResMut<Matched>,
+        ) {
+            assert_eq!(with_a.iter().len(), with_a.len());
+            matched
+                .0
+                .push((with_a.len(), with_a.entity_count(), with_b.entity_count()));
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Matched>();
+        world.spawn(A);
+        world.spawn((A, B));
+        world.spawn((A, C));
+        world.spawn(A);
+        world.spawn(B);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(inspect);
+
+        schedule.run(&mut world);
+        world.spawn((A, B, C));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Matched>().0, [(3, 4, 2), (4, 5, 3)]);
+    }
+
+    #[test]
+    fn entity_count_system() {
+        #[derive(Resource, Default)]
+        struct Counts(Vec<(usize, usize, usize)>);
+
+        fn count(
+            all: EntityCount,
+            with_a: EntityCount<With<A>>,
+            changed_b: EntityCount<Changed<B>>,
+            // Filtering with `With<A>` doesn't conflict with writing `A`.
+            _: Query<&mut A>,
+            mut counts: ResMut<Counts>,
+        ) {
+            counts.0.push((*all, *with_a, changed_b.get()));
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Counts>();
+        world.spawn(A);
+        world.spawn((A, B));
+        world.spawn((A, C));
+        world.spawn(B);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(count);
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        world.spawn((A, B));
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<Counts>().0,
+            [(4, 3, 2), (4, 3, 0), (5, 4, 1)]
+        );
+    }
+
+    #[test]
+    fn system_info_system() {
+        #[derive(Resource, Default)]
+        struct Infos(Vec<(String, SystemInstanceId)>);
+
+        fn record(info: SystemInfo, mut infos: ResMut<Infos>) {
+            infos.0.push((info.name().to_owned(), info.instance_id()));
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Infos>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((record, record).chain());
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        let infos = &world.resource::<Infos>().0;
+        assert_eq!(infos.len(), 4);
+        // Both instances have the same name, but different ids.
+        assert_eq!(infos[0].0, infos[1].0);
+        assert_ne!(infos[0].1, infos[1].1);
+        // The ids stay the same across runs.
+        assert_eq!(infos[0].1, infos[2].1);
+        assert_eq!(infos[1].1, infos[3].1);
+    }
+
+    #[test]
+    fn first_run_system() {
+        #[derive(Resource, Default)]
+        struct Runs(Vec<bool>);
+
+        fn record(first_run: FirstRun, mut runs: ResMut<Runs>) {
+            runs.0.push(*first_run);
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Runs>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(record);
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Runs>().0, [true, false, false]);
+
+        // A new instance of the system runs for the first time again.
+        let mut system = IntoSystem::into_system(record);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        assert_eq!(world.resource::<Runs>().0, [true, false, false, true]);
+    }
+
+    #[test]
+    fn current_tick_system() {
+        #[derive(Resource, Default)]
+        struct Ticks(Vec<Tick>);
+
+        fn record(tick: CurrentTick, change_tick: SystemChangeTick, mut ticks: ResMut<Ticks>) {
+            assert_eq!(tick.get(), change_tick.this_run());
+            ticks.0.push(*tick);
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Ticks>();
+        assert_is_read_only_system(|_: CurrentTick| {});
+
+        let mut system = IntoSystem::into_system(record);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        let first = world.resource::<Ticks>().0[0];
+        assert_eq!(first.get() + 1, world.read_change_tick().get());
+
+        system.run((), &mut world);
+        let second = world.resource::<Ticks>().0[1];
+        assert!(second.is_newer_than(first, world.read_change_tick()));
+    }
+
+    #[test]
+    fn ticks_since_last_run_system() {
+        #[derive(Resource, Default)]
+        struct Deltas(Vec<u32>);
+
+        fn record(ticks: TicksSinceLastRun, mut deltas: ResMut<Deltas>) {
+            deltas.0.push(*ticks);
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Deltas>();
+        assert_is_read_only_system(|_: TicksSinceLastRun| {});
+
+        let mut system = IntoSystem::into_system(record);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        system.run((), &mut world);
+        // Other systems running in between advance the change tick as well.
+        world.increment_change_tick();
+        world.increment_change_tick();
+        system.run((), &mut world);
+
+        assert_eq!(world.resource::<Deltas>().0, [Tick::MAX.get(), 1, 3]);
+    }
+
+    #[test]
+    fn res_or_skip_system_skipped() {
+        #[derive(Resource, Default)]
+        struct Counter(usize);
+
+        #[derive(Resource)]
+        struct Increment(usize);
+
+        fn count(increment: ResOrSkip<Increment>, mut counter: ResMut<Counter>) {
+            counter.0 += increment.0;
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(count);
+
+        // Only warns on the first run, but skips the system on both.
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 0);
+
+        world.insert_resource(Increment(3));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 3);
+    }
+
+    #[test]
+    fn res_exists_system() {
+        #[derive(Resource, Default)]
+        struct Seen(Vec<bool>);
+
+        #[derive(Resource)]
+        struct Flag;
+
+        fn record(flag: ResExists<Flag>, mut seen: ResMut<Seen>) {
+            seen.0.push(*flag);
+        }
+
+        fn insert(mut commands: Commands) {
+            commands.insert_resource(Flag);
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Seen>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((record, insert, apply_deferred, record).chain());
+
+        // The resource is inserted between the two runs of `record`.
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [false, true]);
+
+        world.remove_resource::<Flag>();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [false, true, false, true]);
+    }
+
+    #[test]
+    fn res_exists_does_not_conflict_with_res_mut() {
+        #[derive(Resource)]
+        struct Flag(bool);
+
+        fn sys(flag: ResExists<Flag>, mut value: ResMut<Flag>) {
+            value.0 = *flag;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Flag(false));
+        run_system(&mut world, sys);
+        assert!(world.resource::<Flag>().0);
+    }
+
+    #[test]
+    #[should_panic = "error[B0002]"]
+    fn res_or_skip_conflicts_with_res_mut() {
+        #[derive(Resource)]
+        struct Increment(usize);
+
+        fn sys(_: ResOrSkip<Increment>, _: ResMut<Increment>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    fn res_init_system() {
+        #[derive(Resource)]
+        struct Step(usize);
+
+        impl Default for Step {
+            fn default() -> Self {
+                Step(2)
+            }
+        }
+
+        #[derive(Resource, Default)]
+        struct Counter(usize);
+
+        fn count(step: ResInit<Step>, mut counter: ResMutInit<Counter>) {
+            counter.0 += step.0;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Counter(1));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(count);
+
+        // Only the missing resource is inserted, the existing one is kept.
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 5);
+        assert_eq!(world.resource::<Step>().0, 2);
+    }
+
+    #[test]
+    #[should_panic = "error[B0002]"]
+    fn res_mut_init_conflicts_with_res() {
+        #[derive(Resource, Default)]
+        struct Counter(usize);
+
+        fn sys(_: Res<Counter>, _: ResMutInit<Counter>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    fn optional_single_system() {
+        #[derive(Resource, Default)]
+        struct Found(Vec<Option<usize>>);
+
+        fn find(single: Option<Single<&W<usize>>>, mut found: ResMut<Found>) {
+            found.0.push(single.map(|single| single.0));
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Found>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(find);
+
+        schedule.run(&mut world);
+        world.spawn(W(1usize));
+        schedule.run(&mut world);
+        world.spawn(W(2usize));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Found>().0, vec![None, Some(1), None]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_single_with_optional_single_system() {
+        fn sys(_single: Single<&mut W<usize>>, _optional: Option<Single<&W<usize>>>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_query_with_max_query_set_system() {
+        #[allow(clippy::type_complexity)]
+        fn sys(
+            _query: Query<&mut B>,
+            _set: ParamSet<(
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&A>,
+                Query<&B>,
+            )>,
+        ) {
+        }
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
     #[test]
     #[should_panic]
     fn conflicting_query_with_query_set_system() {
@@ -1022,6 +1667,163 @@ mod tests {
         run_system(&mut world, sys);
     }
 
+    #[test]
+    fn nested_query_set_system() {
+        // The members of the outer set are never accessed at the same time, so the inner set
+        // may conflict with its sibling.
+        fn sys(mut set: ParamSet<(Query<&mut A>, ParamSet<(Query<&A>, Query<&mut B>)>)>) {
+            let _ = set.p0();
+            let _ = set.p1().p1();
+        }
+
+        let mut world = World::default();
+        let mut system = IntoSystem::into_system(sys);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        // The access of the inner members is added to the system.
+        let b_id = world.component_id::<B>().unwrap();
+        assert!(system.component_access().has_write(b_id));
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_query_with_nested_query_set_system() {
+        fn sys(_query: Query<&mut A>, _set: ParamSet<(Query<&B>, ParamSet<(Query<&A>,)>)>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_nested_query_set_system() {
+        fn sys(_set: ParamSet<(Query<&B>, ParamSet<(Query<&mut A>,)>)>, _query: Query<&A>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_nested_query_set_within_member_system() {
+        // The inner set and the query are part of the same member, so they are accessed at
+        // the same time.
+        #[allow(clippy::type_complexity)]
+        fn sys(_set: ParamSet<((Query<&mut A>, ParamSet<(Query<&A>,)>),)>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    fn maybe_conflicting_query_system() {
+        fn sys(_query: Query<&mut A>, maybe: Maybe<Query<(&A, &B)>>) -> bool {
+            maybe.is_none()
+        }
+
+        let mut world = World::default();
+        world.spawn(A);
+        let mut system = IntoSystem::into_system(sys);
+        system.initialize(&mut world);
+        assert!(system.run((), &mut world));
+
+        // None of the access of the conflicting query is registered.
+        let b_id = world.component_id::<B>().unwrap();
+        assert!(!system.component_access().has_read(b_id));
+    }
+
+    #[test]
+    fn maybe_invalid_resource_system() {
+        #[derive(Resource)]
+        struct Counter(usize);
+
+        fn sys(mut counter: Maybe<ResMut<Counter>>) {
+            if let Some(counter) = counter.as_mut() {
+                counter.0 += 1;
+            }
+        }
+
+        let mut world = World::default();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(sys);
+
+        // The system runs without the resource, instead of being skipped.
+        schedule.run(&mut world);
+        world.insert_resource(Counter(0));
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn entity_stats_system() {
+        #[derive(Resource, Default)]
+        struct Seen(Option<EntityStats>);
+
+        fn sys(stats: EntityStats, mut seen: ResMut<Seen>) {
+            seen.0 = Some(stats);
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Seen>();
+        let a = world.spawn(A).id();
+        world.spawn((A, B));
+        world.despawn(a);
+        world.entities().reserve_entity();
+        world.entities().reserve_entity();
+
+        let mut system = IntoSystem::into_system(sys);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        let stats = world.resource::<Seen>().0.unwrap();
+        assert_eq!(stats.entities, 1);
+        assert_eq!(stats.total, 2);
+        // The freed slot of `a` was reserved again, along with a new one.
+        assert_eq!(stats.free, 0);
+        assert_eq!(stats.reserved, 2);
+        assert_eq!(stats.archetypes, world.archetypes().len());
+    }
+
+    #[test]
+    fn component_registry_system() {
+        #[derive(Resource)]
+        struct R;
+
+        fn sys(registry: ComponentRegistry) {
+            let a = registry.component_id::<A>().unwrap();
+            assert!(registry.is::<A>(a));
+            assert!(!registry.is::<B>(a));
+            assert_eq!(registry.type_id_of(a), Some(std::any::TypeId::of::<A>()));
+            assert_eq!(registry.name_of(a), Some(std::any::type_name::<A>()));
+            assert_eq!(registry.info::<A>().unwrap().id(), a);
+            assert_eq!(registry.id_of(std::any::TypeId::of::<A>()), Some(a));
+
+            let r = registry.resource_id::<R>().unwrap();
+            assert!(registry.is::<R>(r));
+            assert_eq!(registry.id_of(std::any::TypeId::of::<R>()), Some(r));
+
+            assert!(registry.info::<C>().is_none());
+        }
+
+        let mut world = World::default();
+        world.spawn(A);
+        world.insert_resource(R);
+        assert_is_read_only_system(sys);
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_query_after_maybe_system() {
+        // Only the parameters before `Maybe` make it `None`.
+        fn sys(_maybe: Maybe<Query<&mut A>>, _query: Query<&A>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
     #[derive(Default, Resource)]
     struct BufferRes {
         _buffer: Vec<u8>,
@@ -1142,6 +1944,34 @@ mod tests {
         assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
     }
 
+    #[test]
+    fn non_send_readers_do_not_conflict() {
+        struct NotSend(std::rc::Rc<i32>);
+
+        fn read_a(_: NonSend<NotSend>) {}
+        fn read_b(_: NonSend<NotSend>, _: Option<NonSend<NotSend>>) {}
+        fn write(_: NonSendMut<NotSend>) {}
+
+        let mut world = World::default();
+        world.insert_non_send_resource(NotSend(std::rc::Rc::new(0)));
+        let mut read_a = IntoSystem::into_system(read_a);
+        let mut read_b = IntoSystem::into_system(read_b);
+        let mut write = IntoSystem::into_system(write);
+        read_a.initialize(&mut world);
+        read_b.initialize(&mut world);
+        write.initialize(&mut world);
+
+        // All of them run on the main thread.
+        assert!(!read_a.is_send() && !read_b.is_send() && !write.is_send());
+        assert!(read_a
+            .component_access()
+            .is_compatible(read_b.component_access()));
+        assert!(!read_a
+            .component_access()
+            .is_compatible(write.component_access()));
+        assert_is_read_only_system(read_b);
+    }
+
     #[test]
     fn removal_tracking() {
         let mut world = World::new();
@@ -1654,6 +2484,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn system_meta_access() {
+        fn sys(_: Query<&A, Without<B>>, _: ResMut<SystemRan>) {}
+
+        let mut world = World::default();
+        let mut system = IntoSystem::into_system(sys);
+        system.initialize(&mut world);
+
+        let a_id = world.components().component_id::<A>().unwrap();
+        let b_id = world.components().component_id::<B>().unwrap();
+        let ran_id = world.components().resource_id::<SystemRan>().unwrap();
+
+        let access_set = system.system_meta().component_access_set();
+        let [query, res_mut] = access_set.filtered_accesses() else {
+            panic!("expected one access per parameter");
+        };
+        assert_eq!(query.access().reads().collect::<Vec<_>>(), vec![a_id]);
+        assert!(!query.access().has_any_write());
+        assert_eq!(res_mut.access().writes().collect::<Vec<_>>(), vec![ran_id]);
+        assert!(!access_set.combined_access().has_read(b_id));
+        assert_eq!(access_set.combined_access().reads_and_writes().count(), 2);
+    }
+
     #[test]
     fn update_archetype_component_access_works() {
         use std::collections::HashSet;
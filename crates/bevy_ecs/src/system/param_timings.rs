@@ -0,0 +1,161 @@
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy_utils::{Duration, HashMap};
+
+use crate as bevy_ecs;
+use crate::system::Resource;
+
+/// The time spent in the [`SystemParam`](super::SystemParam) methods of a parameter of a
+/// system, see [`SystemParamTimings`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemParamTiming {
+    /// The total time spent in [`get_param`](super::SystemParam::get_param).
+    pub get_param: Duration,
+    /// The number of calls to [`get_param`](super::SystemParam::get_param).
+    pub get_param_calls: u32,
+    /// The total time spent in [`new_archetype`](super::SystemParam::new_archetype).
+    pub new_archetype: Duration,
+    /// The number of calls to [`new_archetype`](super::SystemParam::new_archetype).
+    pub new_archetype_calls: u32,
+}
+
+/// The time spent fetching each parameter of each system, only available with the
+/// `param_timings` feature.
+///
+/// The resource is inserted when the first system is initialized, and all systems of the
+/// [`World`](crate::world::World) record their timings into it as they run, by the
+/// [`name`](super::SystemMeta::name) of the system and the type name of the parameter. Systems
+/// can run in parallel, so the timings are shared behind a lock, and this resource can be
+/// read while they run, e.g. to show them in an overlay.
+///
+/// The parameters of a system are timed individually. Parameters made of other parameters,
+/// like tuples and the parameters derived with [`SystemParam`](super::SystemParam), are
+/// recorded along with each of their fields, so the timings of nested parameters overlap.
+///
+/// Timings accumulate until they are [cleared](SystemParamTimings::clear), e.g. once per frame.
+#[derive(Resource, Clone, Default)]
+pub struct SystemParamTimings {
+    timings: Arc<Mutex<HashMap<(Cow<'static, str>, &'static str), SystemParamTiming>>>,
+}
+
+impl SystemParamTimings {
+    /// Returns the timing of the parameter of type `param` of the system named `system`, if it
+    /// was recorded.
+    pub fn get(&self, system: &str, param: &str) -> Option<SystemParamTiming> {
+        self.lock()
+            .iter()
+            .find(|((system_name, param_name), _)| system_name == system && *param_name == param)
+            .map(|(_, timing)| *timing)
+    }
+
+    /// Returns the system name, the parameter type name and the timing of every recorded
+    /// parameter.
+    pub fn snapshot(&self) -> Vec<(Cow<'static, str>, &'static str, SystemParamTiming)> {
+        self.lock()
+            .iter()
+            .map(|((system, param), timing)| (system.clone(), *param, *timing))
+            .collect()
+    }
+
+    /// Discards all recorded timings.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub(crate) fn record_get_param(
+        &self,
+        system: &Cow<'static, str>,
+        param: &'static str,
+        elapsed: Duration,
+    ) {
+        let mut timings = self.lock();
+        let timing = timings.entry((system.clone(), param)).or_default();
+        timing.get_param += elapsed;
+        timing.get_param_calls += 1;
+    }
+
+    pub(crate) fn record_new_archetype(
+        &self,
+        system: &Cow<'static, str>,
+        param: &'static str,
+        elapsed: Duration,
+    ) {
+        let mut timings = self.lock();
+        let timing = timings.entry((system.clone(), param)).or_default();
+        timing.new_archetype += elapsed;
+        timing.new_archetype_calls += 1;
+    }
+
+    fn lock(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<(Cow<'static, str>, &'static str), SystemParamTiming>>
+    {
+        // A panicking system doesn't leave the timings in an inconsistent state.
+        self.timings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SystemParamTimings;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        system::{IntoSystem, ParamBuilder, Query, Res, Resource, System, SystemParamBuilder},
+        world::World,
+    };
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Resource)]
+    struct R;
+
+    #[test]
+    fn system_param_timings() {
+        fn sys(_query: Query<&A>, _res: Res<R>) {}
+
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut system = IntoSystem::into_system(sys);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        system.run((), &mut world);
+
+        let timings = world.resource::<SystemParamTimings>();
+        let name = system.name();
+        let query = timings
+            .get(&name, std::any::type_name::<Query<&A>>())
+            .unwrap();
+        assert_eq!(query.get_param_calls, 2);
+        let res = timings.get(&name, std::any::type_name::<Res<R>>()).unwrap();
+        assert_eq!(res.get_param_calls, 2);
+
+        timings.clear();
+        assert!(timings.snapshot().is_empty());
+    }
+
+    #[test]
+    fn built_system_param_timings() {
+        fn sys(_query: Query<&A>, _res: Res<R>) {}
+
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut system = (ParamBuilder, ParamBuilder)
+            .build_state(&mut world)
+            .build_system(sys);
+        system.run((), &mut world);
+
+        let timings = world.resource::<SystemParamTimings>();
+        let name = system.name();
+        let query = timings
+            .get(&name, std::any::type_name::<Query<&A>>())
+            .unwrap();
+        assert_eq!(query.get_param_calls, 1);
+        let res = timings.get(&name, std::any::type_name::<Res<R>>()).unwrap();
+        assert_eq!(res.get_param_calls, 1);
+    }
+}
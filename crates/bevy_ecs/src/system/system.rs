@@ -69,6 +69,33 @@ pub trait System: Send + Sync + 'static {
         unsafe { self.run_unsafe(input, world) }
     }
 
+    /// Checks whether all of the system's parameters can currently be acquired, see
+    /// [`SystemParam::validate_param`](crate::system::SystemParam::validate_param).
+    ///
+    /// Executors skip systems for which this returns `false` instead of running them.
+    ///
+    /// # Safety
+    ///
+    /// - The caller must ensure that `world` has permission to read any world data
+    ///   registered in [`Self::archetype_component_access`]. There must be no conflicting
+    ///   simultaneous mutable accesses while the parameters are validated.
+    /// - The method [`Self::update_archetype_component_access`] must be called at some
+    ///   point before this one, with the same exact [`World`]. If `update_archetype_component_access`
+    ///   panics (or otherwise does not return for any reason), this method must not be called.
+    unsafe fn validate_param_unsafe(&self, world: UnsafeWorldCell) -> bool;
+
+    /// Checks whether all of the system's parameters can currently be acquired.
+    ///
+    /// This is the safe version of [`System::validate_param_unsafe`].
+    fn validate_param(&mut self, world: &World) -> bool {
+        let world = world.as_unsafe_world_cell_readonly();
+        self.update_archetype_component_access(world);
+        // SAFETY:
+        // - We have read-only access to the entire world.
+        // - `update_archetype_component_access` has been called.
+        unsafe { self.validate_param_unsafe(world) }
+    }
+
     /// Applies any [`Deferred`](crate::system::Deferred) system parameters (or other system buffers) of this system to the world.
     ///
     /// This is where [`Commands`](crate::system::Commands) get applied.
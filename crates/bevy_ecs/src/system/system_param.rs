@@ -1,26 +1,29 @@
 pub use crate::change_detection::{NonSendMut, Res, ResMut};
 use crate::{
-    archetype::{Archetype, Archetypes},
+    archetype::{Archetype, ArchetypeId, Archetypes},
     bundle::Bundles,
     change_detection::{Ticks, TicksMut},
-    component::{ComponentId, ComponentTicks, Components, Tick},
-    entity::Entities,
+    component::{Component, ComponentId, ComponentInfo, ComponentTicks, Components, Tick},
+    entity::{Entities, Entity},
     query::{
-        Access, FilteredAccess, FilteredAccessSet, QueryState, ReadOnlyWorldQuery, WorldQuery,
+        Access, FilteredAccess, FilteredAccessSet, QueryIter, QueryState, ROQueryItem,
+        ReadOnlyWorldQuery, WorldQuery,
     },
-    system::{Query, SystemMeta},
-    world::{unsafe_world_cell::UnsafeWorldCell, FromWorld, World},
+    system::{Query, SystemInstanceId, SystemMeta},
+    world::{unsafe_world_cell::UnsafeWorldCell, FilteredEntityRef, FromWorld, World},
 };
 use bevy_ecs_macros::impl_param_set;
 pub use bevy_ecs_macros::Resource;
 pub use bevy_ecs_macros::SystemParam;
 use bevy_ptr::UnsafeCellDeref;
-use bevy_utils::{all_tuples, synccell::SyncCell};
+use bevy_utils::{all_tuples, synccell::SyncCell, tracing::warn};
 use std::{
+    any::{Any, TypeId},
     borrow::Cow,
     fmt::Debug,
     marker::PhantomData,
     ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 /// A parameter that can be used in a [`System`](super::System).
@@ -63,6 +66,109 @@ use std::{
 /// # bevy_ecs::system::assert_is_system(my_system::<()>);
 /// ```
 ///
+/// ## Named `ParamSet` parameters
+///
+/// A [`ParamSet`] field can be given the `#[system_param(param_set(...))]` attribute, with one
+/// name per parameter of the set. This generates a method of that name for each parameter,
+/// which accesses it like the corresponding `p0()`, `p1()`, ... method of the set.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Component)]
+/// # struct Health(f32);
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// # #[derive(Component)]
+/// # struct Ally;
+/// use bevy_ecs::system::SystemParam;
+///
+/// #[derive(SystemParam)]
+/// struct Combatants<'w, 's> {
+///     #[system_param(param_set(enemies, allies))]
+///     set: ParamSet<
+///         'w,
+///         's,
+///         (
+///             Query<'w, 's, &'static mut Health, With<Enemy>>,
+///             Query<'w, 's, &'static mut Health, With<Ally>>,
+///         ),
+///     >,
+/// }
+///
+/// fn heal_allies(mut combatants: Combatants) {
+///     for mut health in &mut combatants.allies() {
+///         health.0 += 1.0;
+///     }
+/// }
+///
+/// # bevy_ecs::system::assert_is_system(heal_allies);
+/// ```
+///
+/// ## Ignored fields
+///
+/// Fields that aren't [`SystemParam`]s can be given the `#[system_param(ignore)]` attribute.
+/// They don't access the [`World`] and have no state, and are set to their [`Default`] value
+/// every time the parameter is fetched. Generic type parameters must still be used by a field
+/// that isn't ignored, see [`PhantomData`](#phantomdata).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Resource)]
+/// # struct Score(u32);
+/// use bevy_ecs::system::SystemParam;
+///
+/// #[derive(SystemParam)]
+/// struct ScoreParam<'w> {
+///     score: Res<'w, Score>,
+///     #[system_param(ignore)]
+///     bonus: u32,
+/// }
+///
+/// fn print_score(param: ScoreParam) {
+///     println!("{}", param.score.0 + param.bonus);
+/// }
+///
+/// # bevy_ecs::system::assert_is_system(print_score);
+/// ```
+///
+/// ## Builders
+///
+/// With the `#[system_param(builder)]` attribute on the struct, the derive also generates a
+/// [`SystemParamBuilder`](super::SystemParamBuilder) named after the struct with a `Builder`
+/// suffix. It has a field for each field of the struct that isn't ignored, holding the builder
+/// used for the state of that field. Fields that don't need anything special can use
+/// [`ParamBuilder`](super::ParamBuilder) to be initialized as usual.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Resource, Default)]
+/// # struct Total(u64);
+/// use bevy_ecs::system::{LocalBuilder, ParamBuilder, SystemParam, SystemParamBuilder};
+///
+/// #[derive(SystemParam)]
+/// #[system_param(builder)]
+/// struct Counter<'w, 's> {
+///     count: Local<'s, u64>,
+///     total: ResMut<'w, Total>,
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Total>();
+///
+/// let mut system = (CounterBuilder {
+///     count: LocalBuilder(10),
+///     total: ParamBuilder,
+/// },)
+///     .build_state(&mut world)
+///     .build_system(|mut counter: Counter| {
+///         *counter.count += 1;
+///         counter.total.0 += *counter.count;
+///     });
+///
+/// system.run((), &mut world);
+/// assert_eq!(world.resource::<Total>().0, 11);
+/// ```
+///
 /// # Generic `SystemParam`s
 ///
 /// When using the derive macro, you may see an error in the form of:
@@ -125,6 +231,40 @@ pub unsafe trait SystemParam: Sized {
     #[allow(unused_variables)]
     fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {}
 
+    /// Checks whether the parameter can currently be acquired by [`get_param`](SystemParam::get_param).
+    ///
+    /// The executors call this before running a system, and skip the system for that run if
+    /// any of its parameters returns `false`. This allows parameters like [`Single`] to skip
+    /// systems instead of panicking in [`get_param`](SystemParam::get_param), and makes
+    /// [`Res`], [`ResMut`], [`NonSend`] and [`NonSendMut`] skip systems whose resource
+    /// doesn't exist. A run condition with invalid parameters is considered not met.
+    /// Running a system directly with [`System::run`](super::System::run) doesn't validate its
+    /// parameters. The default implementation always returns `true`.
+    ///
+    /// Validation only reads the world, and happens right before the system runs, with no
+    /// other systems running in between that could invalidate the result. It must not have
+    /// side effects, since a system can be validated without being run afterwards.
+    /// Returning `true` doesn't make [`get_param`](SystemParam::get_param) infallible: it
+    /// may still panic on parameters that can't be acquired.
+    ///
+    /// # Safety
+    ///
+    /// - The passed [`UnsafeWorldCell`] must have read-only access to any world data
+    ///   registered in [`init_state`](SystemParam::init_state).
+    /// - `world` must be the same `World` that was used to initialize [`state`](SystemParam::init_state).
+    /// - [`new_archetype`](SystemParam::new_archetype) must have been called for all archetypes
+    ///   of `world` beforehand, as [`System::validate_param`](super::System::validate_param)
+    ///   does through [`System::update_archetype_component_access`](super::System::update_archetype_component_access).
+    #[inline]
+    #[allow(unused_variables)]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        true
+    }
+
     /// Creates a parameter to be passed into a [`SystemParamFunction`].
     ///
     /// [`SystemParamFunction`]: super::SystemParamFunction
@@ -167,20 +307,7 @@ unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemPara
 
     fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
         let state = QueryState::new(world);
-        assert_component_access_compatibility(
-            &system_meta.name,
-            std::any::type_name::<Q>(),
-            std::any::type_name::<F>(),
-            &system_meta.component_access_set,
-            &state.component_access,
-            world,
-        );
-        system_meta
-            .component_access_set
-            .add(state.component_access.clone());
-        system_meta
-            .archetype_component_access
-            .extend(&state.archetype_component_access);
+        init_query_param(world, system_meta, &state);
         state
     }
 
@@ -205,6 +332,29 @@ unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemPara
     }
 }
 
+/// Registers the accesses of a [`Query`] parameter with the given state in `system_meta`,
+/// panicking if they conflict with the accesses of previous parameters.
+pub(crate) fn init_query_param<Q: WorldQuery, F: ReadOnlyWorldQuery>(
+    world: &World,
+    system_meta: &mut SystemMeta,
+    state: &QueryState<Q, F>,
+) {
+    assert_component_access_compatibility(
+        &system_meta.name,
+        std::any::type_name::<Q>(),
+        std::any::type_name::<F>(),
+        &system_meta.component_access_set,
+        &state.component_access,
+        world,
+    );
+    system_meta
+        .component_access_set
+        .add(state.component_access.clone());
+    system_meta
+        .archetype_component_access
+        .extend(&state.archetype_component_access);
+}
+
 fn assert_component_access_compatibility(
     system_name: &str,
     query_type: &'static str,
@@ -225,95 +375,700 @@ fn assert_component_access_compatibility(
     panic!("error[B0001]: Query<{query_type}, {filter_type}> in system {system_name} accesses component(s) {accesses} in a way that conflicts with a previous system parameter. Consider using `Without<T>` to create disjoint Queries or merging conflicting Queries into a `ParamSet`.");
 }
 
-/// A collection of potentially conflicting [`SystemParam`]s allowed by disjoint access.
+/// A [`SystemParam`] that fetches the single item matched by a [`Query`].
 ///
-/// Allows systems to safely access and interact with up to 8 mutually exclusive [`SystemParam`]s, such as
-/// two queries that reference the same mutable data or an event reader and writer of the same type.
+/// This dereferences to the query item, like [`Query::single`] or [`Query::single_mut`].
+/// Unlike those methods, the system doesn't panic if the number of matching entities isn't
+/// exactly one: the executors [validate](SystemParam::validate_param) the parameter and skip
+/// the system for that run instead.
 ///
-/// Each individual [`SystemParam`] can be accessed by using the functions `p0()`, `p1()`, ..., `p7()`,
-/// according to the order they are defined in the `ParamSet`. This ensures that there's either
-/// only one mutable reference to a parameter at a time or any number of immutable references.
+/// Running a system directly with [`System::run`](super::System::run) doesn't validate its
+/// parameters, so a `Single` panics there if the query doesn't match exactly one entity.
 ///
-/// # Examples
+/// Use `Option<Single<Q, F>>` to run the system regardless, with `None` unless exactly one
+/// entity matches.
 ///
-/// The following system mutably accesses the same component two times,
-/// which is not allowed due to rust's mutability rules.
+/// # Example
 ///
-/// ```should_panic
+/// ```
 /// # use bevy_ecs::prelude::*;
-/// #
 /// # #[derive(Component)]
-/// # struct Health;
-/// #
-/// # #[derive(Component)]
-/// # struct Enemy;
-/// #
+/// # struct Health(f32);
 /// # #[derive(Component)]
-/// # struct Ally;
-/// #
-/// // This will panic at runtime when the system gets initialized.
-/// fn bad_system(
-///     mut enemies: Query<&mut Health, With<Enemy>>,
-///     mut allies: Query<&mut Health, With<Ally>>,
-/// ) {
-///     // ...
+/// # struct Player;
+/// fn heal_player(mut player: Single<&mut Health, With<Player>>) {
+///     // Only runs while there is exactly one player.
+///     player.0 += 1.0;
 /// }
-/// #
-/// # let mut bad_system_system = bevy_ecs::system::IntoSystem::into_system(bad_system);
-/// # let mut world = World::new();
-/// # bad_system_system.initialize(&mut world);
-/// # bad_system_system.run((), &mut world);
+/// # bevy_ecs::system::assert_is_system(heal_player);
 /// ```
+pub struct Single<'w, Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
+    item: Q::Item<'w>,
+    _filter: PhantomData<F>,
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> Deref for Single<'w, Q, F> {
+    type Target = Q::Item<'w>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> DerefMut for Single<'w, Q, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> Single<'w, Q, F> {
+    /// Returns the inner item with ownership.
+    pub fn into_inner(self) -> Q::Item<'w> {
+        self.item
+    }
+}
+
+// SAFETY: QueryState is constrained to read-only fetches, so it only reads World.
+unsafe impl<'w, Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static>
+    ReadOnlySystemParam for Single<'w, Q, F>
+{
+}
+
+// SAFETY: Relevant query ComponentId and ArchetypeComponentId access is applied to SystemMeta by
+// the `Query` implementation. If this conflicts with any prior access, a panic will occur.
+unsafe impl<'a, Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemParam
+    for Single<'a, Q, F>
+{
+    type State = QueryState<Q, F>;
+    type Item<'w, 's> = Single<'w, Q, F>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Query::<Q, F>::init_state(world, system_meta)
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        Query::<Q, F>::new_archetype(state, archetype, system_meta);
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: The read-only state only reads the data registered in `init_state`, which the
+        // caller ensures `world` has permission to read.
+        state
+            .as_readonly()
+            .get_single_unchecked_manual(world, system_meta.last_run, world.change_tick())
+            .is_ok()
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: We have registered all of the query's world accesses,
+        // so the caller ensures that `world` has permission to access any
+        // world data that the query needs.
+        let result = state.get_single_unchecked_manual(world, system_meta.last_run, change_tick);
+        let item = result.unwrap_or_else(|err| {
+            panic!(
+                "`Single` in system {} requires exactly one matching entity: {err}",
+                system_meta.name
+            )
+        });
+        Single {
+            item,
+            _filter: PhantomData,
+        }
+    }
+}
+
+// SAFETY: QueryState is constrained to read-only fetches, so it only reads World.
+unsafe impl<'w, Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static>
+    ReadOnlySystemParam for Option<Single<'w, Q, F>>
+{
+}
+
+// SAFETY: The same accesses as for `Single` are registered.
+unsafe impl<'a, Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemParam
+    for Option<Single<'a, Q, F>>
+{
+    type State = QueryState<Q, F>;
+    type Item<'w, 's> = Option<Single<'w, Q, F>>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Single::<Q, F>::init_state(world, system_meta)
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        Single::<Q, F>::new_archetype(state, archetype, system_meta);
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: We have registered all of the query's world accesses,
+        // so the caller ensures that `world` has permission to access any
+        // world data that the query needs.
+        let result = state.get_single_unchecked_manual(world, system_meta.last_run, change_tick);
+        result.ok().map(|item| Single {
+            item,
+            _filter: PhantomData,
+        })
+    }
+}
+
+/// A [`SystemParam`] that reads the components of a specific entity, given by an
+/// [`EntityRefParamBuilder`](super::EntityRefParamBuilder).
 ///
-/// Conflicting `SystemParam`s like these can be placed in a `ParamSet`,
-/// which leverages the borrow checker to ensure that only one of the contained parameters are accessed at a given time.
+/// This dereferences to a [`FilteredEntityRef`] of the entity, which can only read the
+/// components added to the builder. They are registered like the components of a
+/// `Query<FilteredEntityRef>`, so conflicts with the other parameters of the system are
+/// detected the same way.
+///
+/// The executors [validate](SystemParam::validate_param) the parameter and skip the system for
+/// that run while the entity doesn't exist or doesn't have the required components. Without a
+/// builder, the parameter doesn't refer to any entity, so the system is always skipped.
+///
+/// # Example
 ///
 /// ```
-/// # use bevy_ecs::prelude::*;
-/// #
-/// # #[derive(Component)]
-/// # struct Health;
+/// # use bevy_ecs::{
+/// #     prelude::*,
+/// #     system::{EntityRefParam, EntityRefParamBuilder, SystemParamBuilder},
+/// # };
 /// #
 /// # #[derive(Component)]
-/// # struct Enemy;
-/// #
-/// # #[derive(Component)]
-/// # struct Ally;
+/// # struct Health(u32);
 /// #
-/// // Given the following system
-/// fn fancy_system(
-///     mut set: ParamSet<(
-///         Query<&mut Health, With<Enemy>>,
-///         Query<&mut Health, With<Ally>>,
-///     )>
-/// ) {
-///     // This will access the first `SystemParam`.
-///     for mut health in set.p0().iter_mut() {
-///         // Do your fancy stuff here...
-///     }
+/// let mut world = World::new();
+/// let focused = world.spawn(Health(10)).id();
+/// let health = world.init_component::<Health>();
 ///
-///     // The second `SystemParam`.
-///     // This would fail to compile if the previous parameter was still borrowed.
-///     for mut health in set.p1().iter_mut() {
-///         // Do even fancier stuff here...
-///     }
-/// }
-/// # bevy_ecs::system::assert_is_system(fancy_system);
+/// let mut system = (EntityRefParamBuilder::new(focused).ref_id(health),)
+///     .build_state(&mut world)
+///     .build_system(|entity: EntityRefParam| {
+///         assert_eq!(entity.get::<Health>().unwrap().0, 10);
+///     });
+///
+/// system.run((), &mut world);
 /// ```
+pub struct EntityRefParam<'w>(FilteredEntityRef<'w>);
+
+impl<'w> Deref for EntityRefParam<'w> {
+    type Target = FilteredEntityRef<'w>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'w> EntityRefParam<'w> {
+    /// Returns the inner [`FilteredEntityRef`] with ownership.
+    pub fn into_inner(self) -> FilteredEntityRef<'w> {
+        self.0
+    }
+}
+
+// SAFETY: QueryState is constrained to read-only fetches, so it only reads World.
+unsafe impl ReadOnlySystemParam for EntityRefParam<'_> {}
+
+// SAFETY: Relevant query ComponentId and ArchetypeComponentId access is applied to SystemMeta by
+// the builder, like the `Query` implementation does. `init_state` registers no access.
+unsafe impl SystemParam for EntityRefParam<'_> {
+    type State = (Entity, QueryState<FilteredEntityRef<'static>>);
+    type Item<'w, 's> = EntityRefParam<'w>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let state = Query::<FilteredEntityRef<'static>>::init_state(world, system_meta);
+        (Entity::PLACEHOLDER, state)
+    }
+
+    fn new_archetype(
+        (_, state): &mut Self::State,
+        archetype: &Archetype,
+        system_meta: &mut SystemMeta,
+    ) {
+        Query::<FilteredEntityRef<'static>>::new_archetype(state, archetype, system_meta);
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        (entity, state): &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: The read-only state only reads the data registered by the builder, which the
+        // caller ensures `world` has permission to read.
+        state
+            .get_unchecked_manual(world, *entity, system_meta.last_run, world.change_tick())
+            .is_ok()
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        (entity, state): &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: We have registered all of the query's world accesses,
+        // so the caller ensures that `world` has permission to access any
+        // world data that the query needs.
+        let result = state.get_unchecked_manual(world, *entity, system_meta.last_run, change_tick);
+        let item = result.unwrap_or_else(|err| {
+            panic!(
+                "`EntityRefParam` in system {} requires a matching entity: {err}",
+                system_meta.name
+            )
+        });
+        EntityRefParam(item)
+    }
+}
+
+/// A [`SystemParam`] that works like a [`Query`], but only runs the system if the query
+/// matches at least one entity.
 ///
-/// Of course, `ParamSet`s can be used with any kind of `SystemParam`, not just [queries](Query).
+/// This dereferences to the inner [`Query`]. The executors [validate](SystemParam::validate_param)
+/// the parameter and skip the system for that run while no entity matches, which replaces an
+/// early `if query.is_empty() { return; }` in the system.
+///
+/// Running a system directly with [`System::run`](super::System::run) doesn't validate its
+/// parameters, so the query may be empty there.
+///
+/// # Example
 ///
 /// ```
 /// # use bevy_ecs::prelude::*;
-/// #
-/// # #[derive(Event)]
-/// # struct MyEvent;
-/// # impl MyEvent {
-/// #   pub fn new() -> Self { Self }
-/// # }
-/// fn event_system(
-///     mut set: ParamSet<(
-///         // `EventReader`s and `EventWriter`s conflict with each other,
+/// # #[derive(Component)]
+/// # struct Health(f32);
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// fn heal_enemies(mut enemies: Populated<&mut Health, With<Enemy>>) {
+///     // Only runs while there is at least one enemy.
+///     for mut health in &mut enemies {
+///         health.0 += 1.0;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(heal_enemies);
+/// ```
+pub struct Populated<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery = ()>(Query<'w, 's, Q, F>);
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Deref for Populated<'w, 's, Q, F> {
+    type Target = Query<'w, 's, Q, F>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> DerefMut for Populated<'w, 's, Q, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Populated<'w, 's, Q, F> {
+    /// Returns the inner query with ownership.
+    pub fn into_inner(self) -> Query<'w, 's, Q, F> {
+        self.0
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> IntoIterator for &'w Populated<'_, 's, Q, F> {
+    type Item = ROQueryItem<'w, Q>;
+    type IntoIter = QueryIter<'w, 's, Q::ReadOnly, F::ReadOnly>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> IntoIterator
+    for &'w mut Populated<'_, 's, Q, F>
+{
+    type Item = Q::Item<'w>;
+    type IntoIter = QueryIter<'w, 's, Q, F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+// SAFETY: QueryState is constrained to read-only fetches, so it only reads World.
+unsafe impl<'w, 's, Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static>
+    ReadOnlySystemParam for Populated<'w, 's, Q, F>
+{
+}
+
+// SAFETY: Relevant query ComponentId and ArchetypeComponentId access is applied to SystemMeta by
+// the `Query` implementation. If this conflicts with any prior access, a panic will occur.
+unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemParam
+    for Populated<'_, '_, Q, F>
+{
+    type State = QueryState<Q, F>;
+    type Item<'w, 's> = Populated<'w, 's, Q, F>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Query::<Q, F>::init_state(world, system_meta)
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        Query::<Q, F>::new_archetype(state, archetype, system_meta);
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Checking for emptiness only reads the data registered in `init_state`, which
+        // the caller ensures `world` has permission to read.
+        !state.is_empty_unsafe_world_cell(world, system_meta.last_run, world.change_tick())
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: We have registered all of the query's world accesses,
+        // so the caller ensures that `world` has permission to access any
+        // world data that the query needs.
+        Populated(Query::new(
+            world,
+            state,
+            system_meta.last_run,
+            change_tick,
+            false,
+        ))
+    }
+}
+
+/// A [`SystemParam`] that counts the entities matching the query filter `F`, without fetching
+/// any of their data.
+///
+/// Only the access needed by the filter is registered, so `EntityCount<With<T>>` doesn't
+/// conflict with any access to `T`, unlike `Query<&T>`.
+///
+/// # Performance
+///
+/// If the filter only depends on the components of the entities, like
+/// [`With`](crate::query::With), [`Without`](crate::query::Without) and [`Or`](crate::query::Or)
+/// of those, the count is the sum of the lengths of the matching archetypes. This takes time
+/// proportional to the number of matching archetypes, which is usually much less than the number
+/// of matching entities.
+///
+/// Filters like [`Added`](crate::query::Added) and [`Changed`](crate::query::Changed) depend on
+/// the change ticks of each entity, so the matching entities are iterated and counted instead,
+/// like [`Query::iter`] followed by [`Iterator::count`] would.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::EntityCount};
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// # #[derive(Resource)]
+/// # struct Hud { enemies: usize }
+/// fn update_hud(enemies: EntityCount<With<Enemy>>, mut hud: ResMut<Hud>) {
+///     hud.enemies = *enemies;
+/// }
+/// # bevy_ecs::system::assert_is_system(update_hud);
+/// ```
+pub struct EntityCount<F: ReadOnlyWorldQuery + 'static = ()> {
+    count: usize,
+    marker: PhantomData<fn() -> F>,
+}
+
+impl<F: ReadOnlyWorldQuery + 'static> EntityCount<F> {
+    /// Returns the number of entities matching the filter.
+    #[inline]
+    pub fn get(&self) -> usize {
+        self.count
+    }
+}
+
+impl<F: ReadOnlyWorldQuery + 'static> Deref for EntityCount<F> {
+    type Target = usize;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.count
+    }
+}
+
+impl<F: ReadOnlyWorldQuery + 'static> Debug for EntityCount<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EntityCount").field(&self.count).finish()
+    }
+}
+
+// SAFETY: QueryState is constrained to read-only fetches, so it only reads World.
+unsafe impl<F: ReadOnlyWorldQuery + 'static> ReadOnlySystemParam for EntityCount<F> {}
+
+// SAFETY: Relevant query ComponentId and ArchetypeComponentId access is applied to SystemMeta by
+// the `Query` implementation. The fetched `Entity` doesn't access the world.
+unsafe impl<F: ReadOnlyWorldQuery + 'static> SystemParam for EntityCount<F> {
+    type State = QueryState<Entity, F>;
+    type Item<'w, 's> = EntityCount<F>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Query::<Entity, F>::init_state(world, system_meta)
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        Query::<Entity, F>::new_archetype(state, archetype, system_meta);
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        let count = if F::IS_ARCHETYPAL {
+            // Every entity of a matching archetype matches the filter.
+            let archetypes = world.archetypes();
+            state
+                .matched_archetype_ids
+                .iter()
+                .map(|&id| archetypes[id].len())
+                .sum()
+        } else {
+            // SAFETY: We have registered all of the filter's world accesses,
+            // so the caller ensures that `world` has permission to access any
+            // world data that the filter needs.
+            state
+                .iter_unchecked_manual(world, system_meta.last_run, change_tick)
+                .count()
+        };
+        EntityCount {
+            count,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A [`SystemParam`] that provides the archetypes matched by a [`Query`] with the same
+/// parameters, without accessing any of their data.
+///
+/// This registers the same access as `Query<Q, F>`, and is backed by the same kind of
+/// [`QueryState`], so the archetypes are matched incrementally as they are created, in the order
+/// they were created in. Each [`Archetype`] gives its components and the number of entities in
+/// it, e.g. for an archetype inspector.
+///
+/// Filters that depend on the change ticks of each entity, like
+/// [`Changed`](crate::query::Changed), match whole archetypes, so all of the entities of the
+/// matched archetypes are counted, even those the filter would skip.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::MatchedArchetypes};
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// fn inspect_enemies(archetypes: MatchedArchetypes<Entity, With<Enemy>>) {
+///     for archetype in archetypes.iter() {
+///         println!("{:?}: {} entities", archetype.id(), archetype.len());
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_read_only_system(inspect_enemies);
+/// ```
+pub struct MatchedArchetypes<
+    'w,
+    's,
+    Q: ReadOnlyWorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static = (),
+> {
+    archetypes: &'w Archetypes,
+    ids: &'s [ArchetypeId],
+    marker: PhantomData<fn() -> (Q, F)>,
+}
+
+impl<'w, 's, Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static>
+    MatchedArchetypes<'w, 's, Q, F>
+{
+    /// Returns the ids of the matched archetypes.
+    #[inline]
+    pub fn ids(&self) -> &'s [ArchetypeId] {
+        self.ids
+    }
+
+    /// Returns an iterator over the matched archetypes.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'w Archetype> + '_ {
+        let archetypes = self.archetypes;
+        self.ids.iter().map(move |&id| &archetypes[id])
+    }
+
+    /// Returns the number of matched archetypes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns `true` if no archetype is matched.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns the total number of entities in the matched archetypes.
+    pub fn entity_count(&self) -> usize {
+        self.iter().map(Archetype::len).sum()
+    }
+}
+
+impl<'w, 's, Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> Debug
+    for MatchedArchetypes<'w, 's, Q, F>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("MatchedArchetypes").field(&self.ids).finish()
+    }
+}
+
+// SAFETY: QueryState is constrained to read-only fetches, and only archetype metadata is read.
+unsafe impl<'w, 's, Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static>
+    ReadOnlySystemParam for MatchedArchetypes<'w, 's, Q, F>
+{
+}
+
+// SAFETY: Relevant query ComponentId and ArchetypeComponentId access is applied to SystemMeta by
+// the `Query` implementation. Only the archetype metadata is read, which doesn't change while
+// systems run.
+unsafe impl<Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemParam
+    for MatchedArchetypes<'_, '_, Q, F>
+{
+    type State = QueryState<Q, F>;
+    type Item<'w, 's> = MatchedArchetypes<'w, 's, Q, F>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Query::<Q, F>::init_state(world, system_meta)
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        Query::<Q, F>::new_archetype(state, archetype, system_meta);
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        MatchedArchetypes {
+            archetypes: world.archetypes(),
+            ids: &state.matched_archetype_ids,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A collection of potentially conflicting [`SystemParam`]s allowed by disjoint access.
+///
+/// Allows systems to safely access and interact with up to 16 mutually exclusive [`SystemParam`]s, such as
+/// two queries that reference the same mutable data or an event reader and writer of the same type.
+///
+/// Each individual [`SystemParam`] can be accessed by using the functions `p0()`, `p1()`, ..., `p15()`,
+/// according to the order they are defined in the `ParamSet`. This ensures that there's either
+/// only one mutable reference to a parameter at a time or any number of immutable references.
+/// If all parameters have the same type, they can also be accessed by an index chosen at runtime
+/// with `get_mut(index)`.
+///
+/// # Examples
+///
+/// The following system mutably accesses the same component two times,
+/// which is not allowed due to rust's mutability rules.
+///
+/// ```should_panic
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Health;
+/// #
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// #
+/// # #[derive(Component)]
+/// # struct Ally;
+/// #
+/// // This will panic at runtime when the system gets initialized.
+/// fn bad_system(
+///     mut enemies: Query<&mut Health, With<Enemy>>,
+///     mut allies: Query<&mut Health, With<Ally>>,
+/// ) {
+///     // ...
+/// }
+/// #
+/// # let mut bad_system_system = bevy_ecs::system::IntoSystem::into_system(bad_system);
+/// # let mut world = World::new();
+/// # bad_system_system.initialize(&mut world);
+/// # bad_system_system.run((), &mut world);
+/// ```
+///
+/// Conflicting `SystemParam`s like these can be placed in a `ParamSet`,
+/// which leverages the borrow checker to ensure that only one of the contained parameters are accessed at a given time.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Health;
+/// #
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// #
+/// # #[derive(Component)]
+/// # struct Ally;
+/// #
+/// // Given the following system
+/// fn fancy_system(
+///     mut set: ParamSet<(
+///         Query<&mut Health, With<Enemy>>,
+///         Query<&mut Health, With<Ally>>,
+///     )>
+/// ) {
+///     // This will access the first `SystemParam`.
+///     for mut health in set.p0().iter_mut() {
+///         // Do your fancy stuff here...
+///     }
+///
+///     // The second `SystemParam`.
+///     // This would fail to compile if the previous parameter was still borrowed.
+///     for mut health in set.p1().iter_mut() {
+///         // Do even fancier stuff here...
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(fancy_system);
+/// ```
+///
+/// Of course, `ParamSet`s can be used with any kind of `SystemParam`, not just [queries](Query).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Event)]
+/// # struct MyEvent;
+/// # impl MyEvent {
+/// #   pub fn new() -> Self { Self }
+/// # }
+/// fn event_system(
+///     mut set: ParamSet<(
+///         // `EventReader`s and `EventWriter`s conflict with each other,
 ///         // since they both access the event queue resource for `MyEvent`.
 ///         EventReader<MyEvent>,
 ///         EventWriter<MyEvent>,
@@ -332,170 +1087,766 @@ fn assert_component_access_compatibility(
 ///     // ...
 ///     # let _entities = entities;
 /// }
-/// # bevy_ecs::system::assert_is_system(event_system);
+/// # bevy_ecs::system::assert_is_system(event_system);
+/// ```
+///
+/// If all parameters of the set are [`ReadOnlySystemParam`]s, they can be accessed at the same
+/// time with `all_read`, since shared reads never conflict with each other.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// #
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// #
+/// # #[derive(Component)]
+/// # struct Ally;
+/// #
+/// fn compare_system(
+///     mut set: ParamSet<(
+///         Query<&Health, With<Enemy>>,
+///         Query<&Health, With<Ally>>,
+///     )>,
+/// ) {
+///     let (enemies, allies) = set.all_read();
+///     let enemy_health: u32 = enemies.iter().map(|health| health.0).sum();
+///     let ally_health: u32 = allies.iter().map(|health| health.0).sum();
+///     # let _ = enemy_health > ally_health;
+/// }
+/// # bevy_ecs::system::assert_is_system(compare_system);
+/// ```
+///
+/// For introspection, e.g. in debugging tools, `ParamSet::<(P0, P1, ...)>::LEN` is the number
+/// of parameters in the set, and [`ParamSet::is_read_only`] tells which of them only read the
+/// world.
+///
+/// A `ParamSet` holds up to 16 parameters. Larger sets don't compile, and the error names the
+/// [`ParamSetUpTo16NestForMore`] bound they fail:
+///
+/// ```compile_fail
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Resource)]
+/// # struct R;
+/// #
+/// fn too_many_params(
+///     _set: ParamSet<(
+///         Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>,
+///         Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>,
+///     )>,
+/// ) {
+/// }
+/// # bevy_ecs::system::assert_is_system(too_many_params);
+/// ```
+///
+/// More parameters fit by nesting sets, e.g. as the last parameter of the outer set:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Resource)]
+/// # struct R;
+/// #
+/// fn nested_params(
+///     mut set: ParamSet<(
+///         Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>,
+///         Res<R>, Res<R>, Res<R>, Res<R>, Res<R>, Res<R>,
+///         ParamSet<(Res<R>, Res<R>)>,
+///     )>,
+/// ) {
+///     let mut inner = set.p15();
+///     let _r = inner.p1();
+/// }
+/// # bevy_ecs::system::assert_is_system(nested_params);
 /// ```
-pub struct ParamSet<'w, 's, T: SystemParam> {
+pub struct ParamSet<'w, 's, T: ParamSetUpTo16NestForMore> {
     param_states: &'s mut T::State,
+    read_only: &'s [bool],
     world: UnsafeWorldCell<'w>,
     system_meta: SystemMeta,
     change_tick: Tick,
 }
 
-impl_param_set!();
+/// The parameters of a [`ParamSet`]: a tuple of at most 16 [`SystemParam`]s.
+///
+/// A `ParamSet` can't hold more than 16 parameters, so compiler errors about this trait not
+/// being implemented usually come from a set with too many parameters. Nest `ParamSet`s to
+/// use more, e.g. `ParamSet<(P0, ..., P14, ParamSet<(P15, P16)>)>`.
+///
+/// This is implemented for all such tuples along with [`SystemParam`] for [`ParamSet`].
+pub trait ParamSetUpTo16NestForMore: SystemParam {}
+
+impl_param_set!();
+
+impl<'w, 's, T: ParamSetUpTo16NestForMore> ParamSet<'w, 's, T> {
+    /// Returns whether the parameter at `index` only reads the world, or `None` if `index` is
+    /// out of range.
+    ///
+    /// This is based on the access the parameter registered when the system was initialized:
+    /// a parameter that doesn't register any write access is read-only, like every
+    /// [`ReadOnlySystemParam`] is. Parameters with deferred effects that don't access the world
+    /// while the system runs, like [`Commands`](super::Commands), are read-only as well.
+    pub fn is_read_only(&self, index: usize) -> Option<bool> {
+        self.read_only.get(index).copied()
+    }
+}
+
+/// A type that can be inserted into a [`World`] as a singleton.
+///
+/// You can access resource data in systems using the [`Res`] and [`ResMut`] system parameters
+///
+/// Only one resource of each type can be stored in a [`World`] at any given time.
+///
+/// # Examples
+///
+/// ```
+/// # let mut world = World::default();
+/// # let mut schedule = Schedule::default();
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Resource)]
+/// struct MyResource { value: u32 }
+///
+/// world.insert_resource(MyResource { value: 42 });
+///
+/// fn read_resource_system(resource: Res<MyResource>) {
+///     assert_eq!(resource.value, 42);
+/// }
+///
+/// fn write_resource_system(mut resource: ResMut<MyResource>) {
+///     assert_eq!(resource.value, 42);
+///     resource.value = 0;
+///     assert_eq!(resource.value, 0);
+/// }
+/// # schedule.add_systems((read_resource_system, write_resource_system).chain());
+/// # schedule.run(&mut world);
+/// ```
+///
+/// # `!Sync` Resources
+/// A `!Sync` type cannot implement `Resource`. However, it is possible to wrap a `Send` but not `Sync`
+/// type in [`SyncCell`] or the currently unstable [`Exclusive`] to make it `Sync`. This forces only
+/// having mutable access (`&mut T` only, never `&T`), but makes it safe to reference across multiple
+/// threads.
+///
+/// This will fail to compile since `RefCell` is `!Sync`.
+/// ```compile_fail
+/// # use std::cell::RefCell;
+/// # use bevy_ecs::system::Resource;
+///
+/// #[derive(Resource)]
+/// struct NotSync {
+///    counter: RefCell<usize>,
+/// }
+/// ```
+///
+/// This will compile since the `RefCell` is wrapped with `SyncCell`.
+/// ```
+/// # use std::cell::RefCell;
+/// # use bevy_ecs::system::Resource;
+/// use bevy_utils::synccell::SyncCell;
+///
+/// #[derive(Resource)]
+/// struct ActuallySync {
+///    counter: SyncCell<RefCell<usize>>,
+/// }
+/// ```
+///
+/// [`SyncCell`]: bevy_utils::synccell::SyncCell
+/// [`Exclusive`]: https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html
+pub trait Resource: Send + Sync + 'static {}
+
+// SAFETY: Res only reads a single World resource
+unsafe impl<'a, T: Resource> ReadOnlySystemParam for Res<'a, T> {}
+
+// SAFETY: Res ComponentId and ArchetypeComponentId access is applied to SystemMeta. If this Res
+// conflicts with any prior access, a panic will occur.
+unsafe impl<'a, T: Resource> SystemParam for Res<'a, T> {
+    type State = ComponentId;
+    type Item<'w, 's> = Res<'w, T>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let component_id = world.initialize_resource::<T>();
+        let combined_access = system_meta.component_access_set.combined_access();
+        assert!(
+            !combined_access.has_write(component_id),
+            "error[B0002]: Res<{}> in system {} conflicts with a previous ResMut<{0}> access. Consider removing the duplicate access.",
+            std::any::type_name::<T>(),
+            system_meta.name,
+        );
+        system_meta
+            .component_access_set
+            .add_unfiltered_read(component_id);
+
+        let archetype_component_id = world
+            .get_resource_archetype_component_id(component_id)
+            .unwrap();
+        system_meta
+            .archetype_component_access
+            .add_read(archetype_component_id);
+
+        component_id
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Read-only access to the resource is registered in `init_state`.
+        world
+            .storages()
+            .resources
+            .get(component_id)
+            .is_some_and(|resource| resource.is_present())
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        &mut component_id: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        let (ptr, ticks) = world
+            .get_resource_with_ticks(component_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Resource requested by {} does not exist: {}",
+                    system_meta.name,
+                    std::any::type_name::<T>()
+                )
+            });
+        Res {
+            value: ptr.deref(),
+            ticks: Ticks {
+                added: ticks.added.deref(),
+                changed: ticks.changed.deref(),
+                last_run: system_meta.last_run,
+                this_run: change_tick,
+            },
+        }
+    }
+}
+
+// SAFETY: Only reads a single World resource
+unsafe impl<'a, T: Resource> ReadOnlySystemParam for Option<Res<'a, T>> {}
+
+// SAFETY: this impl defers to `Res`, which initializes and validates the correct world access.
+unsafe impl<'a, T: Resource> SystemParam for Option<Res<'a, T>> {
+    type State = ComponentId;
+    type Item<'w, 's> = Option<Res<'w, T>>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Res::<T>::init_state(world, system_meta)
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        &mut component_id: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        world
+            .get_resource_with_ticks(component_id)
+            .map(|(ptr, ticks)| Res {
+                value: ptr.deref(),
+                ticks: Ticks {
+                    added: ticks.added.deref(),
+                    changed: ticks.changed.deref(),
+                    last_run: system_meta.last_run,
+                    this_run: change_tick,
+                },
+            })
+    }
+}
+
+/// A [`SystemParam`] that works like [`Res`], but warns once instead of only skipping the system
+/// silently while the resource doesn't exist.
+///
+/// This dereferences to the inner [`Res`]. The executors [validate](SystemParam::validate_param)
+/// the parameter and skip the system for every run while the resource is missing. The first
+/// time that happens, a warning naming the resource and the system is logged, which helps to
+/// find a plugin that wasn't added, without crashing the app.
+///
+/// Running a system directly with [`System::run`](super::System::run) doesn't validate its
+/// parameters, so getting the parameter panics there if the resource doesn't exist, like [`Res`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::ResOrSkip};
+/// # #[derive(Resource)]
+/// # struct PhysicsSettings { gravity: f32 }
+/// # #[derive(Component)]
+/// # struct Velocity(f32);
+/// // Only runs once the physics plugin inserted its settings.
+/// fn apply_gravity(settings: ResOrSkip<PhysicsSettings>, mut velocities: Query<&mut Velocity>) {
+///     for mut velocity in &mut velocities {
+///         velocity.0 -= settings.gravity;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(apply_gravity);
+/// ```
+pub struct ResOrSkip<'w, T: Resource>(Res<'w, T>);
+
+impl<'w, T: Resource> Deref for ResOrSkip<'w, T> {
+    type Target = Res<'w, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'w, T: Resource> ResOrSkip<'w, T> {
+    /// Returns the inner [`Res`] with ownership.
+    pub fn into_inner(self) -> Res<'w, T> {
+        self.0
+    }
+}
+
+// SAFETY: Only reads a single World resource
+unsafe impl<'a, T: Resource> ReadOnlySystemParam for ResOrSkip<'a, T> {}
+
+// SAFETY: this impl defers to `Res`, which initializes and validates the correct world access.
+unsafe impl<'a, T: Resource> SystemParam for ResOrSkip<'a, T> {
+    /// The id of the resource, and whether a warning about it missing was logged.
+    type State = (ComponentId, AtomicBool);
+    type Item<'w, 's> = ResOrSkip<'w, T>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        (
+            Res::<T>::init_state(world, system_meta),
+            AtomicBool::new(false),
+        )
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        (component_id, warned): &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        let is_valid = Res::<T>::validate_param(component_id, system_meta, world);
+        if !is_valid && !warned.swap(true, Ordering::Relaxed) {
+            warn!(
+                "Resource {} requested by {} does not exist, the system is skipped until it is inserted",
+                std::any::type_name::<T>(),
+                system_meta.name,
+            );
+        }
+        is_valid
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        (component_id, _): &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        ResOrSkip(Res::<T>::get_param(
+            component_id,
+            system_meta,
+            world,
+            change_tick,
+        ))
+    }
+}
+
+/// A [`SystemParam`] that works like [`Res`], but inserts the resource when the system is
+/// initialized if it doesn't exist yet.
+///
+/// This dereferences to the inner [`Res`]. Unlike the other parameters, initializing it mutates
+/// the [`World`]: the resource is created with [`FromWorld`], which uses [`Default`] if the
+/// resource implements it, like [`World::init_resource`] does. The system that needs the
+/// resource then guarantees it exists, without a separate `init_resource` call when setting up
+/// the app. Only read access is registered, because the resource is only written once, before
+/// the system runs.
+///
+/// The resource can still be removed after the system is initialized, in which case getting the
+/// parameter fails like it does for [`Res`]. See [`ResMutInit`] for mutable access.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::ResInit};
+/// #[derive(Resource)]
+/// struct Gravity(f32);
+///
+/// impl Default for Gravity {
+///     fn default() -> Self {
+///         Gravity(9.81)
+///     }
+/// }
+///
+/// fn read_gravity(gravity: ResInit<Gravity>) {
+///     assert_eq!(gravity.0, 9.81);
+/// }
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(read_gravity);
+/// schedule.run(&mut world);
+/// assert!(world.contains_resource::<Gravity>());
+/// ```
+pub struct ResInit<'w, T: Resource + FromWorld>(Res<'w, T>);
+
+impl<'w, T: Resource + FromWorld> Deref for ResInit<'w, T> {
+    type Target = Res<'w, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'w, T: Resource + FromWorld> ResInit<'w, T> {
+    /// Returns the inner [`Res`] with ownership.
+    pub fn into_inner(self) -> Res<'w, T> {
+        self.0
+    }
+}
+
+// SAFETY: Only reads a single World resource
+unsafe impl<'a, T: Resource + FromWorld> ReadOnlySystemParam for ResInit<'a, T> {}
+
+// SAFETY: this impl defers to `Res`, which initializes and validates the correct world access.
+unsafe impl<'a, T: Resource + FromWorld> SystemParam for ResInit<'a, T> {
+    type State = ComponentId;
+    type Item<'w, 's> = ResInit<'w, T>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        world.init_resource::<T>();
+        Res::<T>::init_state(world, system_meta)
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        Res::<T>::validate_param(&component_id, system_meta, world)
+    }
 
-/// A type that can be inserted into a [`World`] as a singleton.
-///
-/// You can access resource data in systems using the [`Res`] and [`ResMut`] system parameters
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        component_id: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        ResInit(Res::<T>::get_param(
+            component_id,
+            system_meta,
+            world,
+            change_tick,
+        ))
+    }
+}
+
+/// A [`SystemParam`] that works like [`ResMut`], but inserts the resource when the system is
+/// initialized if it doesn't exist yet.
 ///
-/// Only one resource of each type can be stored in a [`World`] at any given time.
+/// This dereferences to the inner [`ResMut`]. Like [`ResInit`], initializing it mutates the
+/// [`World`] to create the resource with [`FromWorld`]. Write access to the resource is
+/// registered, like for [`ResMut`].
 ///
-/// # Examples
+/// # Example
 ///
 /// ```
-/// # let mut world = World::default();
-/// # let mut schedule = Schedule::default();
-/// # use bevy_ecs::prelude::*;
-/// #[derive(Resource)]
-/// struct MyResource { value: u32 }
-///
-/// world.insert_resource(MyResource { value: 42 });
+/// # use bevy_ecs::{prelude::*, system::ResMutInit};
+/// #[derive(Resource, Default)]
+/// struct FrameCount(u32);
 ///
-/// fn read_resource_system(resource: Res<MyResource>) {
-///     assert_eq!(resource.value, 42);
+/// fn count_frames(mut frames: ResMutInit<FrameCount>) {
+///     frames.0 += 1;
 /// }
 ///
-/// fn write_resource_system(mut resource: ResMut<MyResource>) {
-///     assert_eq!(resource.value, 42);
-///     resource.value = 0;
-///     assert_eq!(resource.value, 0);
-/// }
-/// # schedule.add_systems((read_resource_system, write_resource_system).chain());
-/// # schedule.run(&mut world);
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(count_frames);
+/// schedule.run(&mut world);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<FrameCount>().0, 2);
 /// ```
+pub struct ResMutInit<'w, T: Resource + FromWorld>(ResMut<'w, T>);
+
+impl<'w, T: Resource + FromWorld> Deref for ResMutInit<'w, T> {
+    type Target = ResMut<'w, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'w, T: Resource + FromWorld> DerefMut for ResMutInit<'w, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'w, T: Resource + FromWorld> ResMutInit<'w, T> {
+    /// Returns the inner [`ResMut`] with ownership.
+    pub fn into_inner(self) -> ResMut<'w, T> {
+        self.0
+    }
+}
+
+// SAFETY: this impl defers to `ResMut`, which initializes and validates the correct world access.
+unsafe impl<'a, T: Resource + FromWorld> SystemParam for ResMutInit<'a, T> {
+    type State = ComponentId;
+    type Item<'w, 's> = ResMutInit<'w, T>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        world.init_resource::<T>();
+        ResMut::<T>::init_state(world, system_meta)
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        ResMut::<T>::validate_param(&component_id, system_meta, world)
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        component_id: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        ResMutInit(ResMut::<T>::get_param(
+            component_id,
+            system_meta,
+            world,
+            change_tick,
+        ))
+    }
+}
+
+/// A [`SystemParam`] wrapping another parameter `P`, which is `None` instead of making the system
+/// fail when `P` can't be used.
 ///
-/// # `!Sync` Resources
-/// A `!Sync` type cannot implement `Resource`. However, it is possible to wrap a `Send` but not `Sync`
-/// type in [`SyncCell`] or the currently unstable [`Exclusive`] to make it `Sync`. This forces only
-/// having mutable access (`&mut T` only, never `&T`), but makes it safe to reference across multiple
-/// threads.
+/// This dereferences to an `Option<P>`, for optional parts of a system that it can run without,
+/// e.g. a debug overlay updated by a plugin system if it is allowed to.
 ///
-/// This will fail to compile since `RefCell` is `!Sync`.
-/// ```compile_fail
-/// # use std::cell::RefCell;
-/// # use bevy_ecs::system::Resource;
+/// - If the access of `P` conflicts with a parameter declared *before* it in the same system,
+///   `P` is never fetched, and the parameter is always `None`. A warning is logged once when the
+///   system is initialized. Without `Maybe`, such a conflict panics with `error[B0001]`, see
+///   [`Query`]. The access of `P` isn't registered then, so it doesn't affect the scheduling of
+///   the system either.
+/// - Otherwise, the access of `P` is registered like it would be for `P` alone. Parameters
+///   declared *after* it are checked against it as usual, and still panic on conflicts, so
+///   `Maybe` parameters should come last.
+/// - On every run, the parameter is `None` while `P` isn't [valid](SystemParam::validate_param),
+///   e.g. because a resource doesn't exist, instead of skipping the whole system.
 ///
-/// #[derive(Resource)]
-/// struct NotSync {
-///    counter: RefCell<usize>,
-/// }
-/// ```
+/// The conflicts are only checked once, when the system is initialized: the access of a
+/// system can't change between runs. Conflicts with *other systems* never panic, they only
+/// keep the systems from running in parallel, so `Maybe` has no effect on them.
 ///
-/// This will compile since the `RefCell` is wrapped with `SyncCell`.
-/// ```
-/// # use std::cell::RefCell;
-/// # use bevy_ecs::system::Resource;
-/// use bevy_utils::synccell::SyncCell;
+/// # Example
 ///
-/// #[derive(Resource)]
-/// struct ActuallySync {
-///    counter: SyncCell<RefCell<usize>>,
+/// ```
+/// # use bevy_ecs::{prelude::*, system::Maybe};
+/// # #[derive(Component)]
+/// # struct Transform(f32);
+/// # #[derive(Resource)]
+/// # struct DebugLines(Vec<f32>);
+/// // Runs whether or not the debug plugin inserted its resource.
+/// fn move_forward(mut transforms: Query<&mut Transform>, mut lines: Maybe<ResMut<DebugLines>>) {
+///     for mut transform in &mut transforms {
+///         transform.0 += 1.0;
+///         if let Some(lines) = lines.as_mut() {
+///             lines.0.push(transform.0);
+///         }
+///     }
 /// }
+/// # bevy_ecs::system::assert_is_system(move_forward);
 /// ```
-///
-/// [`SyncCell`]: bevy_utils::synccell::SyncCell
-/// [`Exclusive`]: https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html
-pub trait Resource: Send + Sync + 'static {}
+pub struct Maybe<P>(Option<P>);
 
-// SAFETY: Res only reads a single World resource
-unsafe impl<'a, T: Resource> ReadOnlySystemParam for Res<'a, T> {}
+impl<P> Deref for Maybe<P> {
+    type Target = Option<P>;
 
-// SAFETY: Res ComponentId and ArchetypeComponentId access is applied to SystemMeta. If this Res
-// conflicts with any prior access, a panic will occur.
-unsafe impl<'a, T: Resource> SystemParam for Res<'a, T> {
-    type State = ComponentId;
-    type Item<'w, 's> = Res<'w, T>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P> DerefMut for Maybe<P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<P> Maybe<P> {
+    /// Returns the inner parameter with ownership, if it could be fetched.
+    pub fn into_inner(self) -> Option<P> {
+        self.0
+    }
+}
+
+// SAFETY: Only fetches `P`, which is read-only.
+unsafe impl<P: ReadOnlySystemParam> ReadOnlySystemParam for Maybe<P> {}
+
+// SAFETY: The access of `P` is registered unless it conflicts with the prior access, in which
+// case `P` is never fetched.
+unsafe impl<P: SystemParam> SystemParam for Maybe<P> {
+    /// The state of `P`, or `None` if it conflicts with a previous parameter.
+    type State = Option<P::State>;
+    type Item<'w, 's> = Maybe<P::Item<'w, 's>>;
 
     fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
-        let component_id = world.initialize_resource::<T>();
-        let combined_access = system_meta.component_access_set.combined_access();
-        assert!(
-            !combined_access.has_write(component_id),
-            "error[B0002]: Res<{}> in system {} conflicts with a previous ResMut<{0}> access. Consider removing the duplicate access.",
-            std::any::type_name::<T>(),
-            system_meta.name,
-        );
-        system_meta
+        // Add the param to the system alone, like a member of a `ParamSet`, and see if it
+        // conflicts.
+        let mut meta = system_meta.clone();
+        meta.component_access_set.clear();
+        meta.archetype_component_access.clear();
+        let state = P::init_state(world, &mut meta);
+        if !system_meta
             .component_access_set
-            .add_unfiltered_read(component_id);
+            .is_compatible(&meta.component_access_set)
+        {
+            warn!(
+                "{} in system {} conflicts with a previous system parameter, so it is always None",
+                std::any::type_name::<P>(),
+                system_meta.name,
+            );
+            return None;
+        }
 
-        let archetype_component_id = world
-            .get_resource_archetype_component_id(component_id)
-            .unwrap();
+        if !meta.is_send() {
+            system_meta.set_non_send();
+        }
+        system_meta
+            .component_access_set
+            .extend(meta.component_access_set);
         system_meta
             .archetype_component_access
-            .add_read(archetype_component_id);
+            .extend(&meta.archetype_component_access);
+        Some(state)
+    }
 
-        component_id
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        if let Some(state) = state {
+            P::new_archetype(state, archetype, system_meta);
+        }
+    }
+
+    fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
+        if let Some(state) = state {
+            P::apply(state, system_meta, world);
+        }
     }
 
     #[inline]
     unsafe fn get_param<'w, 's>(
-        &mut component_id: &'s mut Self::State,
+        state: &'s mut Self::State,
         system_meta: &SystemMeta,
         world: UnsafeWorldCell<'w>,
         change_tick: Tick,
     ) -> Self::Item<'w, 's> {
-        let (ptr, ticks) = world
-            .get_resource_with_ticks(component_id)
-            .unwrap_or_else(|| {
-                panic!(
-                    "Resource requested by {} does not exist: {}",
-                    system_meta.name,
-                    std::any::type_name::<T>()
-                )
-            });
-        Res {
-            value: ptr.deref(),
-            ticks: Ticks {
-                added: ticks.added.deref(),
-                changed: ticks.changed.deref(),
-                last_run: system_meta.last_run,
-                this_run: change_tick,
-            },
-        }
+        // The parameter is `None` rather than skipping the system, so it is always valid itself.
+        Maybe(state.as_mut().and_then(|state| {
+            // SAFETY: The access of `P` is registered, and validating only reads the world.
+            if !P::validate_param(state, system_meta, world) {
+                return None;
+            }
+            // SAFETY: The access of `P` is registered, so the caller ensures that `world` has
+            // permission to access the data `P` needs.
+            Some(P::get_param(state, system_meta, world, change_tick))
+        }))
     }
 }
 
-// SAFETY: Only reads a single World resource
-unsafe impl<'a, T: Resource> ReadOnlySystemParam for Option<Res<'a, T>> {}
+/// A [`SystemParam`] that reports whether the resource `T` exists, without accessing it.
+///
+/// The resource is checked every time the system runs, so this can be used to gate behavior on
+/// resources that are inserted or removed at runtime, e.g. by optional plugins. No access to the
+/// resource is registered, so `ResExists<T>` doesn't conflict with any access to `T`, including
+/// a [`ResMut<T>`] in the same system. Use `Option<Res<T>>` instead to read the resource when it
+/// exists.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::ResExists};
+/// # #[derive(Resource)]
+/// # struct DebugOverlay;
+/// # #[derive(Resource, Default)]
+/// # struct Stats { draw_calls: usize }
+/// fn collect_stats(overlay: ResExists<DebugOverlay>, mut stats: ResMut<Stats>) {
+///     if *overlay {
+///         stats.draw_calls += 1;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_read_only_system(|_: ResExists<DebugOverlay>| {});
+/// # bevy_ecs::system::assert_is_system(collect_stats);
+/// ```
+pub struct ResExists<T: Resource> {
+    exists: bool,
+    marker: PhantomData<fn() -> T>,
+}
 
-// SAFETY: this impl defers to `Res`, which initializes and validates the correct world access.
-unsafe impl<'a, T: Resource> SystemParam for Option<Res<'a, T>> {
+impl<T: Resource> ResExists<T> {
+    /// Returns `true` if the resource existed when the system ran.
+    #[inline]
+    pub fn get(&self) -> bool {
+        self.exists
+    }
+}
+
+impl<T: Resource> Deref for ResExists<T> {
+    type Target = bool;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.exists
+    }
+}
+
+impl<T: Resource> Debug for ResExists<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ResExists").field(&self.exists).finish()
+    }
+}
+
+// SAFETY: Doesn't access any World data.
+unsafe impl<T: Resource> ReadOnlySystemParam for ResExists<T> {}
+
+// SAFETY: No access is registered. Only the presence of the resource is read, which can only
+// change with exclusive access to the World, while no system runs.
+unsafe impl<T: Resource> SystemParam for ResExists<T> {
     type State = ComponentId;
-    type Item<'w, 's> = Option<Res<'w, T>>;
+    type Item<'w, 's> = ResExists<T>;
 
-    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
-        Res::<T>::init_state(world, system_meta)
+    fn init_state(world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {
+        world.initialize_resource::<T>()
     }
 
     #[inline]
     unsafe fn get_param<'w, 's>(
         &mut component_id: &'s mut Self::State,
-        system_meta: &SystemMeta,
+        _system_meta: &SystemMeta,
         world: UnsafeWorldCell<'w>,
-        change_tick: Tick,
+        _change_tick: Tick,
     ) -> Self::Item<'w, 's> {
-        world
-            .get_resource_with_ticks(component_id)
-            .map(|(ptr, ticks)| Res {
-                value: ptr.deref(),
-                ticks: Ticks {
-                    added: ticks.added.deref(),
-                    changed: ticks.changed.deref(),
-                    last_run: system_meta.last_run,
-                    this_run: change_tick,
-                },
-            })
+        ResExists {
+            exists: world
+                .storages()
+                .resources
+                .get(component_id)
+                .is_some_and(|resource| resource.is_present()),
+            marker: PhantomData,
+        }
     }
 }
 
@@ -531,6 +1882,20 @@ unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
         component_id
     }
 
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Read-only access to the resource is registered in `init_state`.
+        world
+            .storages()
+            .resources
+            .get(component_id)
+            .is_some_and(|resource| resource.is_present())
+    }
+
     #[inline]
     unsafe fn get_param<'w, 's>(
         &mut component_id: &'s mut Self::State,
@@ -679,6 +2044,10 @@ unsafe impl SystemParam for &'_ World {
 /// // .add_systems(reset_to_system(my_config))
 /// # assert_is_system(reset_to_system(Config(10)));
 /// ```
+///
+/// To start a [`Local`] out with a value other than [`FromWorld::from_world`], build the system
+/// with a [`LocalBuilder`](super::LocalBuilder) instead, or compute it from the world with a
+/// [`LazyLocalBuilder`](super::LazyLocalBuilder).
 #[derive(Debug)]
 pub struct Local<'s, T: FromWorld + Send + 'static>(pub(crate) &'s mut T);
 
@@ -745,15 +2114,92 @@ unsafe impl<'a, T: FromWorld + Send + 'static> SystemParam for Local<'a, T> {
     }
 }
 
-/// Types that can be used with [`Deferred<T>`] in systems.
-/// This allows storing system-local data which is used to defer [`World`] mutations.
+/// Types that can be used with [`Deferred<T>`] in systems.
+/// This allows storing system-local data which is used to defer [`World`] mutations.
+///
+/// Types that implement `SystemBuffer` should take care to perform as many
+/// computations up-front as possible. Buffers cannot be applied in parallel,
+/// so you should try to minimize the time spent in [`SystemBuffer::apply`].
+///
+/// # Apply order
+///
+/// The buffers of a schedule are applied one system at a time, in the order of the systems in
+/// the built schedule. That order respects the ordering constraints between systems, so if
+/// system `a` runs [`before`](crate::schedule::IntoSystemConfigs::before) system `b`, the buffers
+/// of `a` are applied before those of `b` even when they are applied at the same
+/// [`apply_deferred`](crate::schedule::apply_deferred). Systems without ordering constraints
+/// between them are applied in an unspecified order, which stays the same for every run of the
+/// schedule until it is rebuilt.
+///
+/// Within a system, the buffers are applied in the order of its parameters, so a system with
+/// several `Deferred` parameters of the same type applies them from first to last.
+///
+/// See [`Deferred`] for how an exclusive system can apply buffers before it returns, and
+/// [`MergeSystemBuffer`] for how to combine several buffers into one.
+pub trait SystemBuffer: FromWorld + Send + 'static {
+    /// Applies any deferred mutations to the [`World`].
+    fn apply(&mut self, system_meta: &SystemMeta, world: &mut World);
+
+    /// Reserves capacity for at least `additional` more deferred mutations, to avoid
+    /// reallocating the buffer while a system fills it.
+    ///
+    /// This is only a hint, called when a [`Deferred`] is built with a capacity by a
+    /// [`DeferredBuilder`](super::DeferredBuilder). The unit of `additional` is up to the buffer,
+    /// such as the number of bytes for a [`CommandQueue`](crate::system::CommandQueue).
+    /// The default implementation ignores it.
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+}
+
+/// A [`SystemBuffer`] that can take over the deferred mutations of another buffer of the same type.
 ///
-/// Types that implement `SystemBuffer` should take care to perform as many
-/// computations up-front as possible. Buffers cannot be applied in parallel,
-/// so you should try to minimize the time spent in [`SystemBuffer::apply`].
-pub trait SystemBuffer: FromWorld + Send + 'static {
-    /// Applies any deferred mutations to the [`World`].
-    fn apply(&mut self, system_meta: &SystemMeta, world: &mut World);
+/// This lets a system combine the buffers of several [`Deferred`] parameters, or of several
+/// [`SystemState`](super::SystemState)s, so that their mutations are applied together in a
+/// defined order instead of the order of the parameters or systems holding them.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::system::{Deferred, MergeSystemBuffer, SystemBuffer, SystemMeta};
+///
+/// #[derive(Resource, Default)]
+/// struct Messages(Vec<&'static str>);
+///
+/// #[derive(Default)]
+/// struct MessageBuffer(Vec<&'static str>);
+///
+/// impl SystemBuffer for MessageBuffer {
+///     fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+///         world.resource_mut::<Messages>().0.append(&mut self.0);
+///     }
+/// }
+///
+/// impl MergeSystemBuffer for MessageBuffer {
+///     fn merge(&mut self, other: &mut Self) {
+///         self.0.append(&mut other.0);
+///     }
+/// }
+///
+/// fn report(mut warnings: Deferred<MessageBuffer>, mut errors: Deferred<MessageBuffer>) {
+///     warnings.0.push("warning");
+///     errors.0.push("error");
+///     // Report the errors first, even though `warnings` is the first parameter.
+///     errors.merge(&mut warnings);
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Messages>();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(report);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Messages>().0, ["error", "warning"]);
+/// ```
+pub trait MergeSystemBuffer: SystemBuffer {
+    /// Moves the deferred mutations of `other` to the end of this buffer, leaving `other` empty.
+    ///
+    /// Applying this buffer then applies the mutations it held first, followed by those of
+    /// `other`, in the order they were made.
+    fn merge(&mut self, other: &mut Self);
 }
 
 /// A [`SystemParam`] that stores a buffer which gets applied to the [`World`] during
@@ -876,6 +2322,45 @@ pub trait SystemBuffer: FromWorld + Send + 'static {
 /// schedule.run(&mut world);
 /// assert_eq!(world.resource::<Alarm>().0, true);
 /// ```
+///
+/// # Applying buffers within a system
+///
+/// A regular system can't apply its buffers while it runs. Its parameters are fetched from an
+/// [`UnsafeWorldCell`] that only grants the accesses they registered, and other systems may run
+/// in parallel with accesses of their own, so nothing may structurally change the [`World`]
+/// until the schedule reaches a sync point like
+/// [`apply_deferred`](crate::schedule::apply_deferred).
+///
+/// An exclusive system has unique access to the `World` though, so it can fetch regular
+/// parameters with a [`SystemState`](super::SystemState), apply their buffers with
+/// [`SystemState::apply`](super::SystemState::apply), and fetch them again to observe the
+/// effects. The parameters must be dropped before applying, which the borrow checker enforces
+/// since they borrow the `World` and the state.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::SystemState};
+/// # #[derive(Component)]
+/// # struct Marker;
+/// fn spawn_then_query(
+///     world: &mut World,
+///     state: &mut SystemState<(Commands, Query<&Marker>)>,
+/// ) {
+///     let (mut commands, markers) = state.get_mut(world);
+///     assert_eq!(markers.iter().count(), 0);
+///     commands.spawn(Marker);
+///
+///     // Flush the commands, which spawns the entity.
+///     state.apply(world);
+///
+///     let (_, markers) = state.get_mut(world);
+///     assert_eq!(markers.iter().count(), 1);
+/// }
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(spawn_then_query);
+/// schedule.run(&mut world);
+/// ```
 pub struct Deferred<'a, T: SystemBuffer>(pub(crate) &'a mut T);
 
 impl<'a, T: SystemBuffer> Deref for Deferred<'a, T> {
@@ -926,11 +2411,22 @@ unsafe impl<T: SystemBuffer> SystemParam for Deferred<'_, T> {
 /// the scheduler to instead run the system on the main thread so that it doesn't send the resource
 /// over to another thread.
 ///
+/// # Main thread
+///
+/// A system with a `NonSend` parameter always runs on the main thread, so at most one such
+/// system runs at a time, no matter which resources they access. `NonSend` only registers read
+/// access to the resource though, like [`Res`] does: it doesn't conflict with other systems
+/// reading the same resource, so they can be ordered freely without ambiguities, and it is a
+/// [`ReadOnlySystemParam`]. Use [`NonSendMut`] only when the resource needs to be modified, as
+/// it conflicts with every other access to the resource.
+///
 /// # Panics
 ///
-/// Panics when used as a `SystemParameter` if the resource does not exist.
+/// Systems using this parameter are skipped by the schedule while the resource does not exist.
+/// Getting the parameter anyway, such as by running the system with [`System::run`](super::System::run),
+/// panics.
 ///
-/// Use `Option<NonSend<T>>` instead if the resource might not always exist.
+/// Use `Option<NonSend<T>>` instead if the system should run whether or not the resource exists.
 pub struct NonSend<'w, T: 'static> {
     pub(crate) value: &'w T,
     ticks: ComponentTicks,
@@ -1014,6 +2510,20 @@ unsafe impl<'a, T: 'static> SystemParam for NonSend<'a, T> {
         component_id
     }
 
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Read-only access to the resource is registered in `init_state`.
+        world
+            .storages()
+            .non_send_resources
+            .get(component_id)
+            .is_some_and(|resource| resource.is_present())
+    }
+
     #[inline]
     unsafe fn get_param<'w, 's>(
         &mut component_id: &'s mut Self::State,
@@ -1104,6 +2614,20 @@ unsafe impl<'a, T: 'static> SystemParam for NonSendMut<'a, T> {
         component_id
     }
 
+    #[inline]
+    unsafe fn validate_param(
+        &component_id: &Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Read-only access to the resource is registered in `init_state`.
+        world
+            .storages()
+            .non_send_resources
+            .get(component_id)
+            .is_some_and(|resource| resource.is_present())
+    }
+
     #[inline]
     unsafe fn get_param<'w, 's>(
         &mut component_id: &'s mut Self::State,
@@ -1194,84 +2718,462 @@ unsafe impl<'a> SystemParam for &'a Components {
     }
 }
 
+/// A [`SystemParam`] that reads the [`Components`] of the [`World`], with helpers to translate
+/// between types and [`ComponentId`]s, e.g. for reflection-driven tools.
+///
+/// This dereferences to [`Components`], so the lookups of `&Components`, like
+/// [`Components::component_id`], are available as well. Like `&Components`, it doesn't access
+/// any component value.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::ComponentRegistry};
+/// # use std::any::TypeId;
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// fn inspect(registry: ComponentRegistry) {
+///     if let Some(id) = registry.component_id::<Health>() {
+///         assert!(registry.is::<Health>(id));
+///         assert_eq!(registry.type_id_of(id), Some(TypeId::of::<Health>()));
+///         println!("{} is {id:?}", registry.name_of(id).unwrap());
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_read_only_system(inspect);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ComponentRegistry<'w> {
+    components: &'w Components,
+}
+
+impl<'w> ComponentRegistry<'w> {
+    /// Returns the inner [`Components`], with the lifetime of the [`World`].
+    pub fn components(&self) -> &'w Components {
+        self.components
+    }
+
+    /// Returns the [`ComponentInfo`] of the component type `T`, if it is registered.
+    #[inline]
+    pub fn info<T: Component>(&self) -> Option<&'w ComponentInfo> {
+        self.components
+            .component_id::<T>()
+            .and_then(|id| self.components.get_info(id))
+    }
+
+    /// Returns the [`ComponentId`] of the component with the given [`TypeId`], or of the resource
+    /// if there is no such component, if either is registered.
+    #[inline]
+    pub fn id_of(&self, type_id: TypeId) -> Option<ComponentId> {
+        self.components
+            .get_id(type_id)
+            .or_else(|| self.components.get_resource_id(type_id))
+    }
+
+    /// Returns the [`TypeId`] of the component or resource with the given [`ComponentId`], if it
+    /// is registered and is a Rust type.
+    #[inline]
+    pub fn type_id_of(&self, id: ComponentId) -> Option<TypeId> {
+        self.components
+            .get_info(id)
+            .and_then(ComponentInfo::type_id)
+    }
+
+    /// Returns the name of the component or resource with the given [`ComponentId`], if it is
+    /// registered.
+    #[inline]
+    pub fn name_of(&self, id: ComponentId) -> Option<&'w str> {
+        self.components.get_info(id).map(ComponentInfo::name)
+    }
+
+    /// Returns whether the given [`ComponentId`] is the one of the component or resource type
+    /// `T`.
+    #[inline]
+    pub fn is<T: 'static>(&self, id: ComponentId) -> bool {
+        self.type_id_of(id) == Some(TypeId::of::<T>())
+    }
+}
+
+impl<'w> Deref for ComponentRegistry<'w> {
+    type Target = Components;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.components
+    }
+}
+
+// SAFETY: Only reads World components
+unsafe impl<'a> ReadOnlySystemParam for ComponentRegistry<'a> {}
+
+// SAFETY: no component value access
+unsafe impl<'a> SystemParam for ComponentRegistry<'a> {
+    type State = ();
+    type Item<'w, 's> = ComponentRegistry<'w>;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {}
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        ComponentRegistry {
+            components: world.components(),
+        }
+    }
+}
+
 // SAFETY: Only reads World entities
 unsafe impl<'a> ReadOnlySystemParam for &'a Entities {}
 
-// SAFETY: no component value access
-unsafe impl<'a> SystemParam for &'a Entities {
+// SAFETY: no component value access
+unsafe impl<'a> SystemParam for &'a Entities {
+    type State = ();
+    type Item<'w, 's> = &'w Entities;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {}
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        world.entities()
+    }
+}
+
+// SAFETY: Only reads World bundles
+unsafe impl<'a> ReadOnlySystemParam for &'a Bundles {}
+
+// SAFETY: no component value access
+unsafe impl<'a> SystemParam for &'a Bundles {
+    type State = ();
+    type Item<'w, 's> = &'w Bundles;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {}
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        world.bundles()
+    }
+}
+
+/// A [`SystemParam`] that reads the entity allocation and archetype counts of the
+/// [`World`], e.g. for diagnostics.
+///
+/// This is a snapshot taken when the system runs, read from [`World::entities`] and
+/// [`World::archetypes`], without any component access. To count the entities matching a
+/// query filter instead, see [`EntityCount`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::EntityStats};
+/// fn report(stats: EntityStats) {
+///     println!(
+///         "{} entities in {} archetypes, {} free slots",
+///         stats.entities, stats.archetypes, stats.free
+///     );
+/// }
+/// # bevy_ecs::system::assert_is_system(report);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityStats {
+    /// The count of currently allocated entities, see [`Entities::len`].
+    pub entities: u32,
+    /// The count of entity slots ever allocated, including freed ones, see
+    /// [`Entities::total_count`].
+    pub total: usize,
+    /// The count of freed entity slots available for reuse, see [`Entities::free_count`].
+    pub free: usize,
+    /// The count of reserved entities that are not flushed yet, see
+    /// [`Entities::reserved_count`].
+    pub reserved: usize,
+    /// The count of archetypes, see [`Archetypes::len`].
+    pub archetypes: usize,
+}
+
+// SAFETY: Only reads World entities and archetypes
+unsafe impl ReadOnlySystemParam for EntityStats {}
+
+// SAFETY: no component value access
+unsafe impl SystemParam for EntityStats {
+    type State = ();
+    type Item<'w, 's> = EntityStats;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {}
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        let entities = world.entities();
+        EntityStats {
+            entities: entities.len(),
+            total: entities.total_count(),
+            free: entities.free_count(),
+            reserved: entities.reserved_count(),
+            archetypes: world.archetypes().len(),
+        }
+    }
+}
+
+/// A [`SystemParam`] that reads the previous and current change ticks of the system.
+///
+/// A system's change ticks are updated each time it runs:
+/// - `last_run` copies the previous value of `change_tick`
+/// - `this_run` copies the current value of [`World::read_change_tick`]
+///
+/// Component change ticks that are more recent than `last_run` will be detected by the system.
+/// Those can be read by calling [`last_changed`](crate::change_detection::DetectChanges::last_changed)
+/// on a [`Mut<T>`](crate::change_detection::Mut) or [`ResMut<T>`](crate::change_detection::ResMut).
+#[derive(Debug)]
+pub struct SystemChangeTick {
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl SystemChangeTick {
+    /// Returns the current [`World`] change tick seen by the system.
+    #[inline]
+    pub fn this_run(&self) -> Tick {
+        self.this_run
+    }
+
+    /// Returns the [`World`] change tick seen by the system the previous time it ran.
+    #[inline]
+    pub fn last_run(&self) -> Tick {
+        self.last_run
+    }
+}
+
+// SAFETY: Only reads internal system state
+unsafe impl ReadOnlySystemParam for SystemChangeTick {}
+
+// SAFETY: `SystemChangeTick` doesn't require any world access
+unsafe impl SystemParam for SystemChangeTick {
+    type State = ();
+    type Item<'w, 's> = SystemChangeTick;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {}
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        _world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        SystemChangeTick {
+            last_run: system_meta.last_run,
+            this_run: change_tick,
+        }
+    }
+}
+
+/// A [`SystemParam`] that reads the current change tick of the system.
+///
+/// This is the same [`Tick`] as [`SystemChangeTick::this_run`], i.e. the value
+/// [`World::read_change_tick`] returns when the system runs, for systems that don't need the
+/// `last_run` tick. It dereferences to that [`Tick`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{component::Tick, prelude::*, system::CurrentTick};
+/// #[derive(Resource, Default)]
+/// struct LastSeen(Option<Tick>);
+///
+/// fn remember(tick: CurrentTick, mut last_seen: ResMut<LastSeen>) {
+///     last_seen.0 = Some(*tick);
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<LastSeen>();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(remember);
+///
+/// schedule.run(&mut world);
+/// let first = world.resource::<LastSeen>().0.unwrap();
+/// schedule.run(&mut world);
+/// let second = world.resource::<LastSeen>().0.unwrap();
+/// assert!(second.is_newer_than(first, world.read_change_tick()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentTick(Tick);
+
+impl CurrentTick {
+    /// Returns the current [`World`] change tick seen by the system.
+    #[inline]
+    pub fn get(self) -> Tick {
+        self.0
+    }
+}
+
+impl Deref for CurrentTick {
+    type Target = Tick;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// SAFETY: Only reads internal system state
+unsafe impl ReadOnlySystemParam for CurrentTick {}
+
+// SAFETY: `CurrentTick` doesn't require any world access
+unsafe impl SystemParam for CurrentTick {
     type State = ();
-    type Item<'w, 's> = &'w Entities;
+    type Item<'w, 's> = CurrentTick;
 
     fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {}
 
-    #[inline]
     unsafe fn get_param<'w, 's>(
         _state: &'s mut Self::State,
         _system_meta: &SystemMeta,
-        world: UnsafeWorldCell<'w>,
-        _change_tick: Tick,
+        _world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
     ) -> Self::Item<'w, 's> {
-        world.entities()
+        CurrentTick(change_tick)
     }
 }
 
-// SAFETY: Only reads World bundles
-unsafe impl<'a> ReadOnlySystemParam for &'a Bundles {}
+/// A [`SystemParam`] that reads how many change ticks passed since the system last ran.
+///
+/// This is the difference between [`SystemChangeTick::this_run`] and
+/// [`SystemChangeTick::last_run`], which handles wraparound like change detection does: it is
+/// clamped to [`Tick::MAX`], the oldest a change tick can get. The first run of a system sees
+/// that maximum, like [`FirstRun`] does. The value dereferences to a `u32`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::TicksSinceLastRun};
+/// # #[derive(Resource, Default)]
+/// # struct Work(usize);
+/// fn rate_limited(ticks: TicksSinceLastRun, mut work: ResMut<Work>) {
+///     // Skip runs that are too close to the previous one.
+///     if *ticks < 100 {
+///         return;
+///     }
+///     work.0 += 1;
+/// }
+/// # bevy_ecs::system::assert_is_system(rate_limited);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TicksSinceLastRun(u32);
 
-// SAFETY: no component value access
-unsafe impl<'a> SystemParam for &'a Bundles {
+impl TicksSinceLastRun {
+    /// Returns the number of change ticks since the system last ran.
+    #[inline]
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl Deref for TicksSinceLastRun {
+    type Target = u32;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// SAFETY: Only reads internal system state
+unsafe impl ReadOnlySystemParam for TicksSinceLastRun {}
+
+// SAFETY: `TicksSinceLastRun` doesn't require any world access
+unsafe impl SystemParam for TicksSinceLastRun {
     type State = ();
-    type Item<'w, 's> = &'w Bundles;
+    type Item<'w, 's> = TicksSinceLastRun;
 
     fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {}
 
-    #[inline]
     unsafe fn get_param<'w, 's>(
         _state: &'s mut Self::State,
-        _system_meta: &SystemMeta,
-        world: UnsafeWorldCell<'w>,
-        _change_tick: Tick,
+        system_meta: &SystemMeta,
+        _world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
     ) -> Self::Item<'w, 's> {
-        world.bundles()
+        let ticks = change_tick.relative_to(system_meta.last_run).get();
+        TicksSinceLastRun(ticks.min(Tick::MAX.get()))
     }
 }
 
-/// A [`SystemParam`] that reads the previous and current change ticks of the system.
+/// A [`SystemParam`] that tells whether the system runs for the first time.
 ///
-/// A system's change ticks are updated each time it runs:
-/// - `last_run` copies the previous value of `change_tick`
-/// - `this_run` copies the current value of [`World::read_change_tick`]
+/// This dereferences to a `bool`, which replaces keeping track of the first run in a
+/// `Local<bool>`.
 ///
-/// Component change ticks that are more recent than `last_run` will be detected by the system.
-/// Those can be read by calling [`last_changed`](crate::change_detection::DetectChanges::last_changed)
-/// on a [`Mut<T>`](crate::change_detection::Mut) or [`ResMut<T>`](crate::change_detection::ResMut).
-#[derive(Debug)]
-pub struct SystemChangeTick {
-    last_run: Tick,
-    this_run: Tick,
-}
+/// When a system is initialized, its [`SystemChangeTick::last_run`] is set to [`Tick::MAX`]
+/// change ticks before the current [`World`] change tick, which is as old as a change tick can
+/// get. Every run sets it to the change tick of that run, so the system runs for the first time
+/// exactly if `last_run` is still that old. Since change ticks that old are clamped to the
+/// same age, a system that didn't run for [`Tick::MAX`] change ticks is considered to run for
+/// the first time again.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::FirstRun};
+/// # #[derive(Resource, Default)]
+/// # struct Spawned(usize);
+/// fn spawn_once(first_run: FirstRun, mut spawned: ResMut<Spawned>) {
+///     if *first_run {
+///         spawned.0 += 1;
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Spawned>();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(spawn_once);
+///
+/// schedule.run(&mut world);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Spawned>().0, 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FirstRun(bool);
 
-impl SystemChangeTick {
-    /// Returns the current [`World`] change tick seen by the system.
+impl FirstRun {
+    /// Returns `true` if the system runs for the first time.
     #[inline]
-    pub fn this_run(&self) -> Tick {
-        self.this_run
+    pub fn get(self) -> bool {
+        self.0
     }
+}
+
+impl Deref for FirstRun {
+    type Target = bool;
 
-    /// Returns the [`World`] change tick seen by the system the previous time it ran.
     #[inline]
-    pub fn last_run(&self) -> Tick {
-        self.last_run
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
 // SAFETY: Only reads internal system state
-unsafe impl ReadOnlySystemParam for SystemChangeTick {}
+unsafe impl ReadOnlySystemParam for FirstRun {}
 
-// SAFETY: `SystemChangeTick` doesn't require any world access
-unsafe impl SystemParam for SystemChangeTick {
+// SAFETY: `FirstRun` doesn't require any world access
+unsafe impl SystemParam for FirstRun {
     type State = ();
-    type Item<'w, 's> = SystemChangeTick;
+    type Item<'w, 's> = FirstRun;
 
     fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {}
 
@@ -1281,10 +3183,7 @@ unsafe impl SystemParam for SystemChangeTick {
         _world: UnsafeWorldCell<'w>,
         change_tick: Tick,
     ) -> Self::Item<'w, 's> {
-        SystemChangeTick {
-            last_run: system_meta.last_run,
-            this_run: change_tick,
-        }
+        FirstRun(change_tick.relative_to(system_meta.last_run).get() >= Tick::MAX.get())
     }
 }
 
@@ -1351,6 +3250,66 @@ unsafe impl SystemParam for SystemName<'_> {
 // SAFETY: Only reads internal system state
 unsafe impl<'s> ReadOnlySystemParam for SystemName<'s> {}
 
+/// A [`SystemParam`] that gives the name and the [`SystemInstanceId`] of the system.
+///
+/// The name is the same as with [`SystemName`], so it is shared by all instances of the same
+/// system, while the id tells them apart. Both stay the same across runs of the system, so they
+/// can correlate log messages of different frames.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::SystemInfo};
+/// # use bevy_utils::tracing::info;
+/// fn log_run(info: SystemInfo) {
+///     info!(system = info.name(), id = ?info.instance_id(), "running");
+/// }
+/// # bevy_ecs::system::assert_is_system(log_run);
+/// ```
+#[derive(Debug)]
+pub struct SystemInfo<'s> {
+    name: &'s str,
+    instance_id: SystemInstanceId,
+}
+
+impl<'s> SystemInfo<'s> {
+    /// Gets the name of the system.
+    pub fn name(&self) -> &'s str {
+        self.name
+    }
+
+    /// Gets the id of the system instance.
+    pub fn instance_id(&self) -> SystemInstanceId {
+        self.instance_id
+    }
+}
+
+// SAFETY: no component value access
+unsafe impl SystemParam for SystemInfo<'_> {
+    type State = (Cow<'static, str>, SystemInstanceId);
+    type Item<'w, 's> = SystemInfo<'s>;
+
+    fn init_state(_world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        (system_meta.name.clone(), system_meta.instance_id())
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        (name, instance_id): &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        _world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        SystemInfo {
+            name,
+            instance_id: *instance_id,
+        }
+    }
+}
+
+// SAFETY: Only reads internal system state
+unsafe impl<'s> ReadOnlySystemParam for SystemInfo<'s> {}
+
 macro_rules! impl_system_param_tuple {
     ($($param: ident),*) => {
         // SAFETY: tuple consists only of ReadOnlySystemParams
@@ -1365,12 +3324,20 @@ macro_rules! impl_system_param_tuple {
 
             #[inline]
             fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {
+                #[cfg(feature = "param_timings")]
+                _system_meta.init_param_timings(_world);
                 (($($param::init_state(_world, _system_meta),)*))
             }
 
             #[inline]
             fn new_archetype(($($param,)*): &mut Self::State, _archetype: &Archetype, _system_meta: &mut SystemMeta) {
-                $($param::new_archetype($param, _archetype, _system_meta);)*
+                $(
+                    #[cfg(feature = "param_timings")]
+                    let start = bevy_utils::Instant::now();
+                    $param::new_archetype($param, _archetype, _system_meta);
+                    #[cfg(feature = "param_timings")]
+                    _system_meta.record_new_archetype::<$param>(start.elapsed());
+                )*
             }
 
             #[inline]
@@ -1378,6 +3345,16 @@ macro_rules! impl_system_param_tuple {
                 $($param::apply($param, _system_meta, _world);)*
             }
 
+            #[inline]
+            unsafe fn validate_param(
+                state: &Self::State,
+                _system_meta: &SystemMeta,
+                _world: UnsafeWorldCell,
+            ) -> bool {
+                let ($($param,)*) = state;
+                true $(&& $param::validate_param($param, _system_meta, _world))*
+            }
+
             #[inline]
             #[allow(clippy::unused_unit)]
             unsafe fn get_param<'w, 's>(
@@ -1388,7 +3365,14 @@ macro_rules! impl_system_param_tuple {
             ) -> Self::Item<'w, 's> {
 
                 let ($($param,)*) = state;
-                ($($param::get_param($param, _system_meta, _world, _change_tick),)*)
+                ($({
+                    #[cfg(feature = "param_timings")]
+                    let start = bevy_utils::Instant::now();
+                    let item = $param::get_param($param, _system_meta, _world, _change_tick);
+                    #[cfg(feature = "param_timings")]
+                    _system_meta.record_get_param::<$param>(start.elapsed());
+                    item
+                },)*)
             }
         }
     };
@@ -1404,7 +3388,7 @@ all_tuples!(impl_system_param_tuple, 0, 16, P);
 /// This purely exists for convenience.
 ///
 /// You can't instantiate a static `SystemParam`, you'll always end up with
-/// `Res<'w, T>`, `ResMut<'w, T>` or `&'w T` bound to the lifetime of the provided
+/// `Res<'w, T>`, `ResMut<'w, T>`, `NonSend<'w, T>` or `&'w T` bound to the lifetime of the provided
 /// `&'w World`.
 ///
 /// [`SystemParam`]: super::SystemParam
@@ -1421,6 +3405,12 @@ pub mod lifetimeless {
     pub type SResMut<T> = super::ResMut<'static, T>;
     /// [`Commands`](crate::system::Commands) with `'static` lifetimes.
     pub type SCommands = crate::system::Commands<'static, 'static>;
+    /// A [`NonSend`](super::NonSend) with `'static` lifetimes.
+    pub type SNonSend<T> = super::NonSend<'static, T>;
+    /// A [`NonSendMut`](super::NonSendMut) with `'static` lifetimes.
+    pub type SNonSendMut<T> = super::NonSendMut<'static, T>;
+    /// A [`Local`](super::Local) with `'static` lifetimes.
+    pub type SLocal<T> = super::Local<'static, T>;
 }
 
 /// A helper for using system parameters in generic contexts
@@ -1430,84 +3420,226 @@ pub mod lifetimeless {
 /// no matter the argument [`SystemParam`] (`P`) (other than
 /// that `P` must be `'static`)
 ///
-/// This makes it useful for having arbitrary [`SystemParam`] type arguments
-/// to function systems, or for generic types using the [`derive@SystemParam`]
-/// derive:
+/// This makes it useful for having arbitrary [`SystemParam`] type arguments
+/// to function systems, or for generic types using the [`derive@SystemParam`]
+/// derive:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::system::{SystemParam, StaticSystemParam};
+/// #[derive(SystemParam)]
+/// struct GenericParam<'w,'s, T: SystemParam + 'static> {
+///     field: StaticSystemParam<'w, 's, T>,
+/// }
+/// fn do_thing_generically<T: SystemParam + 'static>(t: StaticSystemParam<T>) {}
+///
+/// fn check_always_is_system<T: SystemParam + 'static>(){
+///     bevy_ecs::system::assert_is_system(do_thing_generically::<T>);
+/// }
+/// ```
+/// Note that in a real case you'd generally want
+/// additional bounds on `P`, for your use of the parameter
+/// to have a reason to be generic.
+///
+/// For example, using this would allow a type to be generic over
+/// whether a resource is accessed mutably or not, with
+/// impls being bounded on [`P: Deref<Target=MyType>`](Deref), and
+/// [`P: DerefMut<Target=MyType>`](DerefMut) depending on whether the
+/// method requires mutable access or not.
+///
+/// The method which doesn't use this type will not compile:
+/// ```compile_fail
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{SystemParam, StaticSystemParam};
+///
+/// fn do_thing_generically<T: SystemParam + 'static>(t: T) {}
+///
+/// #[derive(SystemParam)]
+/// struct GenericParam<'w, 's, T: SystemParam> {
+///     field: T,
+///     // Use the lifetimes in this type, or they will be unbound.
+///     phantom: core::marker::PhantomData<&'w &'s ()>
+/// }
+/// # fn check_always_is_system<T: SystemParam + 'static>(){
+/// #    bevy_ecs::system::assert_is_system(do_thing_generically::<T>);
+/// # }
+/// ```
+///
+pub struct StaticSystemParam<'w, 's, P: SystemParam>(SystemParamItem<'w, 's, P>);
+
+impl<'w, 's, P: SystemParam> Deref for StaticSystemParam<'w, 's, P> {
+    type Target = SystemParamItem<'w, 's, P>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'w, 's, P: SystemParam> DerefMut for StaticSystemParam<'w, 's, P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'w, 's, P: SystemParam> AsRef<SystemParamItem<'w, 's, P>> for StaticSystemParam<'w, 's, P> {
+    fn as_ref(&self) -> &SystemParamItem<'w, 's, P> {
+        &self.0
+    }
+}
+
+impl<'w, 's, P: SystemParam> AsMut<SystemParamItem<'w, 's, P>> for StaticSystemParam<'w, 's, P> {
+    fn as_mut(&mut self) -> &mut SystemParamItem<'w, 's, P> {
+        &mut self.0
+    }
+}
+
+impl<'w, 's, P: SystemParam> StaticSystemParam<'w, 's, P> {
+    /// Get the value of the parameter
+    pub fn into_inner(self) -> SystemParamItem<'w, 's, P> {
+        self.0
+    }
+
+    /// Get a reference to the value of the parameter.
+    ///
+    /// This is the same as dereferencing, but reads better in generic code.
+    #[inline]
+    pub fn get(&self) -> &SystemParamItem<'w, 's, P> {
+        &self.0
+    }
+
+    /// Get a mutable reference to the value of the parameter.
+    ///
+    /// This is the same as dereferencing mutably, but reads better in generic code.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut SystemParamItem<'w, 's, P> {
+        &mut self.0
+    }
+}
+
+// SAFETY: This doesn't add any more reads, and the delegated fetch confirms it
+unsafe impl<'w, 's, P: ReadOnlySystemParam + 'static> ReadOnlySystemParam
+    for StaticSystemParam<'w, 's, P>
+{
+}
+
+// SAFETY: all methods are just delegated to `P`'s `SystemParam` implementation
+unsafe impl<P: SystemParam + 'static> SystemParam for StaticSystemParam<'_, '_, P> {
+    type State = P::State;
+    type Item<'world, 'state> = StaticSystemParam<'world, 'state, P>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        P::init_state(world, system_meta)
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        P::new_archetype(state, archetype, system_meta);
+    }
+
+    fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
+        P::apply(state, system_meta, world);
+    }
+
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Defer to the safety of P::SystemParam
+        P::validate_param(state, system_meta, world)
+    }
+
+    unsafe fn get_param<'world, 'state>(
+        state: &'state mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'world>,
+        change_tick: Tick,
+    ) -> Self::Item<'world, 'state> {
+        // SAFETY: Defer to the safety of P::SystemParam
+        StaticSystemParam(P::get_param(state, system_meta, world, change_tick))
+    }
+}
+
+/// Maps the item of the [`SystemParam`] `P` into another type, for a [`MappedParam`].
+///
+/// This is implemented by a marker type, since the mapping has to be known from the type of
+/// the parameter alone. Like the parameter of a [`StaticSystemParam`], `P` is `'static`, e.g.
+/// `Res<'static, T>`.
+pub trait SystemParamMapper<P: SystemParam>: 'static {
+    /// The type the item of `P` is mapped into.
+    type Output<'w, 's>;
+
+    /// Maps the item of `P` into the output.
+    fn map<'w, 's>(item: SystemParamItem<'w, 's, P>) -> Self::Output<'w, 's>;
+}
+
+/// A [`SystemParam`] adapter mapping the item of `P` with the [`SystemParamMapper`] `M`.
+///
+/// This wraps a parameter into a domain specific type without deriving a new
+/// [`SystemParam`]. It accesses the world exactly like `P` does, and is a
+/// [`ReadOnlySystemParam`] if `P` is one. The mapped value is available through [`Deref`],
+/// [`DerefMut`] or [`MappedParam::into_inner`], like the item of a [`StaticSystemParam`].
 ///
 /// ```
 /// # use bevy_ecs::prelude::*;
-/// use bevy_ecs::system::{SystemParam, StaticSystemParam};
-/// #[derive(SystemParam)]
-/// struct GenericParam<'w,'s, T: SystemParam + 'static> {
-///     field: StaticSystemParam<'w, 's, T>,
-/// }
-/// fn do_thing_generically<T: SystemParam + 'static>(t: StaticSystemParam<T>) {}
+/// use bevy_ecs::system::{MappedParam, SystemParamMapper};
 ///
-/// fn check_always_is_system<T: SystemParam + 'static>(){
-///     bevy_ecs::system::assert_is_system(do_thing_generically::<T>);
+/// #[derive(Resource)]
+/// struct Config {
+///     difficulty: u32,
 /// }
-/// ```
-/// Note that in a real case you'd generally want
-/// additional bounds on `P`, for your use of the parameter
-/// to have a reason to be generic.
 ///
-/// For example, using this would allow a type to be generic over
-/// whether a resource is accessed mutably or not, with
-/// impls being bounded on [`P: Deref<Target=MyType>`](Deref), and
-/// [`P: DerefMut<Target=MyType>`](DerefMut) depending on whether the
-/// method requires mutable access or not.
+/// struct Difficulty;
 ///
-/// The method which doesn't use this type will not compile:
-/// ```compile_fail
-/// # use bevy_ecs::prelude::*;
-/// # use bevy_ecs::system::{SystemParam, StaticSystemParam};
+/// impl SystemParamMapper<Res<'static, Config>> for Difficulty {
+///     type Output<'w, 's> = u32;
 ///
-/// fn do_thing_generically<T: SystemParam + 'static>(t: T) {}
+///     fn map<'w, 's>(config: Res<'w, Config>) -> u32 {
+///         config.difficulty
+///     }
+/// }
 ///
-/// #[derive(SystemParam)]
-/// struct GenericParam<'w, 's, T: SystemParam> {
-///     field: T,
-///     // Use the lifetimes in this type, or they will be unbound.
-///     phantom: core::marker::PhantomData<&'w &'s ()>
+/// fn spawn_enemies(difficulty: MappedParam<Res<'static, Config>, Difficulty>) {
+///     for _ in 0..*difficulty {
+///         // ...
+///     }
 /// }
-/// # fn check_always_is_system<T: SystemParam + 'static>(){
-/// #    bevy_ecs::system::assert_is_system(do_thing_generically::<T>);
-/// # }
+/// # bevy_ecs::system::assert_is_read_only_system(spawn_enemies);
 /// ```
-///
-pub struct StaticSystemParam<'w, 's, P: SystemParam>(SystemParamItem<'w, 's, P>);
+pub struct MappedParam<'w, 's, P: SystemParam, M: SystemParamMapper<P>>(M::Output<'w, 's>);
 
-impl<'w, 's, P: SystemParam> Deref for StaticSystemParam<'w, 's, P> {
-    type Target = SystemParamItem<'w, 's, P>;
+impl<'w, 's, P: SystemParam, M: SystemParamMapper<P>> Deref for MappedParam<'w, 's, P, M> {
+    type Target = M::Output<'w, 's>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<'w, 's, P: SystemParam> DerefMut for StaticSystemParam<'w, 's, P> {
+impl<'w, 's, P: SystemParam, M: SystemParamMapper<P>> DerefMut for MappedParam<'w, 's, P, M> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
 
-impl<'w, 's, P: SystemParam> StaticSystemParam<'w, 's, P> {
-    /// Get the value of the parameter
-    pub fn into_inner(self) -> SystemParamItem<'w, 's, P> {
+impl<'w, 's, P: SystemParam, M: SystemParamMapper<P>> MappedParam<'w, 's, P, M> {
+    /// Get the mapped value of the parameter.
+    pub fn into_inner(self) -> M::Output<'w, 's> {
         self.0
     }
 }
 
 // SAFETY: This doesn't add any more reads, and the delegated fetch confirms it
-unsafe impl<'w, 's, P: ReadOnlySystemParam + 'static> ReadOnlySystemParam
-    for StaticSystemParam<'w, 's, P>
+unsafe impl<'w, 's, P: ReadOnlySystemParam + 'static, M: SystemParamMapper<P>> ReadOnlySystemParam
+    for MappedParam<'w, 's, P, M>
 {
 }
 
 // SAFETY: all methods are just delegated to `P`'s `SystemParam` implementation
-unsafe impl<P: SystemParam + 'static> SystemParam for StaticSystemParam<'_, '_, P> {
+unsafe impl<P: SystemParam + 'static, M: SystemParamMapper<P>> SystemParam
+    for MappedParam<'_, '_, P, M>
+{
     type State = P::State;
-    type Item<'world, 'state> = StaticSystemParam<'world, 'state, P>;
+    type Item<'world, 'state> = MappedParam<'world, 'state, P, M>;
 
     fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
         P::init_state(world, system_meta)
@@ -1521,6 +3653,15 @@ unsafe impl<P: SystemParam + 'static> SystemParam for StaticSystemParam<'_, '_,
         P::apply(state, system_meta, world);
     }
 
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Defer to the safety of P::SystemParam
+        P::validate_param(state, system_meta, world)
+    }
+
     unsafe fn get_param<'world, 'state>(
         state: &'state mut Self::State,
         system_meta: &SystemMeta,
@@ -1528,7 +3669,7 @@ unsafe impl<P: SystemParam + 'static> SystemParam for StaticSystemParam<'_, '_,
         change_tick: Tick,
     ) -> Self::Item<'world, 'state> {
         // SAFETY: Defer to the safety of P::SystemParam
-        StaticSystemParam(P::get_param(state, system_meta, world, change_tick))
+        MappedParam(M::map(P::get_param(state, system_meta, world, change_tick)))
     }
 }
 
@@ -1552,13 +3693,237 @@ unsafe impl<T: ?Sized> SystemParam for PhantomData<T> {
 // SAFETY: No world access.
 unsafe impl<T: ?Sized> ReadOnlySystemParam for PhantomData<T> {}
 
+/// A [`SystemParam`] whose type is chosen at runtime.
+///
+/// The underlying parameter is given with a [`DynParamBuilder`](super::DynParamBuilder) when
+/// building the system, which registers the world accesses of that parameter like it would for a
+/// system using it directly. In the system, it can be downcast back to that parameter type.
+/// Without a builder, the underlying parameter is `()`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{
+/// #     prelude::*,
+/// #     system::{DynParamBuilder, DynSystemParam, LocalBuilder, ParamBuilder, SystemParamBuilder},
+/// # };
+/// #
+/// # #[derive(Resource)]
+/// # struct Score(u32);
+/// #
+/// let mut world = World::new();
+/// world.insert_resource(Score(5));
+///
+/// // The parameter types could be chosen from a data file instead.
+/// let mut system = (
+///     DynParamBuilder::new::<Res<Score>>(ParamBuilder),
+///     DynParamBuilder::new(LocalBuilder(10_u32)),
+/// )
+///     .build_state(&mut world)
+///     .build_system(|mut p0: DynSystemParam, mut p1: DynSystemParam| {
+///         let score = p0.downcast_mut::<Res<Score>>().unwrap().0;
+///         let bonus = *p1.downcast_mut::<Local<u32>>().unwrap();
+///         // Downcasting to a different type fails.
+///         assert!(p1.downcast_mut::<Res<Score>>().is_none());
+///         score + bonus
+///     });
+///
+/// assert_eq!(system.run((), &mut world), 15);
+/// ```
+pub struct DynSystemParam<'w, 's> {
+    /// A `ParamState<T>` wrapping the state of the underlying parameter.
+    state: &'s mut dyn Any,
+    world: UnsafeWorldCell<'w>,
+    system_meta: SystemMeta,
+    change_tick: Tick,
+}
+
+impl<'w, 's> DynSystemParam<'w, 's> {
+    /// Returns `true` if the underlying parameter has the type `T`.
+    pub fn is<T: SystemParam>(&self) -> bool
+    where
+        T::Item<'static, 'static>: SystemParam<Item<'w, 's> = T> + 'static,
+    {
+        self.state.is::<ParamState<T::Item<'static, 'static>>>()
+    }
+
+    /// Returns the underlying parameter, or `None` if it doesn't have the type `T`.
+    ///
+    /// This consumes the `DynSystemParam`, so the parameter keeps the original lifetimes.
+    pub fn downcast<T: SystemParam>(self) -> Option<T>
+    where
+        T::Item<'static, 'static>: SystemParam<Item<'w, 's> = T> + 'static,
+    {
+        // SAFETY: The world accesses of the underlying parameter were registered by the builder,
+        // and the state was created for the same world.
+        unsafe { downcast::<T>(self.state, &self.system_meta, self.world, self.change_tick) }
+    }
+
+    /// Returns the underlying parameter, or `None` if it doesn't have the type `T`.
+    ///
+    /// This borrows the `DynSystemParam`, so the parameter can only be used during that borrow,
+    /// and the `DynSystemParam` can be downcast again afterwards.
+    pub fn downcast_mut<'a, T: SystemParam>(&'a mut self) -> Option<T>
+    where
+        T::Item<'static, 'static>: SystemParam<Item<'a, 'a> = T> + 'static,
+    {
+        // SAFETY: The world accesses of the underlying parameter were registered by the builder,
+        // and the state was created for the same world. Borrowing `self` mutably ensures that
+        // only one parameter is created from the state at a time.
+        unsafe { downcast::<T>(self.state, &self.system_meta, self.world, self.change_tick) }
+    }
+
+    /// Returns the underlying read-only parameter, or `None` if it doesn't have the type `T`.
+    ///
+    /// Like [`downcast_mut`](Self::downcast_mut), this borrows the `DynSystemParam`, but since the
+    /// parameter only reads the world it keeps the original world lifetime.
+    pub fn downcast_mut_inner<'a, T: ReadOnlySystemParam>(&'a mut self) -> Option<T>
+    where
+        T::Item<'static, 'static>: SystemParam<Item<'w, 'a> = T> + 'static,
+    {
+        // SAFETY: The world accesses of the underlying parameter were registered by the builder,
+        // and the state was created for the same world. The parameter only reads the world, so
+        // it can't conflict with other parameters created from the state.
+        unsafe { downcast::<T>(self.state, &self.system_meta, self.world, self.change_tick) }
+    }
+}
+
+/// Gets the parameter of type `T` from a `ParamState<T>`, or returns `None` if `state` has a
+/// different type.
+///
+/// `T::Item<'static, 'static>` is the `'static` version of `T` required for downcasting, and the
+/// `where` clause requires that it turns back into `T` with the right lifetimes, which holds for
+/// all parameter types since their `Item` only differs from them in the lifetimes.
+///
+/// # Safety
+///
+/// - `world` must have access to the world data registered for the underlying parameter.
+/// - `world` must be the same [`World`] the state was created for.
+unsafe fn downcast<'w, 's, T: SystemParam>(
+    state: &'s mut dyn Any,
+    system_meta: &SystemMeta,
+    world: UnsafeWorldCell<'w>,
+    change_tick: Tick,
+) -> Option<T>
+where
+    T::Item<'static, 'static>: SystemParam<Item<'w, 's> = T> + 'static,
+{
+    state
+        .downcast_mut::<ParamState<T::Item<'static, 'static>>>()
+        .map(|state| {
+            // SAFETY: The downcast succeeded, so the underlying parameter is `T`, and the caller
+            // ensures that `world` has access to its data and is the right world.
+            unsafe {
+                T::Item::<'static, 'static>::get_param(
+                    &mut state.0,
+                    system_meta,
+                    world,
+                    change_tick,
+                )
+            }
+        })
+}
+
+/// The [`SystemParam::State`] of a [`DynSystemParam`].
+pub struct DynSystemParamState(Box<dyn DynParamState>);
+
+impl DynSystemParamState {
+    pub(crate) fn new<T: SystemParam + 'static>(state: T::State) -> Self {
+        Self(Box::new(ParamState::<T>(state)))
+    }
+}
+
+/// The methods of [`SystemParam`] that take its state, for a type-erased state.
+trait DynParamState: Sync + Send {
+    /// Casts the underlying `ParamState<T>` to `Any`, so it can be downcast.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// See [`SystemParam::new_archetype`].
+    fn new_archetype(&mut self, archetype: &Archetype, system_meta: &mut SystemMeta);
+
+    /// See [`SystemParam::apply`].
+    fn apply(&mut self, system_meta: &SystemMeta, world: &mut World);
+
+    /// See [`SystemParam::validate_param`].
+    ///
+    /// # Safety
+    ///
+    /// Same as [`SystemParam::validate_param`].
+    unsafe fn validate_param(&self, system_meta: &SystemMeta, world: UnsafeWorldCell) -> bool;
+}
+
+/// The state of a [`SystemParam`] of type `T`, as a [`DynParamState`].
+struct ParamState<T: SystemParam>(T::State);
+
+impl<T: SystemParam + 'static> DynParamState for ParamState<T> {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        T::new_archetype(&mut self.0, archetype, system_meta);
+    }
+
+    fn apply(&mut self, system_meta: &SystemMeta, world: &mut World) {
+        T::apply(&mut self.0, system_meta, world);
+    }
+
+    unsafe fn validate_param(&self, system_meta: &SystemMeta, world: UnsafeWorldCell) -> bool {
+        T::validate_param(&self.0, system_meta, world)
+    }
+}
+
+// SAFETY: `init_state` creates the state of `()`, which doesn't access the world. The builder
+// registers the accesses of the underlying parameter.
+unsafe impl SystemParam for DynSystemParam<'_, '_> {
+    type State = DynSystemParamState;
+    type Item<'world, 'state> = DynSystemParam<'world, 'state>;
+
+    fn init_state(_world: &mut World, _system_meta: &mut SystemMeta) -> Self::State {
+        DynSystemParamState::new::<()>(())
+    }
+
+    fn new_archetype(state: &mut Self::State, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        state.0.new_archetype(archetype, system_meta);
+    }
+
+    fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
+        state.0.apply(system_meta, world);
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Defer to the safety of the underlying parameter.
+        state.0.validate_param(system_meta, world)
+    }
+
+    #[inline]
+    unsafe fn get_param<'world, 'state>(
+        state: &'state mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'world>,
+        change_tick: Tick,
+    ) -> Self::Item<'world, 'state> {
+        DynSystemParam {
+            state: state.0.as_any_mut(),
+            world,
+            system_meta: system_meta.clone(),
+            change_tick,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         self as bevy_ecs, // Necessary for the `SystemParam` Derive when used inside `bevy_ecs`.
         query::{ReadOnlyWorldQuery, WorldQuery},
-        system::{assert_is_system, Query},
+        system::{assert_is_read_only_system, assert_is_system, Query},
     };
     use std::{cell::RefCell, marker::PhantomData};
 
@@ -1672,6 +4037,98 @@ mod tests {
         assert_is_system(my_system);
     }
 
+    // Compile test for the builders generated by `#[system_param(builder)]`.
+    #[test]
+    fn system_param_derive_builder() {
+        use crate::system::{LocalBuilder, ParamBuilder, SystemParamBuilder};
+
+        #[derive(SystemParam)]
+        #[system_param(builder)]
+        pub struct NamedParam<'w, 's, T: Resource> {
+            _res: Res<'w, T>,
+            _local: Local<'s, u32>,
+            #[system_param(ignore)]
+            _ignored: u32,
+        }
+
+        #[derive(SystemParam)]
+        #[system_param(builder)]
+        pub struct TupleParam<'w, 's, T: Resource>(
+            #[system_param(ignore)] u32,
+            Res<'w, T>,
+            Local<'s, u32>,
+        );
+
+        #[derive(SystemParam)]
+        #[system_param(builder)]
+        pub struct UnitParam;
+
+        let mut world = World::new();
+        world.insert_resource(R::<0>);
+
+        let named = NamedParamBuilder {
+            _res: ParamBuilder,
+            _local: LocalBuilder(1),
+        };
+        let tuple = TupleParamBuilder(ParamBuilder, LocalBuilder(2));
+        (named, tuple, UnitParamBuilder {})
+            .build_state(&mut world)
+            .build_system(|_: NamedParam<R<0>>, _: TupleParam<R<0>>, _: UnitParam| {});
+    }
+
+    // Compile test for named `ParamSet` parameters.
+    #[test]
+    fn system_param_named_param_set() {
+        #[derive(SystemParam)]
+        pub struct NamedSet<'w, 's> {
+            #[system_param(param_set(first, second))]
+            set: ParamSet<'w, 's, (ResMut<'w, R<0>>, Res<'w, R<0>>)>,
+        }
+
+        #[derive(SystemParam)]
+        pub struct TupleNamedSet<'w, 's>(
+            #[system_param(param_set(only))] ParamSet<'w, 's, (Query<'w, 's, ()>,)>,
+        );
+
+        fn my_system(mut set: NamedSet, mut tuple_set: TupleNamedSet) {
+            let _: ResMut<R<0>> = set.first();
+            let _: Res<R<0>> = set.second();
+            let _: Query<()> = tuple_set.only();
+        }
+        assert_is_system(my_system);
+    }
+
+    // Compile test for ignored fields.
+    #[test]
+    fn system_param_ignored_fields() {
+        #[derive(Default)]
+        pub struct Config {
+            _scale: f32,
+        }
+
+        #[derive(SystemParam)]
+        pub struct IgnoredParam<'w, 's> {
+            query: Query<'w, 's, ()>,
+            #[system_param(ignore)]
+            config: Config,
+            #[system_param(ignore)]
+            count: usize,
+            res: Res<'w, R<0>>,
+        }
+
+        #[derive(SystemParam)]
+        pub struct TupleIgnoredParam<'w>(Res<'w, R<1>>, #[system_param(ignore)] Option<u32>);
+
+        fn my_system(param: IgnoredParam, tuple_param: TupleIgnoredParam) {
+            let _: &Query<()> = &param.query;
+            let _: &Res<R<0>> = &param.res;
+            let _: &Config = &param.config;
+            assert_eq!(param.count, 0);
+            assert_eq!(tuple_param.1, None);
+        }
+        assert_is_system(my_system);
+    }
+
     // Regression test for https://github.com/bevyengine/bevy/issues/4200.
     #[test]
     fn system_param_private_fields() {
@@ -1700,6 +4157,26 @@ mod tests {
         assert_is_system(my_system);
     }
 
+    // Compile test combining a tuple struct, const and type generics, and a where clause.
+    #[test]
+    fn system_param_tuple_const_generics_where_clause() {
+        #[derive(SystemParam)]
+        pub struct MixedParam<'w, const I: usize, T: Resource>(Res<'w, R<I>>, Res<'w, T>)
+        where
+            T: Send;
+
+        #[derive(Resource)]
+        struct Other;
+
+        fn my_system(param: MixedParam<0, Other>, other: MixedParam<1000, R<0>>) {
+            let _: &Res<R<0>> = &param.0;
+            let _: &Res<Other> = &param.1;
+            let _: &Res<R<1000>> = &other.0;
+        }
+        assert_is_system(my_system);
+        assert_is_read_only_system(my_system);
+    }
+
     // Regression test for https://github.com/bevyengine/bevy/issues/1727.
     #[test]
     fn system_param_name_collision() {
@@ -1740,6 +4217,164 @@ mod tests {
         schedule.run(&mut world);
     }
 
+    #[test]
+    fn param_set_all_read() {
+        #[derive(crate::component::Component)]
+        struct A(usize);
+
+        #[derive(crate::component::Component)]
+        struct B;
+
+        fn sum_both(
+            mut set: ParamSet<(Query<&A>, Query<&A, crate::query::With<B>>, Local<usize>)>,
+        ) {
+            let (all, with_b, mut runs) = set.all_read();
+            *runs += 1;
+            let total = all.iter().map(|a| a.0).sum::<usize>();
+            let total_with_b = with_b.iter().map(|a| a.0).sum::<usize>();
+            assert_eq!((total, total_with_b, *runs), (3, 2, 1));
+        }
+
+        let mut world = World::new();
+        world.spawn(A(1));
+        world.spawn((A(2), B));
+        let mut schedule = crate::schedule::Schedule::default();
+        schedule.add_systems(sum_both);
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn param_set_introspection() {
+        use crate::system::{IntoSystem, System};
+
+        #[derive(crate::component::Component)]
+        struct A;
+
+        fn inspect(
+            set: ParamSet<(Query<&A>, Query<&mut A>, ResMut<R<0>>, Local<usize>)>,
+            mut checked: Local<bool>,
+        ) {
+            let read_only: Vec<_> = (0..4).map(|index| set.is_read_only(index)).collect();
+            assert_eq!(
+                read_only,
+                [Some(true), Some(false), Some(false), Some(true)]
+            );
+            assert_eq!(set.is_read_only(4), None);
+            *checked = true;
+        }
+
+        assert_eq!(
+            ParamSet::<(Query<&A>, Query<&mut A>, ResMut<R<0>>, Local<usize>)>::LEN,
+            4
+        );
+        assert_eq!(ParamSet::<(Local<usize>,)>::LEN, 1);
+
+        let mut world = World::new();
+        world.insert_resource(R::<0>);
+        let mut system = IntoSystem::into_system(inspect);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+    }
+
+    #[test]
+    fn empty_param_set() {
+        use crate::system::{assert_is_read_only_system, IntoSystem, System};
+
+        fn empty(set: ParamSet<()>) -> Option<bool> {
+            set.is_read_only(0)
+        }
+
+        assert_eq!(ParamSet::<()>::LEN, 0);
+        assert_is_read_only_system(empty);
+
+        let mut world = World::new();
+        let mut system = IntoSystem::into_system(empty);
+        system.initialize(&mut world);
+        assert_eq!(system.run((), &mut world), None);
+        assert!(!system.component_access().has_any_read());
+    }
+
+    // Compile test for the `lifetimeless` aliases.
+    #[test]
+    fn lifetimeless_system_params() {
+        use super::lifetimeless::{SLocal, SNonSend, SNonSendMut};
+
+        struct NonSendA(u32);
+        struct NonSendB(u32);
+
+        fn generic_system<P: SystemParam + 'static>(_: StaticSystemParam<P>) {}
+
+        assert_is_system(generic_system::<(SNonSend<NonSendA>, SNonSendMut<NonSendB>)>);
+        assert_is_system(generic_system::<SLocal<u32>>);
+    }
+
+    #[test]
+    fn static_system_param_accessors() {
+        #[derive(crate::system::Resource)]
+        struct R(u32);
+
+        fn increment<P: SystemParam + 'static>(mut param: StaticSystemParam<P>)
+        where
+            for<'w, 's> SystemParamItem<'w, 's, P>: DerefMut<Target = R>,
+        {
+            let before = param.get().0;
+            param.get_mut().0 += 1;
+            assert_eq!(param.as_ref().0, before + 1);
+            param.as_mut().0 += 1;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(R(0));
+        let mut schedule = crate::schedule::Schedule::default();
+        schedule.add_systems(increment::<ResMut<R>>);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<R>().0, 2);
+    }
+
+    #[test]
+    fn mapped_param() {
+        #[derive(crate::system::Resource)]
+        struct R(u32);
+
+        struct Value;
+
+        impl SystemParamMapper<Res<'static, R>> for Value {
+            type Output<'w, 's> = u32;
+
+            fn map<'w, 's>(r: Res<'w, R>) -> u32 {
+                r.0
+            }
+        }
+
+        struct ValueMut;
+
+        impl SystemParamMapper<ResMut<'static, R>> for ValueMut {
+            type Output<'w, 's> = crate::change_detection::Mut<'w, u32>;
+
+            fn map<'w, 's>(r: ResMut<'w, R>) -> crate::change_detection::Mut<'w, u32> {
+                r.map_unchanged(|r| &mut r.0)
+            }
+        }
+
+        fn increment(mut value: MappedParam<ResMut<'static, R>, ValueMut>) {
+            **value += 1;
+        }
+
+        fn read(value: MappedParam<Res<'static, R>, Value>) {
+            assert_eq!(*value, 1);
+            assert_eq!(value.into_inner(), 1);
+        }
+
+        crate::system::assert_is_read_only_system(read);
+
+        let mut world = World::new();
+        world.insert_resource(R(0));
+        let mut schedule = crate::schedule::Schedule::default();
+        schedule.add_systems((increment, read).chain());
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<R>().0, 1);
+    }
+
     // Regression test for https://github.com/bevyengine/bevy/issues/10207.
     #[test]
     fn param_set_non_send_first() {
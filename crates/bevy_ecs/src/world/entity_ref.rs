@@ -4,6 +4,7 @@ use crate::{
     change_detection::MutUntyped,
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
+    query::Access,
     removal_detection::RemovedComponentEvents,
     storage::Storages,
     world::{Mut, World},
@@ -154,6 +155,99 @@ impl<'w> EntityRef<'w> {
     }
 }
 
+/// A read-only reference to a particular [`Entity`] and the subset of its components given by
+/// an [`Access`].
+///
+/// This is the item of queries built at runtime from [`ComponentId`]s, see
+/// [`QueryParamBuilder`](crate::system::QueryParamBuilder). Components outside of the access
+/// can't be read, as if the entity didn't have them.
+#[derive(Clone)]
+pub struct FilteredEntityRef<'w> {
+    entity: UnsafeEntityCell<'w>,
+    access: Access<ComponentId>,
+}
+
+impl<'w> FilteredEntityRef<'w> {
+    /// # Safety
+    /// - `cell` must have permission to read every component of the entity in `access`.
+    /// - No mutable accesses to any of those components may exist at the same time as the
+    ///   returned [`FilteredEntityRef`].
+    #[inline]
+    pub(crate) unsafe fn new(entity: UnsafeEntityCell<'w>, access: Access<ComponentId>) -> Self {
+        Self { entity, access }
+    }
+
+    /// Returns the [ID](Entity) of the current entity.
+    #[inline]
+    #[must_use = "Omit the .id() call if you do not need to store the `Entity` identifier."]
+    pub fn id(&self) -> Entity {
+        self.entity.id()
+    }
+
+    /// Gets metadata indicating the location where the current entity is stored.
+    #[inline]
+    pub fn location(&self) -> EntityLocation {
+        self.entity.location()
+    }
+
+    /// Returns the archetype that the current entity belongs to.
+    #[inline]
+    pub fn archetype(&self) -> &Archetype {
+        self.entity.archetype()
+    }
+
+    /// Returns the components this reference is allowed to read.
+    #[inline]
+    pub fn access(&self) -> &Access<ComponentId> {
+        &self.access
+    }
+
+    /// Returns `true` if the current entity has a component of type `T`.
+    /// Otherwise, this returns `false`.
+    ///
+    /// This doesn't depend on the access, use [`Self::get`] to check whether the component can
+    /// be read.
+    #[inline]
+    pub fn contains<T: Component>(&self) -> bool {
+        self.entity.contains::<T>()
+    }
+
+    /// Returns `true` if the current entity has a component identified by `component_id`.
+    /// Otherwise, this returns false.
+    #[inline]
+    pub fn contains_id(&self, component_id: ComponentId) -> bool {
+        self.entity.contains_id(component_id)
+    }
+
+    /// Gets access to the component of type `T` for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`, or if the
+    /// access doesn't allow reading it.
+    #[inline]
+    pub fn get<T: Component>(&self) -> Option<&'w T> {
+        let component_id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_read(component_id)
+            // SAFETY: We have read access to the component.
+            .then(|| unsafe { self.entity.get::<T>() })
+            .flatten()
+    }
+
+    /// Gets the component of the given [`ComponentId`] from the entity.
+    /// Returns `None` if the entity does not have the component, or if the access doesn't
+    /// allow reading it.
+    ///
+    /// Unlike [`FilteredEntityRef::get`], this returns a raw pointer to the component,
+    /// which is only valid while the `'w` borrow of the lifetime is active.
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<Ptr<'w>> {
+        self.access
+            .has_read(component_id)
+            // SAFETY: We have read access to the component.
+            .then(|| unsafe { self.entity.get_by_id(component_id) })
+            .flatten()
+    }
+}
+
 impl<'w> From<EntityWorldMut<'w>> for EntityRef<'w> {
     fn from(entity_mut: EntityWorldMut<'w>) -> EntityRef<'w> {
         // SAFETY:
@@ -14,6 +14,21 @@ use super::unsafe_world_cell::UnsafeWorldCell;
 ///
 /// The trait [`FromWorld`] is implemented for this type, which returns the
 /// ID of the world passed to [`FromWorld::from_world`].
+///
+/// It can also be used as a [`SystemParam`] to get the ID of the world a system runs in,
+/// for example to check that a system runs in the expected world of a multi-world setup.
+/// It doesn't access any world data, so it never conflicts with other parameters.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::WorldId};
+/// #[derive(Resource)]
+/// struct ExpectedWorld(WorldId);
+///
+/// fn check_world(world_id: WorldId, expected: Res<ExpectedWorld>) {
+///     assert_eq!(world_id, expected.0, "running in the wrong world");
+/// }
+/// # bevy_ecs::system::assert_is_system(check_world);
+/// ```
 // Note that this *is* used by external crates as well as for internal safety checks
 pub struct WorldId(usize);
 
@@ -80,6 +95,7 @@ impl SparseSetIndex for WorldId {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::system::{In, IntoSystem, System};
 
     #[test]
     fn world_ids_unique() {
@@ -95,6 +111,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn world_id_system_param() {
+        fn assert_world_id(In(expected): In<WorldId>, world_id: WorldId) {
+            assert_eq!(expected, world_id);
+        }
+
+        let mut world = World::new();
+        let mut system = IntoSystem::into_system(assert_world_id);
+        system.initialize(&mut world);
+        system.run(world.id(), &mut world);
+
+        let mut other_world = World::new();
+        let mut system = IntoSystem::into_system(assert_world_id);
+        system.initialize(&mut other_world);
+        system.run(other_world.id(), &mut other_world);
+        assert_ne!(world.id(), other_world.id());
+    }
+
     // We cannot use this test as-is, as it causes other tests to panic due to using the same atomic variable.
     // #[test]
     // #[should_panic]
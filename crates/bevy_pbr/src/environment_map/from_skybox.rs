@@ -0,0 +1,511 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AssetId, Assets, Handle};
+use bevy_core_pipeline::{
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state, Skybox, SkyboxProjection,
+};
+use bevy_ecs::{
+    prelude::{Component, Entity},
+    query::With,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    main_graph::node::CAMERA_DRIVER,
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_resource::{
+        AddressMode, BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingType, BufferBindingType, CachedRenderPipelineId,
+        ColorTargetState, ColorWrites, Extent3d, FilterMode, FragmentState, LoadOp,
+        MultisampleState, Operations, PipelineCache, PrimitiveState, RenderPassColorAttachment,
+        RenderPassDescriptor, RenderPipelineDescriptor, Sampler, SamplerBindingType,
+        SamplerDescriptor, Shader, ShaderStages, ShaderType, TextureDescriptor, TextureDimension,
+        TextureFormat, TextureId, TextureSampleType, TextureUsages, TextureView,
+        TextureViewDescriptor, TextureViewDimension, UniformBuffer,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    texture::{Image, ImageSampler},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::{tracing::warn, HashMap, HashSet};
+
+use super::EnvironmentMapLight;
+
+const PREFILTER_SKYBOX_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(91862375519704738);
+
+/// The size of the faces of the prefiltered diffuse map.
+///
+/// Irradiance varies slowly with the direction, so a small map is enough.
+const DIFFUSE_MAP_SIZE: u32 = 32;
+/// The size of the faces of the prefiltered specular map, unless the skybox is smaller.
+const SPECULAR_MAP_SIZE: u32 = 256;
+/// The format of the prefiltered maps, which keeps the range of HDR skyboxes.
+const PREFILTERED_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The render graph node prefiltering the skyboxes of [`SkyboxAsEnvironment`] cameras.
+pub const PREFILTER_SKYBOX: &str = "prefilter_skybox";
+
+/// Lights the scene with the [`Skybox`] of a camera, by adding an [`EnvironmentMapLight`]
+/// prefiltered from it.
+///
+/// When added to a camera with a cubemap [`Skybox`], the skybox image is prefiltered on the GPU
+/// into a diffuse and a specular map once it is loaded, which are then inserted as the
+/// [`EnvironmentMapLight`] of the camera. This avoids authoring and loading a prefiltered
+/// copy of the same environment.
+///
+/// Each skybox image is only prefiltered once, and cameras sharing it share the maps. They are
+/// prefiltered again if the image is modified. Changing [`Skybox::image`] prefilters the new
+/// image and replaces the [`EnvironmentMapLight`], while removing this component leaves the
+/// current one in place.
+///
+/// The maps are prefiltered from the image as is: [`Skybox::rotation`], [`Skybox::brightness`]
+/// and the other display settings of the skybox don't affect the lighting. Equirectangular
+/// skyboxes and cubemap arrays aren't supported, and neither are images whose format can't be
+/// filtered.
+#[derive(Component, Reflect, Clone, Copy, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct SkyboxAsEnvironment;
+
+pub(super) struct SkyboxEnvironmentPlugin;
+
+impl Plugin for SkyboxEnvironmentPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            PREFILTER_SKYBOX_SHADER_HANDLE,
+            "prefilter_skybox.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<SkyboxAsEnvironment>()
+            .init_resource::<SkyboxEnvironmentMaps>()
+            .add_plugins(ExtractResourcePlugin::<SkyboxPrefilterJobs>::default())
+            .add_systems(PostUpdate, add_skybox_environment_maps);
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SkyboxPrefilterPasses>()
+            .add_systems(
+                Render,
+                prepare_skybox_prefilter_passes.in_set(RenderSet::PrepareBindGroups),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<SkyboxPrefilterPipeline>();
+
+        // The environment maps are used by the cameras, so they are prefiltered first.
+        let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+        render_graph.add_node(PREFILTER_SKYBOX, SkyboxPrefilterNode);
+        render_graph.add_node_edge(PREFILTER_SKYBOX, CAMERA_DRIVER);
+    }
+}
+
+/// The environment maps prefiltered from skybox images.
+#[derive(Resource, Default)]
+struct SkyboxEnvironmentMaps {
+    maps: HashMap<AssetId<Image>, EnvironmentMapLight>,
+    /// The images that can't be prefiltered, which were already warned about.
+    invalid: HashSet<AssetId<Image>>,
+}
+
+/// Inserts the [`EnvironmentMapLight`] prefiltered from their [`Skybox`] on
+/// [`SkyboxAsEnvironment`] cameras, creating the maps of new skybox images.
+fn add_skybox_environment_maps(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut environment_maps: ResMut<SkyboxEnvironmentMaps>,
+    cameras: Query<(Entity, &Skybox, Option<&EnvironmentMapLight>), With<SkyboxAsEnvironment>>,
+) {
+    // Drop the maps of skybox images that no longer exist.
+    let environment_maps = &mut *environment_maps;
+    environment_maps
+        .maps
+        .retain(|source, _| images.contains(*source));
+
+    for (entity, skybox, environment_map_light) in &cameras {
+        let source = skybox.image.id();
+        if !environment_maps.maps.contains_key(&source) {
+            // Wait for the skybox image to be loaded.
+            let Some(image) = images.get(source) else {
+                continue;
+            };
+            match prefiltered_maps(skybox.projection, image) {
+                Ok((diffuse_map, specular_map)) => {
+                    let environment_map_light = EnvironmentMapLight {
+                        diffuse_map: images.add(diffuse_map),
+                        specular_map: images.add(specular_map),
+                    };
+                    environment_maps.maps.insert(source, environment_map_light);
+                }
+                Err(err) => {
+                    if environment_maps.invalid.insert(source) {
+                        warn!(
+                            "The skybox of {entity:?} can't be used as an environment map: {err}"
+                        );
+                    }
+                    continue;
+                }
+            }
+        }
+
+        let prefiltered = &environment_maps.maps[&source];
+        if environment_map_light.map_or(true, |current| {
+            current.diffuse_map != prefiltered.diffuse_map
+                || current.specular_map != prefiltered.specular_map
+        }) {
+            commands.entity(entity).insert(prefiltered.clone());
+        }
+    }
+}
+
+/// Creates the images the diffuse and specular maps of a skybox `image` are prefiltered into,
+/// or returns an error message if the image can't be prefiltered.
+fn prefiltered_maps(projection: SkyboxProjection, image: &Image) -> Result<(Image, Image), String> {
+    let descriptor = &image.texture_descriptor;
+    if projection != SkyboxProjection::Cubemap
+        || descriptor.dimension != TextureDimension::D2
+        || descriptor.size.depth_or_array_layers != 6
+        || descriptor.size.width != descriptor.size.height
+    {
+        return Err("only single cubemaps can be prefiltered".into());
+    }
+    if descriptor.format.sample_type(None) != Some(TextureSampleType::Float { filterable: true }) {
+        return Err(format!(
+            "its format {:?} is not filterable",
+            descriptor.format
+        ));
+    }
+
+    let specular_size = descriptor.size.width.min(SPECULAR_MAP_SIZE);
+    // The specular map has a full mip chain, whose levels are sampled by roughness.
+    let specular_mips = specular_size.ilog2() + 1;
+    Ok((
+        prefiltered_image("skybox_environment_map_diffuse", DIFFUSE_MAP_SIZE, 1),
+        prefiltered_image(
+            "skybox_environment_map_specular",
+            specular_size,
+            specular_mips,
+        ),
+    ))
+}
+
+/// Creates a cubemap image that is rendered to by the prefilter passes.
+fn prefiltered_image(label: &'static str, size: u32, mip_level_count: u32) -> Image {
+    // The texture is created with data, which is overwritten once it is prefiltered.
+    let data_len = (0..mip_level_count)
+        .map(|mip| (size >> mip).max(1).pow(2) as usize)
+        .sum::<usize>()
+        * 6
+        * PREFILTERED_FORMAT.pixel_size();
+
+    Image {
+        data: vec![0; data_len],
+        texture_descriptor: TextureDescriptor {
+            label: Some(label),
+            size: Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 6,
+            },
+            mip_level_count,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: PREFILTERED_FORMAT,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+        sampler_descriptor: ImageSampler::Default,
+        texture_view_descriptor: Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        }),
+    }
+}
+
+/// The skybox images to prefilter, and the images of their maps, extracted to the render world.
+#[derive(Resource, Default)]
+struct SkyboxPrefilterJobs(Vec<SkyboxPrefilterJob>);
+
+#[derive(Clone, Copy)]
+struct SkyboxPrefilterJob {
+    source: AssetId<Image>,
+    diffuse_map: AssetId<Image>,
+    specular_map: AssetId<Image>,
+}
+
+impl ExtractResource for SkyboxPrefilterJobs {
+    type Source = SkyboxEnvironmentMaps;
+
+    fn extract_resource(source: &Self::Source) -> Self {
+        Self(
+            source
+                .maps
+                .iter()
+                .map(|(source, maps)| SkyboxPrefilterJob {
+                    source: *source,
+                    diffuse_map: maps.diffuse_map.id(),
+                    specular_map: maps.specular_map.id(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The parameters of a prefilter pass, used by `prefilter_skybox.wgsl`.
+#[derive(ShaderType)]
+struct SkyboxPrefilterParams {
+    /// The cube face rendered to.
+    face: u32,
+    /// The roughness the specular map is prefiltered for, unused for the diffuse map.
+    roughness: f32,
+}
+
+#[derive(Resource)]
+struct SkyboxPrefilterPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    diffuse_pipeline_id: CachedRenderPipelineId,
+    specular_pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for SkyboxPrefilterPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("prefilter_skybox_bind_group_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::Cube,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(SkyboxPrefilterParams::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("prefilter_skybox_sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let mut pipeline_cache = world.resource_mut::<PipelineCache>();
+        let mut queue_pipeline = |label: &'static str, shader_defs: Vec<_>| {
+            pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
+                label: Some(label.into()),
+                layout: vec![layout.clone()],
+                vertex: fullscreen_shader_vertex_state(),
+                fragment: Some(FragmentState {
+                    shader: PREFILTER_SKYBOX_SHADER_HANDLE,
+                    shader_defs,
+                    entry_point: "fragment".into(),
+                    targets: vec![Some(ColorTargetState {
+                        format: PREFILTERED_FORMAT,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    })],
+                }),
+                primitive: PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+                push_constant_ranges: vec![],
+            })
+        };
+        let diffuse_pipeline_id =
+            queue_pipeline("prefilter_skybox_diffuse_pipeline", vec!["DIFFUSE".into()]);
+        let specular_pipeline_id = queue_pipeline("prefilter_skybox_specular_pipeline", vec![]);
+
+        Self {
+            layout,
+            sampler,
+            diffuse_pipeline_id,
+            specular_pipeline_id,
+        }
+    }
+}
+
+/// The prefilter passes to run this frame, and the textures that were already prefiltered.
+#[derive(Resource, Default)]
+struct SkyboxPrefilterPasses {
+    passes: Vec<SkyboxPrefilterPass>,
+    /// The source, diffuse and specular textures of the prefiltered maps.
+    ///
+    /// Modified images are prepared into new textures, which are then prefiltered again.
+    prefiltered: HashSet<(TextureId, TextureId, TextureId)>,
+}
+
+/// Renders one mip level of one face of a prefiltered map.
+struct SkyboxPrefilterPass {
+    pipeline_id: CachedRenderPipelineId,
+    bind_group: BindGroup,
+    target: TextureView,
+}
+
+/// Queues the prefilter passes of the maps whose textures weren't prefiltered yet.
+fn prepare_skybox_prefilter_passes(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    pipeline_cache: Res<PipelineCache>,
+    pipeline: Res<SkyboxPrefilterPipeline>,
+    images: Res<RenderAssets<Image>>,
+    jobs: Res<SkyboxPrefilterJobs>,
+    mut prefilter_passes: ResMut<SkyboxPrefilterPasses>,
+) {
+    prefilter_passes.passes.clear();
+
+    // The maps are only marked as prefiltered once their passes can run.
+    if pipeline_cache
+        .get_render_pipeline(pipeline.diffuse_pipeline_id)
+        .is_none()
+        || pipeline_cache
+            .get_render_pipeline(pipeline.specular_pipeline_id)
+            .is_none()
+    {
+        return;
+    }
+
+    let prefilter_passes = &mut *prefilter_passes;
+    for job in &jobs.0 {
+        let (Some(source), Some(diffuse_map), Some(specular_map)) = (
+            images.get(job.source),
+            images.get(job.diffuse_map),
+            images.get(job.specular_map),
+        ) else {
+            continue;
+        };
+        let textures = (
+            source.texture.id(),
+            diffuse_map.texture.id(),
+            specular_map.texture.id(),
+        );
+        if !prefilter_passes.prefiltered.insert(textures) {
+            continue;
+        }
+
+        // The view of the skybox image may not be a cube view.
+        let source_view = source.texture.create_view(&TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        });
+
+        let maps = [
+            (pipeline.diffuse_pipeline_id, diffuse_map),
+            (pipeline.specular_pipeline_id, specular_map),
+        ];
+        for (pipeline_id, map) in maps {
+            for mip in 0..map.mip_level_count {
+                // Each level of the specular map is sampled for a roughness between 0 and 1.
+                let roughness = mip as f32 / (map.mip_level_count - 1).max(1) as f32;
+                for face in 0..6 {
+                    let mut params = UniformBuffer::from(SkyboxPrefilterParams { face, roughness });
+                    params.write_buffer(&render_device, &render_queue);
+
+                    let bind_group = render_device.create_bind_group(
+                        "prefilter_skybox_bind_group",
+                        &pipeline.layout,
+                        &BindGroupEntries::sequential((
+                            &source_view,
+                            &pipeline.sampler,
+                            params.binding().unwrap(),
+                        )),
+                    );
+                    let target = map.texture.create_view(&TextureViewDescriptor {
+                        label: Some("prefilter_skybox_target"),
+                        dimension: Some(TextureViewDimension::D2),
+                        base_mip_level: mip,
+                        mip_level_count: Some(1),
+                        base_array_layer: face,
+                        array_layer_count: Some(1),
+                        ..Default::default()
+                    });
+
+                    prefilter_passes.passes.push(SkyboxPrefilterPass {
+                        pipeline_id,
+                        bind_group,
+                        target,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Runs the prefilter passes queued for this frame.
+struct SkyboxPrefilterNode;
+
+impl Node for SkyboxPrefilterNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let prefilter_passes = world.resource::<SkyboxPrefilterPasses>();
+
+        for pass in &prefilter_passes.passes {
+            // The pipelines were checked when the passes were queued.
+            let Some(pipeline) = pipeline_cache.get_render_pipeline(pass.pipeline_id) else {
+                continue;
+            };
+
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("prefilter_skybox_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &pass.target,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Default::default()),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            render_pass.set_render_pipeline(pipeline);
+            render_pass.set_bind_group(0, &pass.bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
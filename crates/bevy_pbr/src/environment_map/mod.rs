@@ -1,3 +1,9 @@
+mod from_skybox;
+
+pub use from_skybox::{SkyboxAsEnvironment, PREFILTER_SKYBOX};
+
+use from_skybox::SkyboxEnvironmentPlugin;
+
 use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, Handle};
 use bevy_core_pipeline::prelude::Camera3d;
@@ -27,8 +33,10 @@ impl Plugin for EnvironmentMapPlugin {
             Shader::from_wgsl
         );
 
-        app.register_type::<EnvironmentMapLight>()
-            .add_plugins(ExtractComponentPlugin::<EnvironmentMapLight>::default());
+        app.register_type::<EnvironmentMapLight>().add_plugins((
+            ExtractComponentPlugin::<EnvironmentMapLight>::default(),
+            SkyboxEnvironmentPlugin,
+        ));
     }
 }
 
@@ -46,6 +54,9 @@ impl Plugin for EnvironmentMapPlugin {
 /// The diffuse map uses the Lambertian distribution, and the specular map uses the GGX distribution.
 ///
 /// `KhronosGroup` also has several prefiltered environment maps that can be found [here](https://github.com/KhronosGroup/glTF-Sample-Environments).
+///
+/// Cameras with a cubemap `Skybox` can instead get an environment map prefiltered from it with
+/// [`SkyboxAsEnvironment`].
 #[derive(Component, Reflect, Clone, ExtractComponent)]
 #[extract_component_filter(With<Camera3d>)]
 pub struct EnvironmentMapLight {
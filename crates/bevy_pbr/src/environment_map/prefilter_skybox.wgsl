@@ -0,0 +1,102 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_pbr::utils::PI
+
+struct SkyboxPrefilterParams {
+    face: u32,
+    roughness: f32,
+}
+
+@group(0) @binding(0) var skybox: texture_cube<f32>;
+@group(0) @binding(1) var skybox_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: SkyboxPrefilterParams;
+
+const DIFFUSE_SAMPLE_COUNT: u32 = 512u;
+const SPECULAR_SAMPLE_COUNT: u32 = 256u;
+
+// The direction of the texel at `uv` of a cube face, in the space of the cube texture.
+fn cube_direction(face: u32, uv: vec2<f32>) -> vec3<f32> {
+    let st = uv * 2.0 - 1.0;
+    switch face {
+        case 0u: { return normalize(vec3(1.0, -st.y, -st.x)); }
+        case 1u: { return normalize(vec3(-1.0, -st.y, st.x)); }
+        case 2u: { return normalize(vec3(st.x, 1.0, st.y)); }
+        case 3u: { return normalize(vec3(st.x, -1.0, -st.y)); }
+        case 4u: { return normalize(vec3(st.x, -st.y, 1.0)); }
+        default: { return normalize(vec3(-st.x, -st.y, -1.0)); }
+    }
+}
+
+// A low discrepancy sequence of points in the unit square.
+fn hammersley(i: u32, count: u32) -> vec2<f32> {
+    return vec2(f32(i) / f32(count), f32(reverseBits(i)) * 2.3283064365386963e-10);
+}
+
+// An orthonormal basis whose z axis is `normal`.
+fn tangent_frame(normal: vec3<f32>) -> mat3x3<f32> {
+    let up = select(vec3(1.0, 0.0, 0.0), vec3(0.0, 0.0, 1.0), abs(normal.z) < 0.999);
+    let tangent = normalize(cross(up, normal));
+    let bitangent = cross(normal, tangent);
+    return mat3x3(tangent, bitangent, normal);
+}
+
+// The mip level of the skybox to sample for a sample with the given probability density, so
+// that the samples together cover the texels around them instead of aliasing.
+fn sample_level(pdf: f32, sample_count: u32) -> f32 {
+    let size = f32(textureDimensions(skybox).x);
+    let texel_solid_angle = 4.0 * PI / (6.0 * size * size);
+    let sample_solid_angle = 1.0 / (f32(sample_count) * pdf + 0.0001);
+    return max(0.5 * log2(sample_solid_angle / texel_solid_angle) + 1.0, 0.0);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let normal = cube_direction(params.face, in.uv);
+    let frame = tangent_frame(normal);
+    var color = vec3(0.0);
+    var weight = 0.0;
+
+#ifdef DIFFUSE
+    // Cosine weighted samples of the hemisphere around the normal, whose average is the
+    // irradiance divided by PI, i.e. the light reflected by a white lambertian surface.
+    for (var i = 0u; i < DIFFUSE_SAMPLE_COUNT; i += 1u) {
+        let xi = hammersley(i, DIFFUSE_SAMPLE_COUNT);
+        let phi = 2.0 * PI * xi.x;
+        let cos_theta = sqrt(1.0 - xi.y);
+        let sin_theta = sqrt(xi.y);
+        let light = frame * vec3(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta);
+        let level = sample_level(cos_theta / PI, DIFFUSE_SAMPLE_COUNT);
+        color += textureSampleLevel(skybox, skybox_sampler, light, level).rgb;
+        weight += 1.0;
+    }
+#else
+    if params.roughness == 0.0 {
+        // A perfect mirror only reflects the skybox itself.
+        return vec4(textureSampleLevel(skybox, skybox_sampler, normal, 0.0).rgb, 1.0);
+    }
+
+    // GGX importance samples around the normal, which is assumed to be the view and reflection
+    // direction as well, as in the split-sum approximation.
+    let alpha = params.roughness * params.roughness;
+    let alpha2 = alpha * alpha;
+    for (var i = 0u; i < SPECULAR_SAMPLE_COUNT; i += 1u) {
+        let xi = hammersley(i, SPECULAR_SAMPLE_COUNT);
+        let phi = 2.0 * PI * xi.x;
+        let cos_theta = sqrt((1.0 - xi.y) / (1.0 + (alpha2 - 1.0) * xi.y));
+        let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+        let half_vector = frame * vec3(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta);
+        let light = 2.0 * dot(normal, half_vector) * half_vector - normal;
+        let n_dot_l = dot(normal, light);
+        if n_dot_l > 0.0 {
+            // With the view direction along the normal, the density of the reflected direction
+            // is the GGX distribution divided by 4.
+            let d = cos_theta * cos_theta * (alpha2 - 1.0) + 1.0;
+            let distribution = alpha2 / (PI * d * d);
+            let level = sample_level(distribution / 4.0, SPECULAR_SAMPLE_COUNT);
+            color += textureSampleLevel(skybox, skybox_sampler, light, level).rgb * n_dot_l;
+            weight += n_dot_l;
+        }
+    }
+#endif
+
+    return vec4(color / max(weight, 0.0001), 1.0);
+}
@@ -18,7 +18,7 @@ mod ssao;
 
 pub use alpha::*;
 pub use bundle::*;
-pub use environment_map::EnvironmentMapLight;
+pub use environment_map::{EnvironmentMapLight, SkyboxAsEnvironment, PREFILTER_SKYBOX};
 pub use extended_material::*;
 pub use fog::*;
 pub use light::*;
@@ -37,7 +37,7 @@ pub mod prelude {
             DirectionalLightBundle, MaterialMeshBundle, PbrBundle, PointLightBundle,
             SpotLightBundle,
         },
-        environment_map::EnvironmentMapLight,
+        environment_map::{EnvironmentMapLight, SkyboxAsEnvironment},
         fog::{FogFalloff, FogSettings},
         light::{AmbientLight, DirectionalLight, PointLight, SpotLight},
         material::{Material, MaterialPlugin},
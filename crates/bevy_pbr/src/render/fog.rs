@@ -1,5 +1,6 @@
 use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, Handle};
+use bevy_core_pipeline::{Skybox, SkyboxFog};
 use bevy_ecs::prelude::*;
 use bevy_math::{Vec3, Vec4};
 use bevy_render::{
@@ -7,7 +8,7 @@ use bevy_render::{
     render_resource::{DynamicUniformBuffer, Shader, ShaderType},
     renderer::{RenderDevice, RenderQueue},
     view::ExtractedView,
-    Render, RenderApp, RenderSet,
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
 
 use crate::{FogFalloff, FogSettings};
@@ -126,6 +127,18 @@ pub fn prepare_fog(
     }
 }
 
+/// Extracts the fog color of cameras with a [`Skybox`], for skyboxes that fade into the fog
+pub fn extract_skybox_fog(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &FogSettings), With<Skybox>>>,
+) {
+    for (entity, fog) in &cameras {
+        commands.get_or_spawn(entity).insert(SkyboxFog {
+            color: fog.color.as_linear_rgba_f32().into(),
+        });
+    }
+}
+
 /// Inserted on each `Entity` with an `ExtractedView` to keep track of its offset
 /// in the `gpu_fogs` `DynamicUniformBuffer` within `FogMeta`
 #[derive(Component)]
@@ -149,6 +162,7 @@ impl Plugin for FogPlugin {
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<FogMeta>()
+                .add_systems(ExtractSchedule, extract_skybox_fog)
                 .add_systems(Render, prepare_fog.in_set(RenderSet::PrepareResources));
         }
     }
@@ -75,7 +75,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             ..default()
         },
         CameraController::default(),
-        Skybox(skybox_handle.clone()),
+        Skybox::new(skybox_handle.clone()),
     ));
 
     // ambient light
@@ -154,7 +154,7 @@ fn asset_loaded(
         }
 
         for mut skybox in &mut skyboxes {
-            skybox.0 = cubemap.image_handle.clone();
+            skybox.image = cubemap.image_handle.clone();
         }
 
         cubemap.is_loaded = true;